@@ -0,0 +1,185 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv4Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::ether;
+use crate::arp::{Packet, Hardware, Operation};
+
+/// ARP packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		use crate::size::header::Min;
+		buffer.next(Packet::<()>::min())?;
+
+		// Default to Ethernet and IPv4, the only supported address lengths.
+		Packet::unchecked(buffer.data_mut())
+			.set_hardware(Hardware::Ethernet)?
+			.set_protocol(ether::Protocol::Ipv4)?;
+
+		buffer.data_mut()[4] = 6;
+		buffer.data_mut()[5] = 4;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(self) -> Result<B::Inner> {
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Hardware type.
+	pub fn hardware(mut self, value: Hardware) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_hardware(value)?;
+		Ok(self)
+	}
+
+	/// Protocol type.
+	pub fn protocol(mut self, value: ether::Protocol) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_protocol(value)?;
+		Ok(self)
+	}
+
+	/// Operation.
+	pub fn operation(mut self, value: Operation) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_operation(value)?;
+		Ok(self)
+	}
+
+	/// Make it a request.
+	pub fn request(self) -> Result<Self> {
+		self.operation(Operation::Request)
+	}
+
+	/// Make it a reply.
+	pub fn reply(self) -> Result<Self> {
+		self.operation(Operation::Reply)
+	}
+
+	/// Hardware address of the sender.
+	pub fn sender_hardware_address(mut self, value: HwAddr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_sender_hardware_address(value)?;
+		Ok(self)
+	}
+
+	/// Protocol address of the sender.
+	pub fn sender_protocol_address(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_sender_protocol_address(value)?;
+		Ok(self)
+	}
+
+	/// Hardware address of the target.
+	pub fn target_hardware_address(mut self, value: HwAddr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_target_hardware_address(value)?;
+		Ok(self)
+	}
+
+	/// Protocol address of the target.
+	pub fn target_protocol_address(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_target_protocol_address(value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::arp;
+
+	#[test]
+	fn reply() {
+		let packet = ether::Builder::default()
+			.destination("00:0c:29:3c:1c:1a".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.arp().unwrap()
+				.reply().unwrap()
+				.sender_hardware_address("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+				.sender_protocol_address("192.168.0.2".parse().unwrap()).unwrap()
+				.target_hardware_address("00:0c:29:3c:1c:1a".parse().unwrap()).unwrap()
+				.target_protocol_address("192.168.0.1".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		let ether = ether::Packet::new(&packet[..]).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Arp);
+
+		let arp = arp::Packet::new(ether.payload()).unwrap();
+		assert_eq!(arp.hardware(), arp::Hardware::Ethernet);
+		assert_eq!(arp.protocol(), ether::Protocol::Ipv4);
+		assert_eq!(arp.operation(), arp::Operation::Reply);
+		assert_eq!(arp.sender_hardware_address(), "e4:b3:18:26:63:a3".parse().unwrap());
+		assert_eq!(arp.sender_protocol_address(), "192.168.0.2".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(arp.target_hardware_address(), "00:0c:29:3c:1c:1a".parse().unwrap());
+		assert_eq!(arp.target_protocol_address(), "192.168.0.1".parse::<Ipv4Addr>().unwrap());
+	}
+
+	#[test]
+	fn gratuitous() {
+		let packet = ether::Builder::default()
+			.destination("ff:ff:ff:ff:ff:ff".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.arp().unwrap()
+				.request().unwrap()
+				.sender_hardware_address("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+				.sender_protocol_address("192.168.0.2".parse().unwrap()).unwrap()
+				.target_protocol_address("192.168.0.2".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		let ether = ether::Packet::new(&packet[..]).unwrap();
+		let arp   = arp::Packet::new(ether.payload()).unwrap();
+		assert_eq!(arp.operation(), arp::Operation::Request);
+		assert!(arp.is_gratuitous());
+	}
+}
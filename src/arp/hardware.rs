@@ -0,0 +1,50 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// ARP hardware types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Hardware {
+	/// Ethernet.
+	Ethernet,
+
+	/// IEEE 802 networks.
+	Ieee802,
+
+	/// Unknown hardware type.
+	Unknown(u16),
+}
+
+impl From<u16> for Hardware {
+	fn from(value: u16) -> Hardware {
+		use self::Hardware::*;
+
+		match value {
+			1 => Ethernet,
+			6 => Ieee802,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<Hardware> for u16 {
+	fn from(value: Hardware) -> u16 {
+		use self::Hardware::*;
+
+		match value {
+			Ethernet   => 1,
+			Ieee802    => 6,
+			Unknown(n) => n,
+		}
+	}
+}
@@ -0,0 +1,60 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// ARP operations.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Operation {
+	/// ARP request.
+	Request,
+
+	/// ARP reply.
+	Reply,
+
+	/// RARP request.
+	ReverseRequest,
+
+	/// RARP reply.
+	ReverseReply,
+
+	/// Unknown operation.
+	Unknown(u16),
+}
+
+impl From<u16> for Operation {
+	fn from(value: u16) -> Operation {
+		use self::Operation::*;
+
+		match value {
+			1 => Request,
+			2 => Reply,
+			3 => ReverseRequest,
+			4 => ReverseReply,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<Operation> for u16 {
+	fn from(value: Operation) -> u16 {
+		use self::Operation::*;
+
+		match value {
+			Request        => 1,
+			Reply          => 2,
+			ReverseRequest => 3,
+			ReverseReply   => 4,
+			Unknown(n)     => n,
+		}
+	}
+}
@@ -0,0 +1,285 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ether;
+use crate::arp::{Hardware, Operation};
+
+/// ARP packet parser.
+///
+/// # Note
+///
+/// Only ARP packets mapping 6 octet hardware addresses to IPv4 addresses are
+/// supported.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  28,
+		max:  28,
+		size: 28,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("arp::Packet")
+			.field("hardware", &self.hardware())
+			.field("protocol", &self.protocol())
+			.field("operation", &self.operation())
+			.field("sender_hardware_address", &self.sender_hardware_address())
+			.field("sender_protocol_address", &self.sender_protocol_address())
+			.field("target_hardware_address", &self.target_hardware_address())
+			.field("target_protocol_address", &self.target_protocol_address())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create an ARP packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse an ARP packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if packet.hardware_length() != 6 || packet.protocol_length() != 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		&self.buffer.as_ref()[.. 28]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.buffer.as_mut()[.. 28]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref()[.. 28].split_at(28)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut()[.. 28].split_at_mut(28)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Hardware type.
+	pub fn hardware(&self) -> Hardware {
+		(&self.buffer.as_ref()[0 ..]).read_u16::<BigEndian>().unwrap().into()
+	}
+
+	/// Protocol type.
+	pub fn protocol(&self) -> ether::Protocol {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap().into()
+	}
+
+	/// Length of hardware addresses.
+	pub fn hardware_length(&self) -> u8 {
+		self.buffer.as_ref()[4]
+	}
+
+	/// Length of protocol addresses.
+	pub fn protocol_length(&self) -> u8 {
+		self.buffer.as_ref()[5]
+	}
+
+	/// Operation.
+	pub fn operation(&self) -> Operation {
+		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap().into()
+	}
+
+	/// Hardware address of the sender.
+	pub fn sender_hardware_address(&self) -> HwAddr {
+		self.buffer.as_ref()[8 .. 14].into()
+	}
+
+	/// Protocol address of the sender.
+	pub fn sender_protocol_address(&self) -> Ipv4Addr {
+		let buffer = self.buffer.as_ref();
+		Ipv4Addr::new(buffer[14], buffer[15], buffer[16], buffer[17])
+	}
+
+	/// Hardware address of the target.
+	pub fn target_hardware_address(&self) -> HwAddr {
+		self.buffer.as_ref()[18 .. 24].into()
+	}
+
+	/// Protocol address of the target.
+	pub fn target_protocol_address(&self) -> Ipv4Addr {
+		let buffer = self.buffer.as_ref();
+		Ipv4Addr::new(buffer[24], buffer[25], buffer[26], buffer[27])
+	}
+
+	/// Check if it's a gratuitous ARP, where sender and target protocol
+	/// addresses are the same.
+	pub fn is_gratuitous(&self) -> bool {
+		self.sender_protocol_address() == self.target_protocol_address()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Hardware type.
+	pub fn set_hardware(&mut self, value: Hardware) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[0 ..])
+			.write_u16::<BigEndian>(value.into())?;
+
+		Ok(self)
+	}
+
+	/// Protocol type.
+	pub fn set_protocol(&mut self, value: ether::Protocol) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>(value.into())?;
+
+		Ok(self)
+	}
+
+	/// Operation.
+	pub fn set_operation(&mut self, value: Operation) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[6 ..])
+			.write_u16::<BigEndian>(value.into())?;
+
+		Ok(self)
+	}
+
+	/// Hardware address of the sender.
+	pub fn set_sender_hardware_address(&mut self, value: HwAddr) -> Result<&mut Self> {
+		self.buffer.as_mut()[8 .. 14].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Protocol address of the sender.
+	pub fn set_sender_protocol_address(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[14 .. 18].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Hardware address of the target.
+	pub fn set_target_hardware_address(&mut self, value: HwAddr) -> Result<&mut Self> {
+		self.buffer.as_mut()[18 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Protocol address of the target.
+	pub fn set_target_protocol_address(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[24 .. 28].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::arp;
+
+	#[test]
+	fn values() {
+		let raw = hex::decode("ffffffffffff000c293c1c1a08060001080006040001000c293c1c1ac0a80001000000000000c0a80002").unwrap();
+
+		let ether = ether::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Arp);
+
+		let arp = arp::Packet::new(ether.payload()).unwrap();
+		assert_eq!(arp.hardware(), arp::Hardware::Ethernet);
+		assert_eq!(arp.protocol(), ether::Protocol::Ipv4);
+		assert_eq!(arp.operation(), arp::Operation::Request);
+		assert_eq!(arp.sender_hardware_address(), "00:0c:29:3c:1c:1a".parse().unwrap());
+		assert_eq!(arp.sender_protocol_address(), "192.168.0.1".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(arp.target_hardware_address(), "00:00:00:00:00:00".parse().unwrap());
+		assert_eq!(arp.target_protocol_address(), "192.168.0.2".parse::<Ipv4Addr>().unwrap());
+		assert!(!arp.is_gratuitous());
+	}
+
+	#[test]
+	fn mutable() {
+		let mut raw = hex::decode("0001080006040001000c293c1c1ac0a80001000000000000c0a80002").unwrap();
+		let mut arp = arp::Packet::new(&mut raw[..]).unwrap();
+
+		arp.set_operation(arp::Operation::Reply).unwrap();
+		arp.set_target_hardware_address("e4:b3:18:26:63:a3".parse().unwrap()).unwrap();
+		assert_eq!(arp.operation(), arp::Operation::Reply);
+		assert_eq!(arp.target_hardware_address(), "e4:b3:18:26:63:a3".parse().unwrap());
+	}
+
+	#[test]
+	fn unsupported() {
+		let raw = hex::decode("000186dd06100001000c293c1c1ac0a80001000000000000c0a80002").unwrap();
+		assert!(arp::Packet::new(&raw[..]).is_err());
+	}
+}
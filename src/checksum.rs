@@ -0,0 +1,84 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use crate::error::*;
+
+/// Update an Internet checksum after the covered data changed from `old` to
+/// `new`, as described in RFC 1624.
+///
+/// # Note
+///
+/// Both slices must have the same length and start at an even offset of the
+/// checksummed data.
+pub fn update(checksum: u16, old: &[u8], new: &[u8]) -> u16 {
+	fn word(chunk: &[u8]) -> u32 {
+		u32::from(chunk[0]) << 8 | u32::from(chunk.get(1).cloned().unwrap_or(0))
+	}
+
+	// HC' = ~(~HC + ~m + m')
+	let mut result = u32::from(!checksum);
+
+	for (old, new) in old.chunks(2).zip(new.chunks(2)) {
+		result += !word(old) & 0xffff;
+		result += word(new);
+	}
+
+	while result > 0xffff {
+		result = (result & 0xffff) + (result >> 16);
+	}
+
+	!result as u16
+}
+
+/// Apply `f` to the packet and `adjust` its checksum for the `length` bytes
+/// changed at `offset`.
+///
+/// # Note
+///
+/// The changed bytes are widened to 16-bit boundaries, so at most 3 bytes can
+/// change at once.
+pub fn change<P, F, A>(packet: &mut P, offset: usize, length: usize, f: F, adjust: A) -> Result<()>
+	where P: AsRef<[u8]>,
+	      F: FnOnce(&mut P) -> Result<&mut P>,
+	      A: for<'a> FnOnce(&'a mut P, &[u8], &[u8]) -> Result<&'a mut P>
+{
+	let start   = offset & !1;
+	let end     = (offset + length + 1) & !1;
+	let mut old = [0u8; 4];
+	old[.. end - start].copy_from_slice(&packet.as_ref()[start .. end]);
+
+	f(packet)?;
+
+	let mut new = [0u8; 4];
+	new[.. end - start].copy_from_slice(&packet.as_ref()[start .. end]);
+	adjust(packet, &old[.. end - start], &new[.. end - start])?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use crate::checksum::update;
+
+	#[test]
+	fn rfc1624() {
+		// Example from section 4 of RFC 1624.
+		assert_eq!(update(0xdd2f, &[0x55, 0x55], &[0x32, 0x85]), 0x0000);
+	}
+
+	#[test]
+	fn identity() {
+		assert_eq!(update(0x1234, &[1, 2, 3, 4], &[1, 2, 3, 4]), 0x1234);
+	}
+}
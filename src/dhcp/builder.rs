@@ -0,0 +1,278 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::net::Ipv4Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::arp::Hardware;
+use crate::dhcp::{Packet, Operation, MessageType, COOKIE, MINIMUM};
+use crate::dhcp::option::Number;
+
+/// DHCP message builder.
+///
+/// # Note
+///
+/// Options are written in the order they're given, the message is terminated
+/// by an End option and padded to the minimum BOOTP size.
+#[derive(Debug)]
+pub struct Builder {
+	buffer:    Vec<u8>,
+	options:   Vec<Number>,
+	operation: bool,
+}
+
+impl Default for Builder {
+	fn default() -> Self {
+		let mut buffer = vec![0; 240];
+
+		Packet::unchecked(&mut buffer[..])
+			.set_operation(Operation::Request).unwrap()
+			.set_client_hardware(Hardware::Ethernet, &[0; 6]).unwrap()
+			.set_cookie(COOKIE).unwrap();
+
+		Builder {
+			buffer,
+			options:   Vec::new(),
+			operation: false,
+		}
+	}
+}
+
+impl Builder {
+	/// Operation, set from the message type when not given.
+	pub fn operation(mut self, value: Operation) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_operation(value)?;
+		self.operation = true;
+
+		Ok(self)
+	}
+
+	/// Ethernet address of the client.
+	pub fn client_hardware_address(mut self, value: HwAddr) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_client_hardware(Hardware::Ethernet, &value.octets())?;
+		Ok(self)
+	}
+
+	/// Transaction identifier.
+	pub fn transaction_id(mut self, value: u32) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_transaction_id(value)?;
+		Ok(self)
+	}
+
+	/// Seconds elapsed since the client began the exchange.
+	pub fn seconds(mut self, value: u16) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_seconds(value)?;
+		Ok(self)
+	}
+
+	/// Ask for broadcast replies.
+	pub fn broadcast(mut self, value: bool) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_broadcast(value)?;
+		Ok(self)
+	}
+
+	/// Current address of the client.
+	pub fn client_address(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_client_address(value)?;
+		Ok(self)
+	}
+
+	/// Address assigned to the client.
+	pub fn your_address(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_your_address(value)?;
+		Ok(self)
+	}
+
+	/// Address of the next server to use.
+	pub fn server_address(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_server_address(value)?;
+		Ok(self)
+	}
+
+	/// Address of the relay agent.
+	pub fn gateway_address(mut self, value: Ipv4Addr) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_gateway_address(value)?;
+		Ok(self)
+	}
+
+	/// DHCP message type, also setting the matching operation unless given.
+	pub fn message_type(mut self, value: MessageType) -> Result<Self> {
+		let operation = match value {
+			MessageType::Offer | MessageType::Ack | MessageType::Nak =>
+				Operation::Reply,
+
+			_ =>
+				Operation::Request,
+		};
+
+		if !self.operation {
+			Packet::unchecked(&mut self.buffer[..]).set_operation(operation)?;
+		}
+
+		self.option(Number::MessageType, &[value.into()])
+	}
+
+	/// Address requested by the client.
+	pub fn requested_address(self, value: Ipv4Addr) -> Result<Self> {
+		self.option(Number::RequestedAddress, &value.octets())
+	}
+
+	/// Lease time in seconds.
+	pub fn lease_time(self, value: u32) -> Result<Self> {
+		self.option(Number::LeaseTime, &value.to_be_bytes())
+	}
+
+	/// Identifier of the server.
+	pub fn server_identifier(self, value: Ipv4Addr) -> Result<Self> {
+		self.option(Number::ServerIdentifier, &value.octets())
+	}
+
+	/// Subnet mask of the client.
+	pub fn subnet_mask(self, value: Ipv4Addr) -> Result<Self> {
+		self.option(Number::SubnetMask, &value.octets())
+	}
+
+	/// Options requested by the client.
+	pub fn parameter_request_list(self, value: &[Number]) -> Result<Self> {
+		let value = value.iter().map(|&n| n.into()).collect::<Vec<u8>>();
+		self.option(Number::ParameterRequestList, &value)
+	}
+
+	/// Routers on the subnet of the client.
+	pub fn routers(self, value: &[Ipv4Addr]) -> Result<Self> {
+		let value = value.iter().flat_map(|a| a.octets()).collect::<Vec<u8>>();
+		self.option(Number::Router, &value)
+	}
+
+	/// Domain name servers.
+	pub fn domain_name_servers(self, value: &[Ipv4Addr]) -> Result<Self> {
+		let value = value.iter().flat_map(|a| a.octets()).collect::<Vec<u8>>();
+		self.option(Number::DomainNameServer, &value)
+	}
+
+	/// Option with a raw payload.
+	pub fn option(mut self, number: Number, payload: &[u8]) -> Result<Self> {
+		if matches!(number, Number::Pad | Number::End) || payload.is_empty() || payload.len() > 255 {
+			Err(Error::InvalidValue)?
+		}
+
+		if self.options.contains(&number) {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.options.push(number);
+		self.buffer.push(number.into());
+		self.buffer.push(payload.len() as u8);
+		self.buffer.extend_from_slice(payload);
+
+		Ok(self)
+	}
+
+	/// Build the message.
+	pub fn build(mut self) -> Result<Vec<u8>> {
+		self.buffer.push(Number::End.into());
+
+		if self.buffer.len() < MINIMUM {
+			self.buffer.resize(MINIMUM, 0);
+		}
+
+		Ok(self.buffer)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::packet::Packet;
+	use crate::dhcp;
+	use crate::dhcp::option::Number;
+
+	#[test]
+	fn discover() {
+		let raw = dhcp::Builder::default()
+			.transaction_id(0x3903f326).unwrap()
+			.client_hardware_address("00:0b:82:01:fc:42".parse().unwrap()).unwrap()
+			.broadcast(true).unwrap()
+			.message_type(dhcp::MessageType::Discover).unwrap()
+			.requested_address(Ipv4Addr::new(0, 0, 0, 0)).unwrap()
+			.parameter_request_list(&[Number::SubnetMask, Number::Router, Number::DomainNameServer]).unwrap()
+			.build().unwrap();
+
+		assert_eq!(raw.len(), dhcp::MINIMUM);
+
+		let dhcp = dhcp::Packet::new(&raw[..]).unwrap();
+		assert_eq!(dhcp.operation(), dhcp::Operation::Request);
+		assert_eq!(dhcp.transaction_id(), 0x3903f326);
+		assert!(dhcp.is_broadcast());
+		assert_eq!(dhcp.client_hardware_address().unwrap(), "00:0b:82:01:fc:42".parse().unwrap());
+		assert_eq!(dhcp.message_type().unwrap(), Some(dhcp::MessageType::Discover));
+		assert_eq!(&dhcp.payload()[.. 3], &[53, 1, 1]);
+
+		let options = dhcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 3);
+		assert_eq!(options[2].parameter_request_list().unwrap().count(), 3);
+	}
+
+	#[test]
+	fn ack() {
+		let raw = dhcp::Builder::default()
+			.transaction_id(0x3903f326).unwrap()
+			.client_hardware_address("00:0b:82:01:fc:42".parse().unwrap()).unwrap()
+			.your_address(Ipv4Addr::new(192, 168, 0, 10)).unwrap()
+			.server_address(Ipv4Addr::new(192, 168, 0, 1)).unwrap()
+			.message_type(dhcp::MessageType::Ack).unwrap()
+			.server_identifier(Ipv4Addr::new(192, 168, 0, 1)).unwrap()
+			.lease_time(3600).unwrap()
+			.subnet_mask(Ipv4Addr::new(255, 255, 255, 0)).unwrap()
+			.routers(&[Ipv4Addr::new(192, 168, 0, 1)]).unwrap()
+			.domain_name_servers(&[Ipv4Addr::new(8, 8, 8, 8), Ipv4Addr::new(8, 8, 4, 4)]).unwrap()
+			.build().unwrap();
+
+		let dhcp = dhcp::Packet::new(&raw[..]).unwrap();
+		assert_eq!(dhcp.operation(), dhcp::Operation::Reply);
+		assert_eq!(dhcp.your_address(), Ipv4Addr::new(192, 168, 0, 10));
+		assert_eq!(dhcp.server_address(), Ipv4Addr::new(192, 168, 0, 1));
+		assert_eq!(dhcp.message_type().unwrap(), Some(dhcp::MessageType::Ack));
+
+		let lease = dhcp.option(Number::LeaseTime).unwrap().unwrap();
+		assert_eq!(lease.lease_time().unwrap(), 3600);
+
+		let servers = dhcp.option(Number::DomainNameServer).unwrap().unwrap();
+		assert_eq!(servers.domain_name_servers().unwrap().count(), 2);
+
+		assert!(dhcp.option(Number::HostName).unwrap().is_none());
+	}
+
+	#[test]
+	fn operation() {
+		let raw = dhcp::Builder::default()
+			.operation(dhcp::Operation::Request).unwrap()
+			.message_type(dhcp::MessageType::Ack).unwrap()
+			.build().unwrap();
+
+		let dhcp = dhcp::Packet::new(&raw[..]).unwrap();
+		assert_eq!(dhcp.operation(), dhcp::Operation::Request);
+		assert_eq!(dhcp.message_type().unwrap(), Some(dhcp::MessageType::Ack));
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(dhcp::Builder::default().routers(&[]).is_err());
+		assert!(dhcp::Builder::default()
+			.lease_time(1).unwrap()
+			.lease_time(2).is_err());
+	}
+}
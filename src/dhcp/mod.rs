@@ -0,0 +1,33 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+mod operation;
+pub use self::operation::Operation;
+
+/// DHCP options.
+pub mod option;
+pub use self::option::{Option, MessageType};
+
+mod packet;
+pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::Builder;
+
+/// Magic cookie marking the start of the DHCP options.
+pub const COOKIE: u32 = 0x6382_5363;
+
+/// Minimum size of a BOOTP message.
+pub const MINIMUM: usize = 300;
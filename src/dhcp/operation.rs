@@ -0,0 +1,51 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+/// BOOTP operations.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Operation {
+	/// Message sent by a client.
+	Request,
+
+	/// Message sent by a server.
+	Reply,
+
+	/// Unknown operation.
+	Unknown(u8),
+}
+
+impl From<u8> for Operation {
+	fn from(value: u8) -> Operation {
+		use self::Operation::*;
+
+		match value {
+			1 => Request,
+			2 => Reply,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<Operation> for u8 {
+	fn from(value: Operation) -> u8 {
+		use self::Operation::*;
+
+		match value {
+			Request    => 1,
+			Reply      => 2,
+			Unknown(n) => n,
+		}
+	}
+}
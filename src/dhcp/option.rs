@@ -0,0 +1,467 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::fmt;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+
+/// DHCP option parser.
+pub struct Option<B> {
+	buffer: B,
+}
+
+sized!(Option,
+	header {
+		min:  1,
+		max:  2,
+		size: p => match p.number() {
+			Number::Pad | Number::End => 1,
+			_ => 2,
+		},
+	}
+
+	payload {
+		min:  0,
+		max:  255,
+		size: p => match p.number() {
+			Number::Pad | Number::End => 0,
+			_ => p.length() as usize,
+		},
+	});
+
+/// DHCP option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+	/// Padding.
+	Pad,
+
+	/// Subnet mask of the client.
+	SubnetMask,
+
+	/// Routers on the subnet of the client.
+	Router,
+
+	/// Domain name servers.
+	DomainNameServer,
+
+	/// Name of the client.
+	HostName,
+
+	/// Domain name of the client.
+	DomainName,
+
+	/// Broadcast address of the subnet.
+	BroadcastAddress,
+
+	/// Address requested by the client.
+	RequestedAddress,
+
+	/// Lease time in seconds.
+	LeaseTime,
+
+	/// The file and server name fields carry options.
+	Overload,
+
+	/// DHCP message type.
+	MessageType,
+
+	/// Identifier of the server.
+	ServerIdentifier,
+
+	/// Options requested by the client.
+	ParameterRequestList,
+
+	/// Error message.
+	Message,
+
+	/// Maximum DHCP message size.
+	MaximumMessageSize,
+
+	/// Time until the client starts renewing.
+	RenewalTime,
+
+	/// Time until the client starts rebinding.
+	RebindingTime,
+
+	/// Identifier of the client.
+	ClientIdentifier,
+
+	/// End of the options.
+	End,
+
+	/// Unknown option.
+	Unknown(u8),
+}
+
+/// DHCP message types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum MessageType {
+	/// Client broadcast to locate servers.
+	Discover,
+
+	/// Server offer of configuration parameters.
+	Offer,
+
+	/// Client request of the offered parameters.
+	Request,
+
+	/// Client notice that the address is already in use.
+	Decline,
+
+	/// Server acknowledgment with the configuration parameters.
+	Ack,
+
+	/// Server refusal of the request.
+	Nak,
+
+	/// Client release of the address.
+	Release,
+
+	/// Client request of local configuration parameters only.
+	Inform,
+
+	/// Unknown message type.
+	Unknown(u8),
+}
+
+/// Iterator over the addresses of an option.
+#[derive(Debug)]
+pub struct Addresses<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for Addresses<'a> {
+	type Item = Ipv4Addr;
+
+	fn next(&mut self) -> std::option::Option<Self::Item> {
+		if self.buffer.len() < 4 {
+			return None;
+		}
+
+		let address = Ipv4Addr::new(self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]);
+		self.buffer = &self.buffer[4 ..];
+
+		Some(address)
+	}
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("dhcp::Option")
+			.field("number", &self.number())
+			.field("length", &self.length())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Parse a DHCP option, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Option<B>> {
+		use crate::size::header::Min;
+
+		let option = Option { buffer };
+
+		if option.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if matches!(option.number(), Number::Pad | Number::End) {
+			return Ok(option);
+		}
+
+		if option.buffer.as_ref().len() < 2 ||
+		   option.buffer.as_ref().len() < 2 + option.length() as usize
+		{
+			Err(Error::SmallBuffer)?
+		}
+
+		let length = option.length();
+		let valid  = match option.number() {
+			Number::SubnetMask | Number::BroadcastAddress | Number::RequestedAddress |
+			Number::LeaseTime | Number::ServerIdentifier | Number::RenewalTime |
+			Number::RebindingTime =>
+				length == 4,
+
+			Number::Router | Number::DomainNameServer =>
+				length >= 4 && length & 0b11 == 0,
+
+			Number::Overload | Number::MessageType =>
+				length == 1,
+
+			Number::MaximumMessageSize =>
+				length == 2,
+
+			Number::ParameterRequestList | Number::HostName | Number::DomainName |
+			Number::Message | Number::ClientIdentifier =>
+				length >= 1,
+
+			_ =>
+				true,
+		};
+
+		if !valid {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(option)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Option<&[u8]>> {
+		Option::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		match self.number() {
+			Number::Pad | Number::End =>
+				(&self.buffer.as_ref()[.. 1], &[]),
+
+			_ =>
+				self.buffer.as_ref()[.. self.size()].split_at(2),
+		}
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Option number.
+	pub fn number(&self) -> Number {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Option payload length.
+	pub fn length(&self) -> u8 {
+		match self.number() {
+			Number::Pad | Number::End => 0,
+			_ => self.buffer.as_ref()[1],
+		}
+	}
+
+	/// Value of a Message Type option.
+	pub fn message_type(&self) -> Result<MessageType> {
+		self.expect(Number::MessageType)?;
+		Ok(self.payload()[0].into())
+	}
+
+	/// Value of a Requested Address option.
+	pub fn requested_address(&self) -> Result<Ipv4Addr> {
+		self.expect(Number::RequestedAddress)?;
+		Ok(self.address())
+	}
+
+	/// Value of a Server Identifier option.
+	pub fn server_identifier(&self) -> Result<Ipv4Addr> {
+		self.expect(Number::ServerIdentifier)?;
+		Ok(self.address())
+	}
+
+	/// Value of a Subnet Mask option.
+	pub fn subnet_mask(&self) -> Result<Ipv4Addr> {
+		self.expect(Number::SubnetMask)?;
+		Ok(self.address())
+	}
+
+	/// Value of a Lease Time option, in seconds.
+	pub fn lease_time(&self) -> Result<u32> {
+		self.expect(Number::LeaseTime)?;
+		Ok((&self.payload()[0 ..]).read_u32::<BigEndian>()?)
+	}
+
+	/// Options in a Parameter Request List option.
+	pub fn parameter_request_list(&self) -> Result<impl Iterator<Item = Number> + '_> {
+		self.expect(Number::ParameterRequestList)?;
+		Ok(self.payload().iter().map(|&n| Number::from(n)))
+	}
+
+	/// Addresses in a Router option.
+	pub fn routers(&self) -> Result<Addresses<'_>> {
+		self.expect(Number::Router)?;
+		Ok(Addresses { buffer: self.payload() })
+	}
+
+	/// Addresses in a Domain Name Server option.
+	pub fn domain_name_servers(&self) -> Result<Addresses<'_>> {
+		self.expect(Number::DomainNameServer)?;
+		Ok(Addresses { buffer: self.payload() })
+	}
+
+	fn expect(&self, number: Number) -> Result<()> {
+		if self.number() != number {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(())
+	}
+
+	fn address(&self) -> Ipv4Addr {
+		let payload = self.payload();
+		Ipv4Addr::new(payload[0], payload[1], payload[2], payload[3])
+	}
+}
+
+impl From<u8> for Number {
+	fn from(value: u8) -> Self {
+		use self::Number::*;
+
+		match value {
+			0   => Pad,
+			1   => SubnetMask,
+			3   => Router,
+			6   => DomainNameServer,
+			12  => HostName,
+			15  => DomainName,
+			28  => BroadcastAddress,
+			50  => RequestedAddress,
+			51  => LeaseTime,
+			52  => Overload,
+			53  => MessageType,
+			54  => ServerIdentifier,
+			55  => ParameterRequestList,
+			56  => Message,
+			57  => MaximumMessageSize,
+			58  => RenewalTime,
+			59  => RebindingTime,
+			61  => ClientIdentifier,
+			255 => End,
+			n   => Unknown(n),
+		}
+	}
+}
+
+impl From<Number> for u8 {
+	fn from(value: Number) -> u8 {
+		use self::Number::*;
+
+		match value {
+			Pad                  => 0,
+			SubnetMask           => 1,
+			Router               => 3,
+			DomainNameServer     => 6,
+			HostName             => 12,
+			DomainName           => 15,
+			BroadcastAddress     => 28,
+			RequestedAddress     => 50,
+			LeaseTime            => 51,
+			Overload             => 52,
+			MessageType          => 53,
+			ServerIdentifier     => 54,
+			ParameterRequestList => 55,
+			Message              => 56,
+			MaximumMessageSize   => 57,
+			RenewalTime          => 58,
+			RebindingTime        => 59,
+			ClientIdentifier     => 61,
+			End                  => 255,
+			Unknown(n)           => n,
+		}
+	}
+}
+
+impl From<u8> for MessageType {
+	fn from(value: u8) -> Self {
+		use self::MessageType::*;
+
+		match value {
+			1 => Discover,
+			2 => Offer,
+			3 => Request,
+			4 => Decline,
+			5 => Ack,
+			6 => Nak,
+			7 => Release,
+			8 => Inform,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<MessageType> for u8 {
+	fn from(value: MessageType) -> u8 {
+		use self::MessageType::*;
+
+		match value {
+			Discover   => 1,
+			Offer      => 2,
+			Request    => 3,
+			Decline    => 4,
+			Ack        => 5,
+			Nak        => 6,
+			Release    => 7,
+			Inform     => 8,
+			Unknown(n) => n,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::dhcp;
+	use crate::dhcp::option::Number;
+
+	#[test]
+	fn message_type() {
+		let option = dhcp::Option::new(&[53u8, 1, 3][..]).unwrap();
+
+		assert_eq!(option.number(), Number::MessageType);
+		assert_eq!(option.message_type().unwrap(), dhcp::MessageType::Request);
+		assert!(option.lease_time().is_err());
+	}
+
+	#[test]
+	fn addresses() {
+		let option = dhcp::Option::new(&[6u8, 8, 8, 8, 8, 8, 1, 1, 1, 1, 0xff][..]).unwrap();
+
+		assert_eq!(option.domain_name_servers().unwrap().collect::<Vec<_>>(), vec![
+			Ipv4Addr::new(8, 8, 8, 8),
+			Ipv4Addr::new(1, 1, 1, 1),
+		]);
+	}
+
+	#[test]
+	fn parameter_request_list() {
+		let option = dhcp::Option::new(&[55u8, 3, 1, 3, 6][..]).unwrap();
+
+		assert_eq!(option.parameter_request_list().unwrap().collect::<Vec<_>>(), vec![
+			Number::SubnetMask,
+			Number::Router,
+			Number::DomainNameServer,
+		]);
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(dhcp::Option::new(&[51u8, 3, 0, 0, 0][..]).is_err());
+		assert!(dhcp::Option::new(&[3u8, 6, 0, 0, 0, 0, 0, 0][..]).is_err());
+		assert!(dhcp::Option::new(&[53u8, 1][..]).is_err());
+		assert!(dhcp::Option::new(&[0u8][..]).is_ok());
+	}
+}
@@ -0,0 +1,488 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::arp::Hardware;
+use crate::dhcp::{Operation, MessageType, COOKIE};
+use crate::dhcp::option;
+
+/// DHCP message parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  240,
+		max:  240,
+		size: 240,
+	}
+
+	payload {
+		min:  0,
+		max:  u16::MAX as usize - 240,
+		size: p => p.buffer.as_ref().len() - 240,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("dhcp::Packet")
+			.field("operation", &self.operation())
+			.field("hardware", &self.hardware())
+			.field("hops", &self.hops())
+			.field("transaction_id", &self.transaction_id())
+			.field("seconds", &self.seconds())
+			.field("is_broadcast", &self.is_broadcast())
+			.field("client_address", &self.client_address())
+			.field("your_address", &self.your_address())
+			.field("server_address", &self.server_address())
+			.field("gateway_address", &self.gateway_address())
+			.field("client_hardware_address", &self.client_hardware_address())
+			.field("options", &self.options().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a DHCP message without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a DHCP message, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if packet.cookie() != COOKIE || packet.hardware_length() > 16 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		self.buffer.as_ref()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		self.buffer.as_mut()
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(240)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(240)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Operation.
+	pub fn operation(&self) -> Operation {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Hardware type.
+	pub fn hardware(&self) -> Hardware {
+		u16::from(self.buffer.as_ref()[1]).into()
+	}
+
+	/// Length of the hardware address.
+	pub fn hardware_length(&self) -> u8 {
+		self.buffer.as_ref()[2]
+	}
+
+	/// Number of relay agents the message went through.
+	pub fn hops(&self) -> u8 {
+		self.buffer.as_ref()[3]
+	}
+
+	/// Transaction identifier.
+	pub fn transaction_id(&self) -> u32 {
+		(&self.buffer.as_ref()[4 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Seconds elapsed since the client began the exchange.
+	pub fn seconds(&self) -> u16 {
+		(&self.buffer.as_ref()[8 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Whether the client asked for broadcast replies.
+	pub fn is_broadcast(&self) -> bool {
+		self.buffer.as_ref()[10] & 0b1000_0000 != 0
+	}
+
+	/// Current address of the client.
+	pub fn client_address(&self) -> Ipv4Addr {
+		self.address(12)
+	}
+
+	/// Address assigned to the client.
+	pub fn your_address(&self) -> Ipv4Addr {
+		self.address(16)
+	}
+
+	/// Address of the next server to use.
+	pub fn server_address(&self) -> Ipv4Addr {
+		self.address(20)
+	}
+
+	/// Address of the relay agent.
+	pub fn gateway_address(&self) -> Ipv4Addr {
+		self.address(24)
+	}
+
+	/// Hardware address of the client, as many octets as the hardware length.
+	pub fn client_hardware(&self) -> &[u8] {
+		&self.buffer.as_ref()[28 .. 28 + self.hardware_length() as usize]
+	}
+
+	/// Ethernet address of the client.
+	pub fn client_hardware_address(&self) -> Result<HwAddr> {
+		if self.hardware_length() != 6 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.client_hardware().into())
+	}
+
+	/// Optional server host name.
+	pub fn server_name(&self) -> &[u8] {
+		string(&self.buffer.as_ref()[44 .. 108])
+	}
+
+	/// Boot file name.
+	pub fn file(&self) -> &[u8] {
+		string(&self.buffer.as_ref()[108 .. 236])
+	}
+
+	/// Magic cookie.
+	pub fn cookie(&self) -> u32 {
+		(&self.buffer.as_ref()[236 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Options iterator.
+	pub fn options(&self) -> OptionIter<'_> {
+		OptionIter {
+			buffer: &self.buffer.as_ref()[240 ..],
+		}
+	}
+
+	/// Find the first option with the given number.
+	pub fn option(&self, number: option::Number) -> Result<Option<option::Option<&[u8]>>> {
+		for option in self.options() {
+			let option = option?;
+
+			if option.number() == number {
+				return Ok(Some(option));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// DHCP message type, if any.
+	pub fn message_type(&self) -> Result<Option<MessageType>> {
+		self.option(option::Number::MessageType)?
+			.map(|o| o.message_type())
+			.transpose()
+	}
+
+	fn address(&self, offset: usize) -> Ipv4Addr {
+		let buffer = &self.buffer.as_ref()[offset ..];
+		Ipv4Addr::new(buffer[0], buffer[1], buffer[2], buffer[3])
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Operation.
+	pub fn set_operation(&mut self, value: Operation) -> Result<&mut Self> {
+		self.buffer.as_mut()[0] = value.into();
+
+		Ok(self)
+	}
+
+	/// Hardware type and address of the client.
+	pub fn set_client_hardware(&mut self, kind: Hardware, value: &[u8]) -> Result<&mut Self> {
+		let kind = u16::from(kind);
+
+		if kind > 0xff || value.len() > 16 {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[1]  = kind as u8;
+		buffer[2]  = value.len() as u8;
+		buffer[28 .. 44].copy_from_slice(&[0; 16]);
+		buffer[28 .. 28 + value.len()].copy_from_slice(value);
+
+		Ok(self)
+	}
+
+	/// Number of relay agents the message went through.
+	pub fn set_hops(&mut self, value: u8) -> Result<&mut Self> {
+		self.buffer.as_mut()[3] = value;
+
+		Ok(self)
+	}
+
+	/// Transaction identifier.
+	pub fn set_transaction_id(&mut self, value: u32) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[4 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Seconds elapsed since the client began the exchange.
+	pub fn set_seconds(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[8 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Whether the client asks for broadcast replies.
+	pub fn set_broadcast(&mut self, value: bool) -> Result<&mut Self> {
+		if value {
+			self.buffer.as_mut()[10] |= 0b1000_0000;
+		}
+		else {
+			self.buffer.as_mut()[10] &= 0b0111_1111;
+		}
+
+		Ok(self)
+	}
+
+	/// Current address of the client.
+	pub fn set_client_address(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[12 .. 16].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Address assigned to the client.
+	pub fn set_your_address(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[16 .. 20].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Address of the next server to use.
+	pub fn set_server_address(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[20 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Address of the relay agent.
+	pub fn set_gateway_address(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[24 .. 28].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Magic cookie.
+	pub fn set_cookie(&mut self, value: u32) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[236 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
+
+/// Strip the NUL padding of a fixed size string field.
+fn string(buffer: &[u8]) -> &[u8] {
+	let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+	&buffer[.. end]
+}
+
+/// Iterator over DHCP options.
+#[derive(Debug)]
+pub struct OptionIter<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+	type Item = Result<option::Option<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use crate::size::Size;
+
+		loop {
+			if self.buffer.is_empty() {
+				return None;
+			}
+
+			match option::Option::new(self.buffer) {
+				Ok(option) => {
+					match option.number() {
+						option::Number::End =>
+							return None,
+
+						option::Number::Pad => {
+							self.buffer = &self.buffer[1 ..];
+							continue;
+						}
+
+						_ => (),
+					}
+
+					self.buffer = &self.buffer[option.size() ..];
+					return Some(Ok(option));
+				}
+
+				Err(error) => {
+					self.buffer = &[];
+					return Some(Err(error));
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::udp;
+	use crate::dhcp;
+
+	fn request() -> Vec<u8> {
+		let mut raw = vec![0u8; 240];
+		raw[.. 12].copy_from_slice(&[0x01, 0x01, 0x06, 0x00, 0x39, 0x03, 0xf3, 0x26, 0x00, 0x00, 0x00, 0x00]);
+		raw[28 .. 34].copy_from_slice(&[0x00, 0x0b, 0x82, 0x01, 0xfc, 0x42]);
+		raw[44 .. 48].copy_from_slice(b"boot");
+		raw[236 .. 240].copy_from_slice(&[0x63, 0x82, 0x53, 0x63]);
+		raw.extend_from_slice(&[
+			0x35, 0x01, 0x03,
+			0x00,
+			0x32, 0x04, 0xc0, 0xa8, 0x00, 0x0a,
+			0x36, 0x04, 0xc0, 0xa8, 0x00, 0x01,
+			0xff, 0x00, 0x00,
+		]);
+
+		raw
+	}
+
+	#[test]
+	fn values() {
+		let raw  = request();
+		let dhcp = dhcp::Packet::new(&raw[..]).unwrap();
+
+		assert_eq!(dhcp.operation(), dhcp::Operation::Request);
+		assert_eq!(dhcp.hardware(), crate::arp::Hardware::Ethernet);
+		assert_eq!(dhcp.transaction_id(), 0x3903f326);
+		assert!(!dhcp.is_broadcast());
+		assert_eq!(dhcp.client_address(), Ipv4Addr::new(0, 0, 0, 0));
+		assert_eq!(dhcp.client_hardware_address().unwrap(), "00:0b:82:01:fc:42".parse().unwrap());
+		assert_eq!(dhcp.server_name(), b"boot");
+		assert_eq!(dhcp.file(), b"");
+		assert_eq!(dhcp.message_type().unwrap(), Some(dhcp::MessageType::Request));
+
+		let options = dhcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 3);
+		assert_eq!(options[1].requested_address().unwrap(), Ipv4Addr::new(192, 168, 0, 10));
+		assert_eq!(options[2].server_identifier().unwrap(), Ipv4Addr::new(192, 168, 0, 1));
+	}
+
+	#[test]
+	fn cookie() {
+		let mut raw = request();
+		raw[239] = 0x00;
+
+		assert!(dhcp::Packet::new(&raw[..]).is_err());
+		assert!(dhcp::Packet::new(&request()[.. 239]).is_err());
+	}
+
+	#[test]
+	fn malformed() {
+		let mut raw = request();
+		raw.truncate(raw.len() - 3);
+		raw.extend_from_slice(&[0x33, 0x04, 0x00]);
+
+		let dhcp = dhcp::Packet::new(&raw[..]).unwrap();
+		assert!(dhcp.options().last().unwrap().is_err());
+	}
+
+	#[test]
+	fn over_udp() {
+		let packet = ip::v4::Builder::default()
+			.source("0.0.0.0".parse().unwrap()).unwrap()
+			.destination("255.255.255.255".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(68).unwrap()
+				.destination(67).unwrap()
+				.dhcp(dhcp::Builder::default()
+					.transaction_id(1).unwrap()
+					.message_type(dhcp::MessageType::Discover).unwrap()).unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(packet).unwrap();
+		let udp  = udp::Packet::new(ip.payload()).unwrap();
+		let dhcp = dhcp::Packet::new(udp.payload()).unwrap();
+
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+		assert_eq!(dhcp.message_type().unwrap(), Some(dhcp::MessageType::Discover));
+	}
+}
@@ -0,0 +1,517 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::ops::Range;
+
+use crate::packet::Packet as P;
+use crate::pcap::LinkType;
+use crate::ether;
+use crate::arp;
+use crate::ip;
+use crate::icmp;
+use crate::tcp;
+use crate::udp;
+use crate::gre;
+
+/// Maximum number of nested tunnels that are dissected.
+const TUNNELS: usize = 8;
+
+/// A protocol layer borrowed from the dissected buffer.
+#[derive(Debug)]
+pub enum Layer<'a> {
+	/// Ethernet frame.
+	Ether(ether::Packet<&'a [u8]>),
+
+	/// 802.1Q or 802.1ad tag within the Ethernet frame.
+	Vlan(ether::Tag<&'a [u8]>),
+
+	/// ARP packet.
+	Arp(arp::Packet<&'a [u8]>),
+
+	/// IPv4 packet.
+	Ipv4(ip::v4::Packet<&'a [u8]>),
+
+	/// IPv6 packet, including its extension headers.
+	Ipv6(ip::v6::Packet<&'a [u8]>),
+
+	/// ICMP packet.
+	Icmp(icmp::Packet<&'a [u8]>),
+
+	/// ICMPv6 packet.
+	Icmpv6(icmp::v6::Packet<&'a [u8]>),
+
+	/// TCP segment.
+	Tcp(tcp::Packet<&'a [u8]>),
+
+	/// UDP datagram.
+	Udp(udp::Packet<&'a [u8]>),
+
+	/// GRE packet, followed by the encapsulated layers.
+	Gre(gre::Packet<&'a [u8]>),
+
+	/// Bytes of an unknown, unsupported or truncated layer.
+	Unknown(&'a [u8]),
+}
+
+/// A dissected layer and the range of bytes it spans, payload included.
+#[derive(Debug)]
+pub struct Entry<'a> {
+	/// Range within the dissected buffer.
+	pub range: Range<usize>,
+
+	/// The layer itself.
+	pub layer: Layer<'a>,
+}
+
+/// Dissect a buffer captured on the given link type, outermost layer first.
+///
+/// # Note
+///
+/// Dissection stops at the first layer that's unknown or can't be parsed,
+/// which is returned as `Layer::Unknown` with its remaining bytes.
+pub fn dissect(link: LinkType, buffer: &[u8]) -> Vec<Entry<'_>> {
+	let mut layers = Vec::new();
+
+	match link {
+		LinkType::Ethernet =>
+			ether(&mut layers, buffer, 0, 0),
+
+		LinkType::Raw => match buffer.first().map(|b| b >> 4) {
+			Some(4) =>
+				ipv4(&mut layers, buffer, 0, 0),
+
+			Some(6) =>
+				ipv6(&mut layers, buffer, 0, 0),
+
+			_ =>
+				unknown(&mut layers, buffer, 0),
+		},
+
+		LinkType::Ipv4 =>
+			ipv4(&mut layers, buffer, 0, 0),
+
+		LinkType::Ipv6 =>
+			ipv6(&mut layers, buffer, 0, 0),
+
+		_ =>
+			unknown(&mut layers, buffer, 0),
+	}
+
+	layers
+}
+
+fn push<'a>(layers: &mut Vec<Entry<'a>>, start: usize, length: usize, layer: Layer<'a>) {
+	layers.push(Entry {
+		range: start .. start + length,
+		layer,
+	});
+}
+
+fn unknown<'a>(layers: &mut Vec<Entry<'a>>, buffer: &'a [u8], start: usize) {
+	if !buffer.is_empty() {
+		push(layers, start, buffer.len(), Layer::Unknown(buffer));
+	}
+}
+
+fn ether<'a>(layers: &mut Vec<Entry<'a>>, buffer: &'a [u8], start: usize, depth: usize) {
+	let packet = match ether::Packet::new(buffer) {
+		Ok(packet) =>
+			packet,
+
+		Err(_) =>
+			return unknown(layers, buffer, start),
+	};
+
+	let protocol = packet.protocol();
+	let tags     = packet.tags().count();
+	let payload  = buffer.len() - packet.payload().len();
+
+	push(layers, start, buffer.len(), Layer::Ether(packet));
+
+	for offset in (0 .. tags).map(|i| 12 + i * 4) {
+		let tag = ether::Tag::unchecked(&buffer[offset .. offset + 4]);
+		push(layers, start + offset, buffer.len() - offset, Layer::Vlan(tag));
+	}
+
+	let start  = start + payload;
+	let buffer = &buffer[payload ..];
+
+	match protocol {
+		ether::Protocol::Arp => match arp::Packet::new(buffer) {
+			Ok(packet) =>
+				push(layers, start, packet.as_ref().len(), Layer::Arp(packet)),
+
+			Err(_) =>
+				unknown(layers, buffer, start),
+		},
+
+		ether::Protocol::Ipv4 =>
+			ipv4(layers, buffer, start, depth),
+
+		ether::Protocol::Ipv6 =>
+			ipv6(layers, buffer, start, depth),
+
+		_ =>
+			unknown(layers, buffer, start),
+	}
+}
+
+fn ipv4<'a>(layers: &mut Vec<Entry<'a>>, buffer: &'a [u8], start: usize, depth: usize) {
+	let packet = match ip::v4::Packet::no_payload(buffer) {
+		Ok(packet) if packet.header() >= 5 && packet.length() as usize >= packet.header() as usize * 4 =>
+			packet,
+
+		_ =>
+			return unknown(layers, buffer, start),
+	};
+
+	// The buffer may be truncated or padded past the packet.
+	let buffer   = &buffer[.. buffer.len().min(packet.length() as usize)];
+	let header   = packet.header() as usize * 4;
+	let protocol = packet.protocol();
+	let first    = packet.offset() == 0;
+
+	push(layers, start, buffer.len(), Layer::Ipv4(ip::v4::Packet::unchecked(buffer)));
+
+	let start  = start + header;
+	let buffer = &buffer[header ..];
+
+	match protocol {
+		// Only the first fragment carries the upper-layer header.
+		_ if !first =>
+			unknown(layers, buffer, start),
+
+		ip::Protocol::Icmp => match icmp::Packet::new(buffer) {
+			Ok(packet) =>
+				push(layers, start, buffer.len(), Layer::Icmp(packet)),
+
+			Err(_) =>
+				unknown(layers, buffer, start),
+		},
+
+		protocol =>
+			transport(layers, protocol, buffer, start, depth),
+	}
+}
+
+fn ipv6<'a>(layers: &mut Vec<Entry<'a>>, buffer: &'a [u8], start: usize, depth: usize) {
+	let packet = match ip::v6::Packet::no_payload(buffer) {
+		Ok(packet) =>
+			packet,
+
+		Err(_) =>
+			return unknown(layers, buffer, start),
+	};
+
+	// The buffer may be truncated or padded past the packet.
+	let buffer   = &buffer[.. buffer.len().min(40 + packet.payload_length() as usize)];
+	let packet   = ip::v6::Packet::unchecked(buffer);
+	let upper    = packet.protocol().and_then(|p| packet.payload_offset().map(|o| (p, o)));
+
+	push(layers, start, buffer.len(), Layer::Ipv6(packet));
+
+	let (protocol, offset) = match upper {
+		Ok((protocol, offset)) if offset <= buffer.len() =>
+			(protocol, offset),
+
+		_ =>
+			return unknown(layers, &buffer[40 ..], start + 40),
+	};
+
+	let start  = start + offset;
+	let buffer = &buffer[offset ..];
+
+	match protocol {
+		// Only the first fragment carries the upper-layer header, the walk
+		// stops at the fragment header of later ones.
+		ip::Protocol::Ipv6Frag =>
+			unknown(layers, buffer, start),
+
+		ip::Protocol::Ipv6Icmp => match icmp::v6::Packet::new(buffer) {
+			Ok(packet) =>
+				push(layers, start, buffer.len(), Layer::Icmpv6(packet)),
+
+			Err(_) =>
+				unknown(layers, buffer, start),
+		},
+
+		protocol =>
+			transport(layers, protocol, buffer, start, depth),
+	}
+}
+
+fn transport<'a>(layers: &mut Vec<Entry<'a>>, protocol: ip::Protocol, buffer: &'a [u8], start: usize, depth: usize) {
+	match protocol {
+		ip::Protocol::Tcp => match tcp::Packet::new(buffer) {
+			Ok(packet) =>
+				push(layers, start, buffer.len(), Layer::Tcp(packet)),
+
+			Err(_) =>
+				unknown(layers, buffer, start),
+		},
+
+		ip::Protocol::Udp => match udp::Packet::new(buffer) {
+			Ok(packet) =>
+				push(layers, start, buffer.len(), Layer::Udp(packet)),
+
+			Err(_) =>
+				unknown(layers, buffer, start),
+		},
+
+		ip::Protocol::Gre =>
+			tunnel(layers, buffer, start, depth),
+
+		_ =>
+			unknown(layers, buffer, start),
+	}
+}
+
+fn tunnel<'a>(layers: &mut Vec<Entry<'a>>, buffer: &'a [u8], start: usize, depth: usize) {
+	let packet = match gre::Packet::new(buffer) {
+		// Nested tunnels are dissected up to a limit, to bound the recursion.
+		Ok(packet) if depth < TUNNELS =>
+			packet,
+
+		_ =>
+			return unknown(layers, buffer, start),
+	};
+
+	let protocol = packet.protocol();
+	let header   = buffer.len() - packet.payload().len();
+
+	push(layers, start, buffer.len(), Layer::Gre(packet));
+
+	let start  = start + header;
+	let buffer = &buffer[header ..];
+
+	match protocol {
+		ether::Protocol::TransparentEthernetBridging =>
+			ether(layers, buffer, start, depth + 1),
+
+		ether::Protocol::Ipv4 =>
+			ipv4(layers, buffer, start, depth + 1),
+
+		ether::Protocol::Ipv6 =>
+			ipv6(layers, buffer, start, depth + 1),
+
+		_ =>
+			unknown(layers, buffer, start),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::pcap::LinkType;
+	use crate::dissect::{dissect, Layer};
+	use crate::ether;
+	use crate::ip;
+	use crate::tcp;
+
+	#[test]
+	fn ether_ipv4_tcp() {
+		let packet = ether::Builder::default()
+			.vlan(0, false, 42).unwrap()
+			.ip().unwrap().v4().unwrap()
+				.source("66.102.1.108".parse().unwrap()).unwrap()
+				.destination("192.168.0.79".parse().unwrap()).unwrap()
+				.tcp().unwrap()
+					.source(1337).unwrap()
+					.destination(9001).unwrap()
+					.payload(b"lol").unwrap()
+					.build().unwrap();
+
+		let layers = dissect(LinkType::Ethernet, &packet);
+		assert_eq!(layers.len(), 4);
+
+		assert!(matches!(layers[0].layer, Layer::Ether(_)));
+		assert_eq!(layers[0].range, 0 .. 61);
+
+		match layers[1].layer {
+			Layer::Vlan(ref tag) => assert_eq!(tag.vid(), 42),
+			_ => panic!("expected a VLAN tag"),
+		}
+		assert_eq!(layers[1].range, 12 .. 61);
+
+		assert!(matches!(layers[2].layer, Layer::Ipv4(_)));
+		assert_eq!(layers[2].range, 18 .. 61);
+
+		match layers[3].layer {
+			Layer::Tcp(ref tcp) => assert_eq!(tcp.destination(), 9001),
+			_ => panic!("expected a TCP segment"),
+		}
+		assert_eq!(layers[3].range, 38 .. 61);
+	}
+
+	#[test]
+	fn raw_ipv6_udp() {
+		let packet = ip::v6::Builder::default()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(53).unwrap()
+				.destination(9001).unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		let layers = dissect(LinkType::Raw, &packet);
+		assert_eq!(layers.len(), 2);
+		assert!(matches!(layers[0].layer, Layer::Ipv6(_)));
+		assert!(matches!(layers[1].layer, Layer::Udp(_)));
+		assert_eq!(layers[1].range, 40 .. 51);
+	}
+
+	#[test]
+	fn ipv6_fragment() {
+		let mut packet = hex::decode("6000000000102c4020010db800000000000000000000000120010db80000000000000000000000021100000012345678003500350008dead").unwrap();
+
+		let layers = dissect(LinkType::Ipv6, &packet);
+		assert_eq!(layers.len(), 2);
+		assert!(matches!(layers[1].layer, Layer::Udp(_)));
+		assert_eq!(layers[1].range, 48 .. 56);
+
+		// Fragment at offset 100.
+		packet[42] = 0x03;
+		packet[43] = 0x20;
+
+		let layers = dissect(LinkType::Ipv6, &packet);
+		assert_eq!(layers.len(), 2);
+		assert!(matches!(layers[0].layer, Layer::Ipv6(_)));
+		assert!(matches!(layers[1].layer, Layer::Unknown(b) if b.len() == 8));
+		assert_eq!(layers[1].range, 48 .. 56);
+	}
+
+	#[test]
+	fn truncated() {
+		let packet = ip::v4::Builder::default()
+			.tcp().unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		let layers = dissect(LinkType::Ipv4, &packet[.. 30]);
+		assert_eq!(layers.len(), 2);
+		assert!(matches!(layers[0].layer, Layer::Ipv4(_)));
+		assert_eq!(layers[0].range, 0 .. 30);
+		assert!(matches!(layers[1].layer, Layer::Unknown(b) if b.len() == 10));
+
+		let layers = dissect(LinkType::Ethernet, &[0u8; 10]);
+		assert_eq!(layers.len(), 1);
+		assert!(matches!(layers[0].layer, Layer::Unknown(_)));
+
+		assert!(dissect(LinkType::Ipv4, &[]).is_empty());
+	}
+
+	#[test]
+	fn nested_gre() {
+		// Outer IPv4 header followed by GRE headers each carrying another
+		// IPv4 header with GRE inside, as many as fit in a packet.
+		let level = hex::decode("4500000000000000402f0000000000000000000000000800").unwrap();
+		let mut packet = Vec::new();
+
+		while packet.len() + level.len() <= 0xffff {
+			packet.extend_from_slice(&level);
+		}
+
+		for offset in (0 .. packet.len()).step_by(level.len()) {
+			let length = (packet.len() - offset) as u16;
+			packet[offset + 2 .. offset + 4].copy_from_slice(&length.to_be_bytes());
+		}
+
+		let layers = dissect(LinkType::Ipv4, &packet);
+		assert_eq!(layers.iter().filter(|e| matches!(e.layer, Layer::Gre(_))).count(), 8);
+		assert!(matches!(layers.last().unwrap().layer, Layer::Unknown(_)));
+	}
+
+	#[test]
+	fn snaplen() {
+		let packet = ether::Builder::default()
+			.ip().unwrap().v4().unwrap()
+				.source("192.168.0.79".parse().unwrap()).unwrap()
+				.destination("66.102.1.108".parse().unwrap()).unwrap()
+				.tcp().unwrap()
+					.source(40000).unwrap()
+					.destination(80).unwrap()
+					.flags(tcp::flag::SYN).unwrap()
+					.mss(1460).unwrap()
+					.sack_permitted().unwrap()
+					.timestamp(1, 0).unwrap()
+					.nop().unwrap()
+					.window_scale(7).unwrap()
+					.build().unwrap();
+
+		assert_eq!(packet.len(), 74);
+
+		let layers = dissect(LinkType::Ethernet, &packet[.. 60]);
+		assert_eq!(layers.len(), 3);
+		assert!(matches!(layers[1].layer, Layer::Ipv4(_)));
+		assert!(matches!(layers[2].layer, Layer::Unknown(b) if b.len() == 26));
+		assert_eq!(layers[2].range, 34 .. 60);
+
+		for entry in &layers {
+			assert!(!format!("{:?}", entry).is_empty());
+		}
+
+		let mut packet = packet;
+		packet[46] = 0x40;
+
+		let layers = dissect(LinkType::Ethernet, &packet);
+		assert!(matches!(layers[2].layer, Layer::Unknown(_)));
+	}
+
+	#[test]
+	fn unknown() {
+		let packet = ip::v4::Builder::default()
+			.protocol(ip::Protocol::Sctp).unwrap()
+			.payload(b"lol").unwrap()
+			.build().unwrap();
+
+		let layers = dissect(LinkType::Ipv4, &packet);
+		assert_eq!(layers.len(), 2);
+		assert!(matches!(layers[1].layer, Layer::Unknown(b"lol")));
+		assert_eq!(layers[1].range, 20 .. 23);
+	}
+
+	#[test]
+	fn gre() {
+		let packet = ip::v4::Builder::default()
+			.source("10.0.0.1".parse().unwrap()).unwrap()
+			.destination("10.0.0.2".parse().unwrap()).unwrap()
+			.gre().unwrap()
+				.key(42).unwrap()
+				.ether().unwrap()
+					.ip().unwrap().v4().unwrap()
+						.source("192.168.0.1".parse().unwrap()).unwrap()
+						.destination("192.168.0.2".parse().unwrap()).unwrap()
+						.udp().unwrap()
+							.source(1337).unwrap()
+							.destination(9001).unwrap()
+							.payload(b"lol").unwrap()
+							.build().unwrap();
+
+		let layers = dissect(LinkType::Ipv4, &packet);
+		assert_eq!(layers.len(), 5);
+		assert!(matches!(layers[0].layer, Layer::Ipv4(_)));
+
+		match layers[1].layer {
+			Layer::Gre(ref gre) => assert_eq!(gre.key(), Some(42)),
+			_ => panic!("expected a GRE packet"),
+		}
+		assert_eq!(layers[1].range, 20 .. 73);
+
+		assert!(matches!(layers[2].layer, Layer::Ether(_)));
+		assert_eq!(layers[2].range, 28 .. 73);
+		assert!(matches!(layers[3].layer, Layer::Ipv4(_)));
+		assert!(matches!(layers[4].layer, Layer::Udp(_)));
+	}
+}
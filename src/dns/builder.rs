@@ -0,0 +1,291 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::error::*;
+use crate::dns::{Packet, Flags, OpCode, ResponseCode, Type, Class};
+use crate::dns::name::MAX;
+
+/// Record data for the builder.
+#[derive(Copy, Clone, Debug)]
+pub enum Data<'a> {
+	/// Address of an A record.
+	Ipv4(Ipv4Addr),
+
+	/// Address of an AAAA record.
+	Ipv6(Ipv6Addr),
+
+	/// Name of a NS, CNAME or PTR record.
+	Name(&'a str),
+
+	/// Preference and exchange of a MX record.
+	MailExchange(u16, &'a str),
+
+	/// Character strings of a TXT record.
+	Text(&'a [&'a [u8]]),
+
+	/// Raw record data.
+	Raw(&'a [u8]),
+}
+
+/// DNS message builder.
+///
+/// # Note
+///
+/// Names are compressed against the ones already in the message, and the
+/// sections have to be filled in order.
+#[derive(Debug)]
+pub struct Builder {
+	buffer:  Vec<u8>,
+	names:   HashMap<String, u16>,
+	section: usize,
+	counts:  [u16; 4],
+}
+
+impl Default for Builder {
+	fn default() -> Self {
+		Builder {
+			buffer:  vec![0; 12],
+			names:   HashMap::new(),
+			section: 0,
+			counts:  [0; 4],
+		}
+	}
+}
+
+impl Builder {
+	/// Message identifier.
+	pub fn id(mut self, value: u16) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_id(value)?;
+		Ok(self)
+	}
+
+	/// Header flags.
+	pub fn flags(mut self, value: Flags) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_flags(value)?;
+		Ok(self)
+	}
+
+	/// Operation code.
+	pub fn opcode(mut self, value: OpCode) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_opcode(value)?;
+		Ok(self)
+	}
+
+	/// Response code.
+	pub fn response_code(mut self, value: ResponseCode) -> Result<Self> {
+		Packet::unchecked(&mut self.buffer[..]).set_response_code(value)?;
+		Ok(self)
+	}
+
+	/// Add a question.
+	pub fn question(mut self, name: &str, kind: Type, class: Class) -> Result<Self> {
+		self.enter(0)?;
+		self.name(name)?;
+		self.buffer.extend_from_slice(&u16::from(kind).to_be_bytes());
+		self.buffer.extend_from_slice(&u16::from(class).to_be_bytes());
+
+		Ok(self)
+	}
+
+	/// Add an answer record.
+	pub fn answer(self, name: &str, kind: Type, class: Class, ttl: u32, data: Data<'_>) -> Result<Self> {
+		self.record(1, name, kind, class, ttl, data)
+	}
+
+	/// Add an authority record.
+	pub fn authority(self, name: &str, kind: Type, class: Class, ttl: u32, data: Data<'_>) -> Result<Self> {
+		self.record(2, name, kind, class, ttl, data)
+	}
+
+	/// Add an additional record.
+	pub fn additional(self, name: &str, kind: Type, class: Class, ttl: u32, data: Data<'_>) -> Result<Self> {
+		self.record(3, name, kind, class, ttl, data)
+	}
+
+	/// Build the message.
+	pub fn build(self) -> Result<Vec<u8>> {
+		Ok(self.buffer)
+	}
+
+	fn record(mut self, section: usize, name: &str, kind: Type, class: Class, ttl: u32, data: Data<'_>) -> Result<Self> {
+		self.enter(section)?;
+		self.name(name)?;
+		self.buffer.extend_from_slice(&u16::from(kind).to_be_bytes());
+		self.buffer.extend_from_slice(&u16::from(class).to_be_bytes());
+		self.buffer.extend_from_slice(&ttl.to_be_bytes());
+		self.buffer.extend_from_slice(&[0, 0]);
+
+		let start = self.buffer.len();
+
+		match data {
+			Data::Ipv4(address) =>
+				self.buffer.extend_from_slice(&address.octets()),
+
+			Data::Ipv6(address) =>
+				self.buffer.extend_from_slice(&address.octets()),
+
+			Data::Name(name) =>
+				self.name(name)?,
+
+			Data::MailExchange(preference, name) => {
+				self.buffer.extend_from_slice(&preference.to_be_bytes());
+				self.name(name)?;
+			}
+
+			Data::Text(strings) => {
+				for string in strings {
+					if string.len() > 255 {
+						Err(Error::InvalidValue)?
+					}
+
+					self.buffer.push(string.len() as u8);
+					self.buffer.extend_from_slice(string);
+				}
+			}
+
+			Data::Raw(data) =>
+				self.buffer.extend_from_slice(data),
+		}
+
+		let length = self.buffer.len() - start;
+		if length > u16::MAX as usize || self.buffer.len() > u16::MAX as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		self.buffer[start - 2 .. start].copy_from_slice(&(length as u16).to_be_bytes());
+		Ok(self)
+	}
+
+	/// Move to the given section, counting the new entry.
+	fn enter(&mut self, section: usize) -> Result<()> {
+		if section < self.section {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.section         = section;
+		self.counts[section] = self.counts[section].checked_add(1).ok_or(Error::InvalidValue)?;
+
+		let count = self.counts[section];
+		Packet::unchecked(&mut self.buffer[..]).set_count(section, count)?;
+
+		Ok(())
+	}
+
+	/// Write a name, compressing it against the names already written.
+	fn name(&mut self, value: &str) -> Result<()> {
+		let value  = value.strip_suffix('.').unwrap_or(value);
+		let labels = if value.is_empty() {
+			Vec::new()
+		}
+		else {
+			value.split('.').collect::<Vec<_>>()
+		};
+
+		if labels.iter().any(|l| l.is_empty() || l.len() > 63) ||
+		   labels.iter().map(|l| l.len() + 1).sum::<usize>() + 1 > MAX
+		{
+			Err(Error::InvalidValue)?
+		}
+
+		for i in 0 .. labels.len() {
+			let suffix = labels[i ..].join(".").to_ascii_lowercase();
+
+			if let Some(&pointer) = self.names.get(&suffix) {
+				self.buffer.extend_from_slice(&(0xc000 | pointer).to_be_bytes());
+				return Ok(());
+			}
+
+			if self.buffer.len() <= 0x3fff {
+				self.names.insert(suffix, self.buffer.len() as u16);
+			}
+
+			self.buffer.push(labels[i].len() as u8);
+			self.buffer.extend_from_slice(labels[i].as_bytes());
+		}
+
+		self.buffer.push(0);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::dns;
+
+	#[test]
+	fn query() {
+		let raw = dns::Builder::default()
+			.id(0xbc81).unwrap()
+			.flags(dns::flag::RECURSION_DESIRED).unwrap()
+			.question("api.steampowered.com", dns::Type::Aaaa, dns::Class::In).unwrap()
+			.build().unwrap();
+
+		assert_eq!(raw, hex::decode("bc810100000100000000000003617069 0c737465616d706f7765726564 03636f6d 00 001c 0001".replace(' ', "")).unwrap());
+	}
+
+	#[test]
+	fn compression() {
+		let raw = dns::Builder::default()
+			.id(1).unwrap()
+			.flags(dns::flag::RESPONSE).unwrap()
+			.response_code(dns::ResponseCode::NoError).unwrap()
+			.question("example.com", dns::Type::Mx, dns::Class::In).unwrap()
+			.answer("example.com", dns::Type::Mx, dns::Class::In, 60, dns::Data::MailExchange(10, "mail.EXAMPLE.com.")).unwrap()
+			.additional("mail.example.com", dns::Type::A, dns::Class::In, 60, dns::Data::Ipv4("10.0.0.1".parse().unwrap())).unwrap()
+			.additional("example.com", dns::Type::Txt, dns::Class::In, 60, dns::Data::Text(&[b"v=spf1", b"-all"])).unwrap()
+			.build().unwrap();
+
+		// Header, question, answer with a compressed owner and exchange, and
+		// fully compressed additional owners.
+		assert_eq!(raw.len(), 12 + 17 + (2 + 10 + 2 + 7) + (2 + 10 + 4) + (2 + 10 + 12));
+
+		let dns = dns::Packet::new(&raw[..]).unwrap();
+		assert_eq!(dns.answer_count(), 1);
+		assert_eq!(dns.authority_count(), 0);
+		assert_eq!(dns.additional_count(), 2);
+
+		let answer = dns.answers().unwrap().next().unwrap().unwrap();
+		let (preference, exchange) = answer.mail_exchange().unwrap();
+		assert_eq!(preference, 10);
+		assert_eq!(exchange.to_string(), "mail.example.com");
+
+		let additionals = dns.additionals().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(additionals[0].name(), "mail.example.com");
+		assert_eq!(additionals[0].ipv4().unwrap(), "10.0.0.1".parse::<std::net::Ipv4Addr>().unwrap());
+
+		let text = additionals[1].text().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(text, vec![&b"v=spf1"[..], &b"-all"[..]]);
+	}
+
+	#[test]
+	fn order() {
+		let builder = dns::Builder::default()
+			.answer("example.com", dns::Type::A, dns::Class::In, 60, dns::Data::Raw(&[1, 2, 3, 4])).unwrap();
+
+		assert!(builder.question("example.com", dns::Type::A, dns::Class::In).is_err());
+	}
+
+	#[test]
+	fn invalid() {
+		let long = "a".repeat(64);
+
+		assert!(dns::Builder::default().question(&long, dns::Type::A, dns::Class::In).is_err());
+		assert!(dns::Builder::default().question("a..b", dns::Type::A, dns::Class::In).is_err());
+		assert!(dns::Builder::default().question(".", dns::Type::Ns, dns::Class::In).is_ok());
+	}
+}
@@ -0,0 +1,123 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+/// DNS operation codes.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum OpCode {
+	/// Standard query.
+	Query,
+
+	/// Inverse query.
+	InverseQuery,
+
+	/// Server status request.
+	Status,
+
+	/// Zone change notification.
+	Notify,
+
+	/// Dynamic update.
+	Update,
+
+	/// Unknown operation.
+	Unknown(u8),
+}
+
+/// DNS response codes.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ResponseCode {
+	/// No error.
+	NoError,
+
+	/// The server was unable to interpret the query.
+	FormatError,
+
+	/// The server was unable to process the query.
+	ServerFailure,
+
+	/// The domain name does not exist.
+	NameError,
+
+	/// The server does not support the kind of query.
+	NotImplemented,
+
+	/// The server refuses to perform the operation.
+	Refused,
+
+	/// Unknown response code.
+	Unknown(u8),
+}
+
+impl From<u8> for OpCode {
+	fn from(value: u8) -> OpCode {
+		use self::OpCode::*;
+
+		match value {
+			0 => Query,
+			1 => InverseQuery,
+			2 => Status,
+			4 => Notify,
+			5 => Update,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<OpCode> for u8 {
+	fn from(value: OpCode) -> u8 {
+		use self::OpCode::*;
+
+		match value {
+			Query        => 0,
+			InverseQuery => 1,
+			Status       => 2,
+			Notify       => 4,
+			Update       => 5,
+			Unknown(n)   => n,
+		}
+	}
+}
+
+impl From<u8> for ResponseCode {
+	fn from(value: u8) -> ResponseCode {
+		use self::ResponseCode::*;
+
+		match value {
+			0 => NoError,
+			1 => FormatError,
+			2 => ServerFailure,
+			3 => NameError,
+			4 => NotImplemented,
+			5 => Refused,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<ResponseCode> for u8 {
+	fn from(value: ResponseCode) -> u8 {
+		use self::ResponseCode::*;
+
+		match value {
+			NoError        => 0,
+			FormatError    => 1,
+			ServerFailure  => 2,
+			NameError      => 3,
+			NotImplemented => 4,
+			Refused        => 5,
+			Unknown(n)     => n,
+		}
+	}
+}
@@ -0,0 +1,53 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use bitflags::bitflags;
+
+bitflags! {
+	/// DNS header flags.
+	///
+	/// The operation and response codes share the same field and are
+	/// accessed separately.
+	pub struct Flags: u16 {
+		/// The message is a response.
+		const RESPONSE = 0b1000_0000_0000_0000;
+
+		/// Authoritative answer.
+		const AUTHORITATIVE = 0b0000_0100_0000_0000;
+
+		/// The message was truncated.
+		const TRUNCATED = 0b0000_0010_0000_0000;
+
+		/// Recursion desired.
+		const RECURSION_DESIRED = 0b0000_0001_0000_0000;
+
+		/// Recursion available.
+		const RECURSION_AVAILABLE = 0b0000_0000_1000_0000;
+
+		/// Authentic data.
+		const AUTHENTIC_DATA = 0b0000_0000_0010_0000;
+
+		/// Checking disabled.
+		const CHECKING_DISABLED = 0b0000_0000_0001_0000;
+	}
+}
+
+pub const RESPONSE:            Flags = Flags::RESPONSE;
+pub const AUTHORITATIVE:       Flags = Flags::AUTHORITATIVE;
+pub const TRUNCATED:           Flags = Flags::TRUNCATED;
+pub const RECURSION_DESIRED:   Flags = Flags::RECURSION_DESIRED;
+pub const RECURSION_AVAILABLE: Flags = Flags::RECURSION_AVAILABLE;
+pub const AUTHENTIC_DATA:      Flags = Flags::AUTHENTIC_DATA;
+pub const CHECKING_DISABLED:   Flags = Flags::CHECKING_DISABLED;
@@ -0,0 +1,143 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+/// Resource record types.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Type {
+	/// IPv4 address.
+	A,
+
+	/// Authoritative name server.
+	Ns,
+
+	/// Canonical name for an alias.
+	Cname,
+
+	/// Start of a zone of authority.
+	Soa,
+
+	/// Domain name pointer.
+	Ptr,
+
+	/// Mail exchange.
+	Mx,
+
+	/// Text strings.
+	Txt,
+
+	/// IPv6 address.
+	Aaaa,
+
+	/// Service location.
+	Srv,
+
+	/// EDNS pseudo-record.
+	Opt,
+
+	/// Any record, only valid in questions.
+	Any,
+
+	/// Unknown type.
+	Unknown(u16),
+}
+
+/// Resource record classes.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Class {
+	/// The Internet.
+	In,
+
+	/// Chaos.
+	Ch,
+
+	/// Hesiod.
+	Hs,
+
+	/// Any class, only valid in questions.
+	Any,
+
+	/// Unknown class.
+	Unknown(u16),
+}
+
+impl From<u16> for Type {
+	fn from(value: u16) -> Type {
+		use self::Type::*;
+
+		match value {
+			1   => A,
+			2   => Ns,
+			5   => Cname,
+			6   => Soa,
+			12  => Ptr,
+			15  => Mx,
+			16  => Txt,
+			28  => Aaaa,
+			33  => Srv,
+			41  => Opt,
+			255 => Any,
+			n   => Unknown(n),
+		}
+	}
+}
+
+impl From<Type> for u16 {
+	fn from(value: Type) -> u16 {
+		use self::Type::*;
+
+		match value {
+			A          => 1,
+			Ns         => 2,
+			Cname      => 5,
+			Soa        => 6,
+			Ptr        => 12,
+			Mx         => 15,
+			Txt        => 16,
+			Aaaa       => 28,
+			Srv        => 33,
+			Opt        => 41,
+			Any        => 255,
+			Unknown(n) => n,
+		}
+	}
+}
+
+impl From<u16> for Class {
+	fn from(value: u16) -> Class {
+		use self::Class::*;
+
+		match value {
+			1   => In,
+			3   => Ch,
+			4   => Hs,
+			255 => Any,
+			n   => Unknown(n),
+		}
+	}
+}
+
+impl From<Class> for u16 {
+	fn from(value: Class) -> u16 {
+		use self::Class::*;
+
+		match value {
+			In         => 1,
+			Ch         => 3,
+			Hs         => 4,
+			Any        => 255,
+			Unknown(n) => n,
+		}
+	}
+}
@@ -0,0 +1,38 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+/// DNS header flags.
+pub mod flag;
+pub use self::flag::Flags;
+
+mod code;
+pub use self::code::{OpCode, ResponseCode};
+
+mod kind;
+pub use self::kind::{Type, Class};
+
+/// Domain names.
+pub mod name;
+pub use self::name::Name;
+
+/// Questions and resource records.
+pub mod record;
+pub use self::record::{Question, Record};
+
+mod packet;
+pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::{Builder, Data};
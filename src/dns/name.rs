@@ -0,0 +1,224 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::fmt;
+
+use crate::error::*;
+
+/// Maximum length of an encoded name.
+pub const MAX: usize = 255;
+
+/// Domain name within a DNS message, possibly compressed.
+#[derive(Copy, Clone)]
+pub struct Name<'a> {
+	buffer: &'a [u8],
+	offset: usize,
+}
+
+impl<'a> Name<'a> {
+	/// Parse the name at the given offset of the message, returning it with
+	/// the offset right after it.
+	///
+	/// # Note
+	///
+	/// Compression pointers have to point before the labels they follow,
+	/// which rules out loops.
+	pub fn new(buffer: &'a [u8], offset: usize) -> Result<(Name<'a>, usize)> {
+		let mut start    = offset;
+		let mut position = offset;
+		let mut length   = 0;
+		let mut end      = None;
+
+		loop {
+			let size = *buffer.get(position).ok_or(Error::SmallBuffer)? as usize;
+
+			match size & 0xc0 {
+				0x00 => {
+					length += size + 1;
+
+					if length > MAX {
+						Err(Error::InvalidPacket)?
+					}
+
+					if size == 0 {
+						break;
+					}
+
+					if buffer.len() < position + 1 + size {
+						Err(Error::SmallBuffer)?
+					}
+
+					position += size + 1;
+				}
+
+				0xc0 => {
+					let low    = *buffer.get(position + 1).ok_or(Error::SmallBuffer)? as usize;
+					let target = (size & 0x3f) << 8 | low;
+
+					if target >= start {
+						Err(Error::InvalidPacket)?
+					}
+
+					end.get_or_insert(position + 2);
+					start    = target;
+					position = target;
+				}
+
+				_ =>
+					Err(Error::InvalidPacket)?
+			}
+		}
+
+		Ok((Name { buffer, offset }, end.unwrap_or(position + 1)))
+	}
+
+	/// Iterator over the labels.
+	pub fn labels(&self) -> Labels<'a> {
+		Labels {
+			buffer:   self.buffer,
+			position: self.offset,
+		}
+	}
+
+	/// Whether this is the root name.
+	pub fn is_root(&self) -> bool {
+		self.labels().next().is_none()
+	}
+}
+
+impl fmt::Display for Name<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_root() {
+			return f.write_str(".");
+		}
+
+		for (i, label) in self.labels().enumerate() {
+			if i != 0 {
+				f.write_str(".")?;
+			}
+
+			f.write_str(&String::from_utf8_lossy(label))?;
+		}
+
+		Ok(())
+	}
+}
+
+impl fmt::Debug for Name<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("dns::Name")
+			.field(&self.to_string())
+			.finish()
+	}
+}
+
+impl PartialEq<&str> for Name<'_> {
+	/// Compare with a dotted name, ignoring case.
+	fn eq(&self, other: &&str) -> bool {
+		let other = other.strip_suffix('.').unwrap_or(other);
+		let mut labels = self.labels();
+
+		if other.is_empty() {
+			return labels.next().is_none();
+		}
+
+		other.split('.')
+			.all(|part| labels.next().is_some_and(|label| label.eq_ignore_ascii_case(part.as_bytes()))) &&
+			labels.next().is_none()
+	}
+}
+
+/// Iterator over the labels of a name.
+#[derive(Debug)]
+pub struct Labels<'a> {
+	buffer:   &'a [u8],
+	position: usize,
+}
+
+impl<'a> Iterator for Labels<'a> {
+	type Item = &'a [u8];
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let size = self.buffer[self.position] as usize;
+
+			if size & 0xc0 == 0xc0 {
+				self.position = (size & 0x3f) << 8 | self.buffer[self.position + 1] as usize;
+				continue;
+			}
+
+			if size == 0 {
+				return None;
+			}
+
+			let label = &self.buffer[self.position + 1 .. self.position + 1 + size];
+			self.position += size + 1;
+
+			return Some(label);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::dns::Name;
+
+	#[test]
+	fn plain() {
+		let raw = b"\x03api\x0csteampowered\x03com\x00";
+		let (name, end) = Name::new(&raw[..], 0).unwrap();
+
+		assert_eq!(end, raw.len());
+		assert_eq!(name.to_string(), "api.steampowered.com");
+		assert_eq!(name, "API.steampowered.com.");
+		assert!(name != "steampowered.com");
+	}
+
+	#[test]
+	fn compressed() {
+		let raw = b"\x03com\x00\x07example\xc0\x00\x03www\xc0\x05";
+
+		let (name, end) = Name::new(&raw[..], 5).unwrap();
+		assert_eq!(end, 15);
+		assert_eq!(name, "example.com");
+
+		let (name, end) = Name::new(&raw[..], 15).unwrap();
+		assert_eq!(end, raw.len());
+		assert_eq!(name, "www.example.com");
+	}
+
+	#[test]
+	fn root() {
+		let (name, end) = Name::new(&[0u8][..], 0).unwrap();
+		assert_eq!(end, 1);
+		assert!(name.is_root());
+		assert_eq!(name.to_string(), ".");
+	}
+
+	#[test]
+	fn loops() {
+		assert!(Name::new(&b"\xc0\x00"[..], 0).is_err());
+		assert!(Name::new(&b"\x01a\xc0\x00"[..], 0).is_err());
+		assert!(Name::new(&b"\x01a\x00\x01b\xc0\x05"[..], 3).is_err());
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(Name::new(&b"\x05abc"[..], 0).is_err());
+		assert!(Name::new(&b"\x03abc"[..], 0).is_err());
+		assert!(Name::new(&b"\x41abc\x00"[..], 0).is_err());
+		assert!(Name::new(&[&[63u8][..], &[b'a'; 63][..]].concat().repeat(4)[..], 0).is_err());
+	}
+}
@@ -0,0 +1,349 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::dns::{Flags, OpCode, ResponseCode, Question, Record};
+use crate::dns::record::{Questions, Records};
+
+/// DNS message parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  12,
+		max:  12,
+		size: 12,
+	}
+
+	payload {
+		min:  0,
+		max:  u16::MAX as usize - 12,
+		size: p => p.buffer.as_ref().len() - 12,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("dns::Packet")
+			.field("id", &self.id())
+			.field("flags", &self.flags())
+			.field("opcode", &self.opcode())
+			.field("response_code", &self.response_code())
+			.field("questions", &self.questions().collect::<Vec<_>>())
+			.field("answers", &self.answers().map(|r| r.collect::<Vec<_>>()))
+			.field("authorities", &self.authorities().map(|r| r.collect::<Vec<_>>()))
+			.field("additionals", &self.additionals().map(|r| r.collect::<Vec<_>>()))
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a DNS message without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a DNS message, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		self.buffer.as_ref()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		self.buffer.as_mut()
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(12)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(12)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Message identifier.
+	pub fn id(&self) -> u16 {
+		(&self.buffer.as_ref()[0 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Header flags.
+	pub fn flags(&self) -> Flags {
+		Flags::from_bits_truncate((&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap())
+	}
+
+	/// Operation code.
+	pub fn opcode(&self) -> OpCode {
+		((self.buffer.as_ref()[2] >> 3) & 0b1111).into()
+	}
+
+	/// Response code.
+	pub fn response_code(&self) -> ResponseCode {
+		(self.buffer.as_ref()[3] & 0b1111).into()
+	}
+
+	/// Number of questions.
+	pub fn question_count(&self) -> u16 {
+		(&self.buffer.as_ref()[4 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Number of answer records.
+	pub fn answer_count(&self) -> u16 {
+		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Number of authority records.
+	pub fn authority_count(&self) -> u16 {
+		(&self.buffer.as_ref()[8 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Number of additional records.
+	pub fn additional_count(&self) -> u16 {
+		(&self.buffer.as_ref()[10 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Iterator over the questions.
+	pub fn questions(&self) -> Questions<'_> {
+		Questions {
+			buffer:    self.buffer.as_ref(),
+			offset:    12,
+			remaining: self.question_count(),
+		}
+	}
+
+	/// Iterator over the answer records.
+	pub fn answers(&self) -> Result<Records<'_>> {
+		self.section(0, self.answer_count())
+	}
+
+	/// Iterator over the authority records.
+	pub fn authorities(&self) -> Result<Records<'_>> {
+		self.section(1, self.authority_count())
+	}
+
+	/// Iterator over the additional records.
+	pub fn additionals(&self) -> Result<Records<'_>> {
+		self.section(2, self.additional_count())
+	}
+
+	/// Find the start of a record section by walking the ones before it.
+	fn section(&self, index: usize, count: u16) -> Result<Records<'_>> {
+		let buffer = self.buffer.as_ref();
+		let mut offset = 12;
+
+		for _ in 0 .. self.question_count() {
+			offset = Question::new(buffer, offset)?.1;
+		}
+
+		let skip = [self.answer_count(), self.authority_count()].iter()
+			.take(index)
+			.map(|&c| c as usize)
+			.sum::<usize>();
+
+		for _ in 0 .. skip {
+			offset = Record::new(buffer, offset)?.1;
+		}
+
+		Ok(Records { buffer, offset, remaining: count })
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Message identifier.
+	pub fn set_id(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[0 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Header flags.
+	pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
+		let codes = (&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>()? & 0b0111_1000_0000_1111;
+
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>(value.bits() | codes)?;
+
+		Ok(self)
+	}
+
+	/// Operation code.
+	pub fn set_opcode(&mut self, value: OpCode) -> Result<&mut Self> {
+		let value = u8::from(value);
+
+		if value > 0b1111 {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[2]  = (buffer[2] & 0b1000_0111) | (value << 3);
+
+		Ok(self)
+	}
+
+	/// Response code.
+	pub fn set_response_code(&mut self, value: ResponseCode) -> Result<&mut Self> {
+		let value = u8::from(value);
+
+		if value > 0b1111 {
+			Err(Error::InvalidValue)?
+		}
+
+		let buffer = self.buffer.as_mut();
+		buffer[3]  = (buffer[3] & 0b1111_0000) | value;
+
+		Ok(self)
+	}
+
+	pub(in crate::dns) fn set_count(&mut self, index: usize, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[4 + index * 2 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::udp;
+	use crate::dns;
+
+	#[test]
+	fn query() {
+		let raw = [0x45u8, 0x00, 0x00, 0x42, 0x47, 0x07, 0x40, 0x00, 0x40, 0x11, 0x6e, 0xcc, 0xc0, 0xa8, 0x01, 0x89, 0xc0, 0xa8, 0x01, 0xfe, 0xba, 0x2f, 0x00, 0x35, 0x00, 0x2e, 0x1d, 0xf8, 0xbc, 0x81, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x61, 0x70, 0x69, 0x0c, 0x73, 0x74, 0x65, 0x61, 0x6d, 0x70, 0x6f, 0x77, 0x65, 0x72, 0x65, 0x64, 0x03, 0x63, 0x6f, 0x6d, 0x00, 0x00, 0x1c, 0x00, 0x01];
+
+		let ip  = ip::v4::Packet::new(&raw[..]).unwrap();
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		let dns = dns::Packet::new(udp.payload()).unwrap();
+
+		assert_eq!(dns.id(), 0xbc81);
+		assert_eq!(dns.flags(), dns::flag::RECURSION_DESIRED);
+		assert_eq!(dns.opcode(), dns::OpCode::Query);
+		assert_eq!(dns.response_code(), dns::ResponseCode::NoError);
+		assert_eq!(dns.question_count(), 1);
+		assert_eq!(dns.answers().unwrap().count(), 0);
+
+		let questions = dns.questions().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(questions.len(), 1);
+		assert_eq!(questions[0].name(), "api.steampowered.com");
+		assert_eq!(questions[0].kind(), dns::Type::Aaaa);
+		assert_eq!(questions[0].class(), dns::Class::In);
+	}
+
+	#[test]
+	fn response() {
+		let raw = hex::decode(concat!(
+			"bc8181800001000200000000",
+			"03617069", "0c737465616d706f7765726564", "03636f6d", "00", "001c0001",
+			"c00c", "0005", "0001", "0000012c", "000a", "03617069", "03636463", "c010",
+			"c032", "001c", "0001", "0000003c", "0010", "20010db8000000000000000000000001",
+		)).unwrap();
+
+		let dns = dns::Packet::new(&raw[..]).unwrap();
+		assert!(dns.flags().contains(dns::flag::RESPONSE | dns::flag::RECURSION_AVAILABLE));
+
+		let answers = dns.answers().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(answers.len(), 2);
+
+		assert_eq!(answers[0].name(), "api.steampowered.com");
+		assert_eq!(answers[0].kind(), dns::Type::Cname);
+		assert_eq!(answers[0].ttl(), 300);
+		assert_eq!(answers[0].target().unwrap(), "api.cdc.steampowered.com");
+		assert!(answers[0].ipv6().is_err());
+
+		assert_eq!(answers[1].name(), "api.cdc.steampowered.com");
+		assert_eq!(answers[1].ipv6().unwrap(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+	}
+
+	#[test]
+	fn truncated() {
+		let raw = hex::decode("bc8181800001000100000000036170690000010001c00c00010001").unwrap();
+		let dns = dns::Packet::new(&raw[..]).unwrap();
+
+		assert_eq!(dns.questions().count(), 1);
+		assert!(dns.answers().unwrap().next().unwrap().is_err());
+		assert!(dns.additionals().is_err());
+		assert!(dns::Packet::new(&raw[.. 11]).is_err());
+	}
+
+	#[test]
+	fn mutable() {
+		let mut raw = hex::decode("bc8101000000000000000000").unwrap();
+
+		let mut dns = dns::Packet::new(&mut raw[..]).unwrap();
+		dns.set_id(0x1234).unwrap();
+		dns.set_opcode(dns::OpCode::Update).unwrap();
+		dns.set_flags(dns::flag::RESPONSE).unwrap();
+		dns.set_response_code(dns::ResponseCode::Refused).unwrap();
+
+		assert_eq!(dns.id(), 0x1234);
+		assert_eq!(dns.flags(), dns::flag::RESPONSE);
+		assert_eq!(dns.opcode(), dns::OpCode::Update);
+		assert_eq!(dns.response_code(), dns::ResponseCode::Refused);
+	}
+}
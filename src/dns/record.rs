@@ -0,0 +1,272 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::dns::{Name, Type, Class};
+
+/// Question entry.
+#[derive(Copy, Clone, Debug)]
+pub struct Question<'a> {
+	name:  Name<'a>,
+	kind:  Type,
+	class: Class,
+}
+
+impl<'a> Question<'a> {
+	/// Parse the question at the given offset of the message, returning it
+	/// with the offset right after it.
+	pub fn new(buffer: &'a [u8], offset: usize) -> Result<(Question<'a>, usize)> {
+		let (name, offset) = Name::new(buffer, offset)?;
+		let mut fields = buffer.get(offset ..).ok_or(Error::SmallBuffer)?;
+
+		let kind  = fields.read_u16::<BigEndian>().map_err(|_| Error::SmallBuffer)?.into();
+		let class = fields.read_u16::<BigEndian>().map_err(|_| Error::SmallBuffer)?.into();
+
+		Ok((Question { name, kind, class }, offset + 4))
+	}
+
+	/// Queried name.
+	pub fn name(&self) -> Name<'a> {
+		self.name
+	}
+
+	/// Queried type.
+	pub fn kind(&self) -> Type {
+		self.kind
+	}
+
+	/// Queried class.
+	pub fn class(&self) -> Class {
+		self.class
+	}
+}
+
+/// Resource record.
+#[derive(Copy, Clone, Debug)]
+pub struct Record<'a> {
+	buffer: &'a [u8],
+
+	name:  Name<'a>,
+	kind:  Type,
+	class: Class,
+	ttl:   u32,
+	data:  (usize, usize),
+}
+
+impl<'a> Record<'a> {
+	/// Parse the resource record at the given offset of the message,
+	/// returning it with the offset right after it.
+	pub fn new(buffer: &'a [u8], offset: usize) -> Result<(Record<'a>, usize)> {
+		let (name, offset) = Name::new(buffer, offset)?;
+		let mut fields = buffer.get(offset ..).ok_or(Error::SmallBuffer)?;
+
+		if fields.len() < 10 {
+			Err(Error::SmallBuffer)?
+		}
+
+		let kind   = fields.read_u16::<BigEndian>()?.into();
+		let class  = fields.read_u16::<BigEndian>()?.into();
+		let ttl    = fields.read_u32::<BigEndian>()?;
+		let length = fields.read_u16::<BigEndian>()? as usize;
+		let start  = offset + 10;
+
+		if buffer.len() < start + length {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok((Record { buffer, name, kind, class, ttl, data: (start, length) }, start + length))
+	}
+
+	/// Owner name.
+	pub fn name(&self) -> Name<'a> {
+		self.name
+	}
+
+	/// Record type.
+	pub fn kind(&self) -> Type {
+		self.kind
+	}
+
+	/// Record class.
+	pub fn class(&self) -> Class {
+		self.class
+	}
+
+	/// Time to live in seconds.
+	pub fn ttl(&self) -> u32 {
+		self.ttl
+	}
+
+	/// Raw record data.
+	pub fn data(&self) -> &'a [u8] {
+		&self.buffer[self.data.0 .. self.data.0 + self.data.1]
+	}
+
+	/// Address of an A record.
+	pub fn ipv4(&self) -> Result<Ipv4Addr> {
+		let data = self.data();
+
+		if self.kind != Type::A || data.len() != 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(Ipv4Addr::new(data[0], data[1], data[2], data[3]))
+	}
+
+	/// Address of an AAAA record.
+	pub fn ipv6(&self) -> Result<Ipv6Addr> {
+		let data = self.data();
+
+		if self.kind != Type::Aaaa || data.len() != 16 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(data);
+
+		Ok(octets.into())
+	}
+
+	/// Name of a NS, CNAME or PTR record.
+	pub fn target(&self) -> Result<Name<'a>> {
+		match self.kind {
+			Type::Ns | Type::Cname | Type::Ptr =>
+				self.name_at(0),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+	}
+
+	/// Preference and exchange of a MX record.
+	pub fn mail_exchange(&self) -> Result<(u16, Name<'a>)> {
+		if self.kind != Type::Mx || self.data.1 < 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(((&self.data()[0 ..]).read_u16::<BigEndian>()?, self.name_at(2)?))
+	}
+
+	/// Character strings of a TXT record.
+	pub fn text(&self) -> Result<Strings<'a>> {
+		if self.kind != Type::Txt {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(Strings { buffer: self.data() })
+	}
+
+	/// Parse a name within the record data, it must not extend past it.
+	fn name_at(&self, offset: usize) -> Result<Name<'a>> {
+		let (name, end) = Name::new(self.buffer, self.data.0 + offset)?;
+
+		if end > self.data.0 + self.data.1 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(name)
+	}
+}
+
+/// Iterator over the character strings of a TXT record.
+#[derive(Debug)]
+pub struct Strings<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for Strings<'a> {
+	type Item = Result<&'a [u8]>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let length = *self.buffer.first()? as usize;
+
+		if self.buffer.len() < length + 1 {
+			self.buffer = &[];
+			return Some(Err(Error::SmallBuffer));
+		}
+
+		let value = &self.buffer[1 .. length + 1];
+		self.buffer = &self.buffer[length + 1 ..];
+
+		Some(Ok(value))
+	}
+}
+
+/// Iterator over the questions of a message.
+#[derive(Debug)]
+pub struct Questions<'a> {
+	pub(in crate::dns) buffer:    &'a [u8],
+	pub(in crate::dns) offset:    usize,
+	pub(in crate::dns) remaining: u16,
+}
+
+impl<'a> Iterator for Questions<'a> {
+	type Item = Result<Question<'a>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining == 0 {
+			return None;
+		}
+
+		match Question::new(self.buffer, self.offset) {
+			Ok((question, offset)) => {
+				self.offset     = offset;
+				self.remaining -= 1;
+
+				Some(Ok(question))
+			}
+
+			Err(error) => {
+				self.remaining = 0;
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+/// Iterator over the resource records of a section.
+#[derive(Debug)]
+pub struct Records<'a> {
+	pub(in crate::dns) buffer:    &'a [u8],
+	pub(in crate::dns) offset:    usize,
+	pub(in crate::dns) remaining: u16,
+}
+
+impl<'a> Iterator for Records<'a> {
+	type Item = Result<Record<'a>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining == 0 {
+			return None;
+		}
+
+		match Record::new(self.buffer, self.offset) {
+			Ok((record, offset)) => {
+				self.offset     = offset;
+				self.remaining -= 1;
+
+				Some(Ok(record))
+			}
+
+			Err(error) => {
+				self.remaining = 0;
+				Some(Err(error))
+			}
+		}
+	}
+}
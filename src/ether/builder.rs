@@ -22,6 +22,7 @@ use crate::builder::{Builder as Build, Finalization};
 use crate::packet::{AsPacket, AsPacketMut};
 use crate::ether::Packet;
 use crate::ether::Protocol;
+use crate::ether::Tag;
 
 /// Ethernet frame builder.
 #[derive(Debug)]
@@ -96,6 +97,41 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
+	/// Add an 802.1Q tag, calling it again stacks 802.1ad tags for QinQ with
+	/// the first call being the outermost tag.
+	pub fn vlan(mut self, pcp: u8, dei: bool, vid: u16) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		if pcp > 0b111 || vid > 0x0fff {
+			Err(Error::InvalidValue)?
+		}
+
+		let length = self.buffer.length();
+		self.buffer.more(4)?;
+
+		let data = self.buffer.data_mut();
+
+		// Move the inner protocol after the new tag.
+		data.copy_within(length - 2 .. length, length + 2);
+
+		// Only the innermost tag is a customer tag, any outer one becomes a
+		// service tag.
+		if length > 14 {
+			Tag::unchecked(&mut data[length - 6 .. length - 2])
+				.set_protocol(Protocol::PBridge)?;
+		}
+
+		Tag::unchecked(&mut data[length - 2 .. length + 2])
+			.set_protocol(Protocol::Vlan)?
+			.set_pcp(pcp)?
+			.set_dei(dei)?
+			.set_vid(vid)?;
+
+		Ok(self)
+	}
+
 	/// Payload for the frame.
 	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
 		if self.payload {
@@ -112,6 +148,20 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
+	/// Build an ARP packet inside the Ethernet frame.
+	pub fn arp(mut self) -> Result<crate::arp::Builder<B>> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self = self.protocol(Protocol::Arp)?;
+
+		let mut arp = crate::arp::Builder::with(self.buffer)?;
+		arp.finalizer().extend(self.finalizer);
+
+		Ok(arp)
+	}
+
 	/// Build an IP packet inside the Ethernet frame.
 	pub fn ip(mut self) -> Result<crate::ip::Builder<B>> {
 		if self.payload {
@@ -124,11 +174,11 @@ impl<B: Buffer> Builder<B> {
 		self.finalizer.add(move |out| {
 			match out[offset + length] >> 4 {
 				4 =>
-					Cursor::new(&mut out[offset + 12 ..])
+					Cursor::new(&mut out[offset + length - 2 ..])
 						.write_u16::<BigEndian>(Protocol::Ipv4.into())?,
 
 				6 =>
-					Cursor::new(&mut out[offset + 12 ..])
+					Cursor::new(&mut out[offset + length - 2 ..])
 						.write_u16::<BigEndian>(Protocol::Ipv6.into())?,
 
 				_ =>
@@ -147,7 +197,7 @@ impl<B: Buffer> Builder<B> {
 
 #[cfg(test)]
 mod test {
-	use std::net::Ipv4Addr;
+	use std::net::{Ipv4Addr, Ipv6Addr};
 	use crate::builder::Builder;
 	use crate::packet::Packet;
 	use crate::ether;
@@ -189,4 +239,78 @@ mod test {
 		assert_eq!(udp.destination(), 9001);
 		assert!(udp.is_valid(&ip::Packet::from(&ip)));
 	}
+
+	#[test]
+	fn simple_v6() {
+		let packet = ether::Builder::default()
+			.destination("00:23:69:63:59:be".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.ip().unwrap().v6().unwrap()
+				.hop_limit(64).unwrap()
+				.source("2001:db8::1".parse().unwrap()).unwrap()
+				.destination("2001:db8::2".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1337).unwrap()
+					.destination(9001).unwrap()
+					.build().unwrap();
+
+		let ether = ether::Packet::new(packet).unwrap();
+		assert_eq!(ether.protocol(), ether::Protocol::Ipv6);
+
+		let ip = ip::v6::Packet::new(ether.payload()).unwrap();
+		assert_eq!(ip.payload_length(), 8);
+		assert_eq!(ip::Protocol::from(ip.next_header()), ip::Protocol::Udp);
+		assert_eq!(ip.hop_limit(), 64);
+		assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(ip.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(udp.source(), 1337);
+		assert_eq!(udp.destination(), 9001);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn qinq() {
+		let packet = ether::Builder::default()
+			.destination("00:23:69:63:59:be".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.vlan(3, false, 100).unwrap()
+			.vlan(0, true, 200).unwrap()
+			.ip().unwrap().v4().unwrap()
+				.ttl(64).unwrap()
+				.source("66.102.1.108".parse().unwrap()).unwrap()
+				.destination("192.168.0.79".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1337).unwrap()
+					.destination(9001).unwrap()
+					.build().unwrap();
+
+		let ether = ether::Packet::new(packet).unwrap();
+		assert_eq!(ether.destination(), "00:23:69:63:59:be".parse().unwrap());
+		assert_eq!(ether.source(), "e4:b3:18:26:63:a3".parse().unwrap());
+
+		let tags = ether.tags().collect::<Vec<_>>();
+		assert_eq!(tags.len(), 2);
+		assert_eq!(tags[0].protocol(), ether::Protocol::PBridge);
+		assert_eq!(tags[0].pcp(), 3);
+		assert_eq!(tags[0].vid(), 100);
+		assert_eq!(tags[1].protocol(), ether::Protocol::Vlan);
+		assert!(tags[1].dei());
+		assert_eq!(tags[1].vid(), 200);
+		assert_eq!(ether.protocol(), ether::Protocol::Ipv4);
+
+		let ip = ip::v4::Packet::new(ether.payload()).unwrap();
+		assert!(ip.is_valid());
+
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(udp.destination(), 9001);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn vlan_invalid() {
+		assert!(ether::Builder::default().vlan(8, false, 1).is_err());
+		assert!(ether::Builder::default().vlan(0, false, 0x1000).is_err());
+	}
 }
@@ -15,8 +15,12 @@
 mod protocol;
 pub use self::protocol::Protocol;
 
+/// 802.1Q and 802.1ad tags.
+pub mod tag;
+pub use self::tag::Tag;
+
 mod packet;
-pub use self::packet::Packet;
+pub use self::packet::{Packet, TagIter};
 
 mod builder;
 pub use self::builder::Builder;
@@ -19,6 +19,7 @@ use std::io::Cursor;
 
 use crate::error::*;
 use crate::ether::Protocol;
+use crate::ether::tag::{self, Tag};
 use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
 
 /// Ethernet frame parser.
@@ -29,14 +30,13 @@ pub struct Packet<B> {
 sized!(Packet,
 header {
     min:  14,
-    max:  14,
-    size: 14,
+    size: p => p.header_length(),
 }
 
 payload {
     min:  0,
     max:  1486,
-    size: p => p.buffer.as_ref().len() - 14,
+    size: p => p.buffer.as_ref().len() - p.header_length(),
 });
 
 impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
@@ -44,6 +44,7 @@ impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
         f.debug_struct("ether::Packet")
             .field("destination", &self.source())
             .field("source", &self.destination())
+            .field("tags", &self.tags().collect::<Vec<_>>())
             .field("protocol", &self.protocol())
             .field("payload", &self.payload())
             .finish()
@@ -66,6 +67,12 @@ impl<B: AsRef<[u8]>> Packet<B> {
             Err(Error::SmallBuffer)?
         }
 
+        // A tag that doesn't fit the buffer leaves its protocol identifier
+        // where the inner protocol is expected.
+        if tag::is_tag(packet.protocol()) {
+            Err(Error::SmallBuffer)?
+        }
+
         Ok(packet)
     }
 }
@@ -113,13 +120,15 @@ impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for
 
 impl<B: AsRef<[u8]>> P for Packet<B> {
     fn split(&self) -> (&[u8], &[u8]) {
-        self.buffer.as_ref().split_at(14)
+        let header = self.header_length();
+        self.buffer.as_ref().split_at(header)
     }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
     fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
-        self.buffer.as_mut().split_at_mut(14)
+        let header = self.header_length();
+        self.buffer.as_mut().split_at_mut(header)
     }
 }
 
@@ -134,13 +143,37 @@ impl<B: AsRef<[u8]>> Packet<B> {
         self.buffer.as_ref()[6..12].into()
     }
 
-    /// Protocol of the inner packet.
+    /// 802.1Q and 802.1ad tags, outermost first.
+    pub fn tags(&self) -> TagIter<'_> {
+        TagIter {
+            buffer: &self.buffer.as_ref()[12..self.header_length() - 2],
+        }
+    }
+
+    /// Protocol of the inner packet, after any tags.
     pub fn protocol(&self) -> Protocol {
-        (&self.buffer.as_ref()[12..])
+        (&self.buffer.as_ref()[self.header_length() - 2..])
             .read_u16::<BigEndian>()
             .unwrap()
             .into()
     }
+
+    fn header_length(&self) -> usize {
+        let buffer = self.buffer.as_ref();
+        let mut offset = 12;
+
+        while buffer.len() >= offset + 6 {
+            let protocol = (&buffer[offset..]).read_u16::<BigEndian>().unwrap();
+
+            if !tag::is_tag(protocol.into()) {
+                break;
+            }
+
+            offset += 4;
+        }
+
+        offset + 2
+    }
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
@@ -158,12 +191,44 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
         Ok(self)
     }
 
-    /// Inner protocol.
+    /// Inner protocol, after any tags.
     pub fn set_protocol(&mut self, value: Protocol) -> Result<&mut Self> {
-        Cursor::new(&mut self.buffer.as_mut()[12..]).write_u16::<BigEndian>(value.into())?;
+        let offset = self.header_length() - 2;
+        Cursor::new(&mut self.buffer.as_mut()[offset..]).write_u16::<BigEndian>(value.into())?;
 
         Ok(self)
     }
+
+    /// Tag at the given depth, outermost first.
+    pub fn tag_mut(&mut self, index: usize) -> Result<Tag<&mut [u8]>> {
+        let offset = 12 + index * 4;
+
+        if offset + 4 > self.header_length() - 2 {
+            Err(Error::InvalidValue)?
+        }
+
+        Ok(Tag::unchecked(&mut self.buffer.as_mut()[offset..offset + 4]))
+    }
+}
+
+/// Iterator over 802.1Q and 802.1ad tags.
+pub struct TagIter<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Iterator for TagIter<'a> {
+    type Item = Tag<&'a [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+
+        let (tag, rest) = self.buffer.split_at(4);
+        self.buffer = rest;
+
+        Some(Tag::unchecked(tag))
+    }
 }
 
 #[cfg(test)]
@@ -213,4 +278,47 @@ mod test {
 		
         assert_eq!(ether.protocol(), ether::Protocol::Ipv6);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn values_qinq() {
+        let raw = hex::decode("ffffffffffff000c293c1c1a88a86064810010c808060001080006040001000c293c1c1ac0a80001000000000000c0a80002").unwrap();
+        let ether = ether::Packet::new(&raw[..]).unwrap();
+
+        let tags = ether.tags().collect::<Vec<_>>();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].protocol(), ether::Protocol::PBridge);
+        assert_eq!(tags[0].pcp(), 3);
+        assert!(!tags[0].dei());
+        assert_eq!(tags[0].vid(), 100);
+        assert_eq!(tags[1].protocol(), ether::Protocol::Vlan);
+        assert_eq!(tags[1].pcp(), 0);
+        assert!(tags[1].dei());
+        assert_eq!(tags[1].vid(), 200);
+
+        assert_eq!(ether.protocol(), ether::Protocol::Arp);
+        assert_eq!(ether.payload().len(), 28);
+        assert_eq!(&ether.payload()[..2], &[0x00, 0x01]);
+    }
+
+    #[test]
+    fn mutable_qinq() {
+        let mut raw = hex::decode("ffffffffffff000c293c1c1a88a86064810010c808060001080006040001000c293c1c1ac0a80001000000000000c0a80002").unwrap();
+        let mut ether = ether::Packet::new(&mut raw[..]).unwrap();
+
+        ether.tag_mut(1).unwrap().set_vid(300).unwrap();
+        ether.set_protocol(ether::Protocol::Rarp).unwrap();
+        assert!(ether.tag_mut(2).is_err());
+
+        let tags = ether.tags().collect::<Vec<_>>();
+        assert_eq!(tags[0].vid(), 100);
+        assert_eq!(tags[1].vid(), 300);
+        assert!(tags[1].dei());
+        assert_eq!(ether.protocol(), ether::Protocol::Rarp);
+    }
+
+    #[test]
+    fn truncated_tag() {
+        let raw = hex::decode("ffffffffffff000c293c1c1a8100").unwrap();
+        assert!(ether::Packet::new(&raw[..]).is_err());
+    }
+}
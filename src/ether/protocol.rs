@@ -84,6 +84,9 @@ pub enum Protocol {
 	///
 	QinQ,
 
+	/// Transparent Ethernet Bridging, used to carry Ethernet frames over GRE.
+	TransparentEthernetBridging,
+
 	///
 	Unknown(u16),
 }
@@ -116,6 +119,7 @@ impl From<u16> for Protocol {
 			0x88f7 => Ptp,
 			0x8902 => Cfm,
 			0x9100 => QinQ,
+			0x6558 => TransparentEthernetBridging,
 			n      => Unknown(n),
 		}
 	}
@@ -149,6 +153,7 @@ impl Into<u16> for Protocol {
 			Ptp            => 0x88f7,
 			Cfm            => 0x8902,
 			QinQ           => 0x9100,
+			TransparentEthernetBridging => 0x6558,
 			Unknown(n)     => n,
 		}
 	}
@@ -0,0 +1,144 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::ether::Protocol;
+
+/// 802.1Q and 802.1ad tag parser.
+pub struct Tag<B> {
+	buffer: B,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Tag<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ether::Tag")
+			.field("protocol", &self.protocol())
+			.field("pcp", &self.pcp())
+			.field("dei", &self.dei())
+			.field("vid", &self.vid())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Tag<B> {
+	/// Create a tag without checking the buffer.
+	pub fn unchecked(buffer: B) -> Tag<B> {
+		Tag { buffer }
+	}
+
+	/// Parse a tag, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Tag<B>> {
+		let tag = Tag::unchecked(buffer);
+
+		if tag.buffer.as_ref().len() < 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		if !is_tag(tag.protocol()) {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(tag)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Tag<B> {
+	fn as_ref(&self) -> &[u8] {
+		&self.buffer.as_ref()[.. 4]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Tag<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		&mut self.buffer.as_mut()[.. 4]
+	}
+}
+
+impl<B: AsRef<[u8]>> Tag<B> {
+	/// Tag protocol identifier.
+	pub fn protocol(&self) -> Protocol {
+		(&self.buffer.as_ref()[0 ..]).read_u16::<BigEndian>().unwrap().into()
+	}
+
+	/// Priority code point.
+	pub fn pcp(&self) -> u8 {
+		self.buffer.as_ref()[2] >> 5
+	}
+
+	/// Drop eligible indicator.
+	pub fn dei(&self) -> bool {
+		self.buffer.as_ref()[2] & 0b0001_0000 != 0
+	}
+
+	/// VLAN identifier.
+	pub fn vid(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap() & 0x0fff
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Tag<B> {
+	/// Tag protocol identifier.
+	pub fn set_protocol(&mut self, value: Protocol) -> Result<&mut Self> {
+		if !is_tag(value) {
+			Err(Error::InvalidValue)?
+		}
+
+		Cursor::new(&mut self.buffer.as_mut()[0 ..])
+			.write_u16::<BigEndian>(value.into())?;
+
+		Ok(self)
+	}
+
+	/// Priority code point.
+	pub fn set_pcp(&mut self, value: u8) -> Result<&mut Self> {
+		if value > 0b111 {
+			Err(Error::InvalidValue)?
+		}
+
+		let old = self.buffer.as_ref()[2];
+		self.buffer.as_mut()[2] = (old & 0b0001_1111) | value << 5;
+
+		Ok(self)
+	}
+
+	/// Drop eligible indicator.
+	pub fn set_dei(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[2];
+		self.buffer.as_mut()[2] = if value { old | 0b0001_0000 } else { old & !0b0001_0000 };
+
+		Ok(self)
+	}
+
+	/// VLAN identifier.
+	pub fn set_vid(&mut self, value: u16) -> Result<&mut Self> {
+		if value > 0x0fff {
+			Err(Error::InvalidValue)?
+		}
+
+		let old = self.buffer.as_ref()[2];
+		self.buffer.as_mut()[2] = (old & 0b1111_0000) | (value >> 8) as u8;
+		self.buffer.as_mut()[3] = value as u8;
+
+		Ok(self)
+	}
+}
+
+/// Check if the protocol introduces an 802.1Q or 802.1ad tag.
+pub fn is_tag(protocol: Protocol) -> bool {
+	matches!(protocol, Protocol::Vlan | Protocol::PBridge | Protocol::QinQ)
+}
@@ -0,0 +1,340 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::net::IpAddr;
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::{self, Protocol};
+use crate::ip::v6::extension::Extension;
+use crate::dissect::{Entry, Layer};
+
+/// Flow key, identifying the conversation a packet belongs to.
+///
+/// # Note
+///
+/// For ICMP echo messages both ports are the echo identifier, for other
+/// protocols without ports, or fragments not carrying them, they are zero.
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub struct Key {
+	/// Source address.
+	pub source: IpAddr,
+
+	/// Destination address.
+	pub destination: IpAddr,
+
+	/// Source port.
+	pub source_port: u16,
+
+	/// Destination port.
+	pub destination_port: u16,
+
+	/// Transport protocol.
+	pub protocol: Protocol,
+}
+
+impl Key {
+	/// Extract the key of an IP packet.
+	pub fn new<B: AsRef<[u8]>>(packet: &ip::Packet<B>) -> Result<Key> {
+		let (source, destination, protocol, payload) = match *packet {
+			ip::Packet::V4(ref packet) => {
+				let payload = if packet.offset() == 0 { packet.payload() } else { &[][..] };
+				(packet.source().into(), packet.destination().into(), packet.protocol(), payload)
+			}
+
+			ip::Packet::V6(ref packet) => {
+				// The capture may end before the packet does.
+				let buffer = packet.payload();
+				let offset = (packet.payload_offset()? - 40).min(buffer.len());
+
+				let (protocol, payload) = match packet.protocol()? {
+					// Only the first fragment carries the ports.
+					Protocol::Ipv6Frag =>
+						(fragmented(packet), &[][..]),
+
+					protocol =>
+						(protocol, &buffer[offset ..]),
+				};
+
+				(packet.source().into(), packet.destination().into(), protocol, payload)
+			}
+		};
+
+		let (source_port, destination_port) = ports(protocol, payload);
+
+		Ok(Key {
+			source,
+			destination,
+			source_port,
+			destination_port,
+			protocol,
+		})
+	}
+
+	/// Extract the key from dissected layers, using the outermost IP layer.
+	pub fn from_layers(layers: &[Entry<'_>]) -> Result<Key> {
+		for entry in layers {
+			match entry.layer {
+				Layer::Ipv4(ref packet) =>
+					return Key::new(&ip::Packet::V4(ip::v4::Packet::unchecked(packet.as_ref()))),
+
+				Layer::Ipv6(ref packet) =>
+					return Key::new(&ip::Packet::V6(packet.clone())),
+
+				_ =>
+					(),
+			}
+		}
+
+		Err(Error::InvalidPacket)?
+	}
+
+	/// IP version of the flow.
+	pub fn version(&self) -> u8 {
+		match self.source {
+			IpAddr::V4(_) => 4,
+			IpAddr::V6(_) => 6,
+		}
+	}
+
+	/// The key of the opposite direction.
+	pub fn reverse(&self) -> Key {
+		Key {
+			source:           self.destination,
+			destination:      self.source,
+			source_port:      self.destination_port,
+			destination_port: self.source_port,
+			protocol:         self.protocol,
+		}
+	}
+
+	/// Whether the key is in canonical form, with the lowest endpoint first.
+	pub fn is_canonical(&self) -> bool {
+		(self.source, self.source_port) <= (self.destination, self.destination_port)
+	}
+
+	/// The direction-independent form of the key, both directions of a
+	/// conversation have the same canonical key.
+	pub fn canonical(&self) -> Key {
+		if self.is_canonical() {
+			*self
+		}
+		else {
+			self.reverse()
+		}
+	}
+
+	/// A hash of the canonical key that's the same for both directions, and
+	/// stable across runs and platforms.
+	pub fn stable_hash(&self) -> u64 {
+		// 64-bit FNV-1a.
+		fn feed(hash: u64, bytes: &[u8]) -> u64 {
+			bytes.iter().fold(hash, |hash, &byte|
+				(hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3))
+		}
+
+		fn address(hash: u64, address: IpAddr) -> u64 {
+			match address {
+				IpAddr::V4(address) => feed(hash, &address.octets()),
+				IpAddr::V6(address) => feed(hash, &address.octets()),
+			}
+		}
+
+		let key  = self.canonical();
+		let hash = feed(0xcbf2_9ce4_8422_2325, &[key.version(), key.protocol.into()]);
+		let hash = address(hash, key.source);
+		let hash = feed(hash, &key.source_port.to_be_bytes());
+		let hash = address(hash, key.destination);
+
+		feed(hash, &key.destination_port.to_be_bytes())
+	}
+}
+
+/// Protocol of the fragmented packet, from the fragment header.
+fn fragmented<B: AsRef<[u8]>>(packet: &ip::v6::Packet<B>) -> Protocol {
+	packet.extensions()
+		.filter_map(|extension| match extension {
+			Ok(Extension::Fragment(fragment)) =>
+				Some(fragment.next_header()),
+
+			_ =>
+				None,
+		})
+		.next()
+		.unwrap_or(Protocol::Ipv6Frag)
+}
+
+/// Ports, or echo identifier, of a transport header.
+fn ports(protocol: Protocol, payload: &[u8]) -> (u16, u16) {
+	let word = |offset: usize| u16::from_be_bytes([payload[offset], payload[offset + 1]]);
+
+	match protocol {
+		Protocol::Tcp | Protocol::Udp | Protocol::UdpLite | Protocol::Sctp if payload.len() >= 4 =>
+			(word(0), word(2)),
+
+		Protocol::Icmp if payload.len() >= 6 && (payload[0] == 0 || payload[0] == 8) =>
+			(word(4), word(4)),
+
+		Protocol::Ipv6Icmp if payload.len() >= 6 && (payload[0] == 128 || payload[0] == 129) =>
+			(word(4), word(4)),
+
+		_ =>
+			(0, 0),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::IpAddr;
+	use crate::builder::Builder;
+	use crate::pcap::LinkType;
+	use crate::flow::Key;
+	use crate::ip;
+	use crate::ether;
+
+	fn address(value: &str) -> IpAddr {
+		value.parse().unwrap()
+	}
+
+	#[test]
+	fn tcp() {
+		let raw = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(40000).unwrap()
+				.destination(80).unwrap()
+				.build().unwrap();
+
+		let key = Key::new(&ip::Packet::new(&raw[..]).unwrap()).unwrap();
+		assert_eq!(key.source, address("192.168.0.79"));
+		assert_eq!(key.destination, address("66.102.1.108"));
+		assert_eq!(key.source_port, 40000);
+		assert_eq!(key.destination_port, 80);
+		assert_eq!(key.protocol, ip::Protocol::Tcp);
+		assert_eq!(key.version(), 4);
+
+		assert!(!key.is_canonical());
+		assert_eq!(key.canonical(), key.reverse());
+		assert_eq!(key.reverse().reverse(), key);
+	}
+
+	#[test]
+	fn udp_v6() {
+		let raw = ip::v6::Builder::default()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(5353).unwrap()
+				.destination(53).unwrap()
+				.build().unwrap();
+
+		let key = Key::new(&ip::Packet::new(&raw[..]).unwrap()).unwrap();
+		assert_eq!(key.source_port, 5353);
+		assert_eq!(key.destination_port, 53);
+		assert_eq!(key.protocol, ip::Protocol::Udp);
+		assert_eq!(key.version(), 6);
+		assert!(key.is_canonical());
+	}
+
+	#[test]
+	fn echo() {
+		let raw = ip::v4::Builder::default()
+			.icmp().unwrap()
+				.echo().unwrap().request().unwrap()
+					.identifier(42).unwrap()
+					.sequence(1).unwrap()
+					.build().unwrap();
+
+		let key = Key::new(&ip::Packet::new(&raw[..]).unwrap()).unwrap();
+		assert_eq!(key.source_port, 42);
+		assert_eq!(key.destination_port, 42);
+	}
+
+	#[test]
+	fn symmetric() {
+		let key = Key {
+			source:           address("10.0.0.2"),
+			destination:      address("10.0.0.1"),
+			source_port:      1234,
+			destination_port: 80,
+			protocol:         ip::Protocol::Tcp,
+		};
+
+		assert_eq!(key.stable_hash(), key.reverse().stable_hash());
+		assert_eq!(key.stable_hash(), 0x6694_8b54_3f0e_0924);
+
+		let other = Key { source_port: 1235, .. key };
+		assert_ne!(key.stable_hash(), other.stable_hash());
+	}
+
+	#[test]
+	fn layers() {
+		let raw = ether::Builder::default()
+			.ip().unwrap().v4().unwrap()
+				.source("10.0.0.1".parse().unwrap()).unwrap()
+				.destination("10.0.0.2".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1).unwrap()
+					.destination(2).unwrap()
+					.build().unwrap();
+
+		let key = Key::from_layers(&crate::dissect(LinkType::Ethernet, &raw)).unwrap();
+		assert_eq!(key.source, address("10.0.0.1"));
+		assert_eq!(key.destination_port, 2);
+
+		assert!(Key::from_layers(&crate::dissect(LinkType::Ethernet, &raw[.. 10])).is_err());
+	}
+
+	#[test]
+	fn truncated_v6() {
+		let raw = ether::Builder::default()
+			.ip().unwrap().v6().unwrap()
+				.source("2001:db8::1".parse().unwrap()).unwrap()
+				.destination("2001:db8::2".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1).unwrap()
+					.destination(2).unwrap()
+					.payload(&[0x42; 100]).unwrap()
+					.build().unwrap();
+
+		let key = Key::from_layers(&crate::dissect(LinkType::Ethernet, &raw[.. 82])).unwrap();
+		assert_eq!(key.source, address("2001:db8::1"));
+		assert_eq!(key.source_port, 1);
+		assert_eq!(key.destination_port, 2);
+
+		let key = Key::from_layers(&crate::dissect(LinkType::Ethernet, &raw[.. 56])).unwrap();
+		assert_eq!(key.protocol, ip::Protocol::Udp);
+		assert_eq!(key.source_port, 0);
+	}
+
+	#[test]
+	fn fragment_v6() {
+		let mut raw = hex::decode("6000000000102c4020010db800000000000000000000000120010db80000000000000000000000021100000012345678003500350008dead").unwrap();
+
+		let key = Key::new(&ip::Packet::new(&raw[..]).unwrap()).unwrap();
+		assert_eq!(key.protocol, ip::Protocol::Udp);
+		assert_eq!(key.source_port, 53);
+
+		// Fragment at offset 100.
+		raw[42] = 0x03;
+		raw[43] = 0x20;
+
+		let key = Key::new(&ip::Packet::new(&raw[..]).unwrap()).unwrap();
+		assert_eq!(key.protocol, ip::Protocol::Udp);
+		assert_eq!(key.source_port, 0);
+		assert_eq!(key.destination_port, 0);
+	}
+}
@@ -0,0 +1,342 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::ether;
+use crate::gre::Packet;
+use crate::gre::Flags;
+
+/// GRE packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	checksum: bool,
+	key:      Option<u32>,
+	sequence: Option<u32>,
+	prepared: bool,
+	payload:  bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		use crate::size::header::Min;
+		buffer.next(Packet::<()>::min())?;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			checksum: false,
+			key:      None,
+			sequence: None,
+			prepared: false,
+			payload:  false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		self.prepare()?;
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Include a checksum covering the header and the payload.
+	pub fn checksum(mut self) -> Result<Self> {
+		if self.prepared {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.checksum = true;
+		Ok(self)
+	}
+
+	/// Key identifying the flow within the tunnel.
+	pub fn key(mut self, value: u32) -> Result<Self> {
+		if self.prepared {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.key = Some(value);
+		Ok(self)
+	}
+
+	/// Sequence number.
+	pub fn sequence(mut self, value: u32) -> Result<Self> {
+		if self.prepared {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.sequence = Some(value);
+		Ok(self)
+	}
+
+	/// Protocol type of the payload.
+	pub fn protocol(mut self, value: ether::Protocol) -> Result<Self> {
+		Packet::unchecked(self.buffer.data_mut()).set_protocol(value)?;
+		Ok(self)
+	}
+
+	/// Payload for the packet.
+	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.prepare()?;
+		self.payload = true;
+
+		for byte in value {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+
+	/// Build a bridged Ethernet frame.
+	pub fn ether(mut self) -> Result<ether::Builder<B>> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self = self.protocol(ether::Protocol::TransparentEthernetBridging)?;
+		self.prepare()?;
+
+		let mut ether = ether::Builder::with(self.buffer)?;
+		ether.finalizer().extend(self.finalizer);
+
+		Ok(ether)
+	}
+
+	/// Build an IPv4 or IPv6 packet.
+	pub fn ip(mut self) -> Result<crate::ip::Builder<B>> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.prepare()?;
+
+		let offset = self.buffer.offset();
+		let length = self.buffer.length();
+
+		self.finalizer.add(move |out| {
+			match out[offset + length] >> 4 {
+				4 =>
+					Cursor::new(&mut out[offset + 2 ..])
+						.write_u16::<BigEndian>(ether::Protocol::Ipv4.into())?,
+
+				6 =>
+					Cursor::new(&mut out[offset + 2 ..])
+						.write_u16::<BigEndian>(ether::Protocol::Ipv6.into())?,
+
+				_ =>
+					unreachable!()
+			}
+
+			Ok(())
+		});
+
+		let mut ip = crate::ip::Builder::with(self.buffer)?;
+		ip.finalizer().extend(self.finalizer);
+
+		Ok(ip)
+	}
+
+	/// Write the flags and optional fields, after which the header can't
+	/// change anymore.
+	fn prepare(&mut self) -> Result<()> {
+		if self.prepared {
+			return Ok(());
+		}
+
+		self.prepared = true;
+
+		let mut flags  = Flags::empty();
+		let mut fields = Vec::new();
+
+		if self.checksum {
+			flags |= Flags::CHECKSUM;
+			fields.extend_from_slice(&[0; 4]);
+		}
+
+		if let Some(key) = self.key {
+			flags |= Flags::KEY;
+			fields.extend_from_slice(&key.to_be_bytes());
+		}
+
+		if let Some(sequence) = self.sequence {
+			flags |= Flags::SEQUENCE;
+			fields.extend_from_slice(&sequence.to_be_bytes());
+		}
+
+		Cursor::new(&mut self.buffer.data_mut()[0 ..])
+			.write_u16::<BigEndian>(flags.bits())?;
+
+		for byte in &fields {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		if self.checksum {
+			let offset = self.buffer.offset();
+
+			self.finalizer.add(move |out| {
+				Packet::unchecked(&mut out[offset ..]).update_checksum()?;
+				Ok(())
+			});
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::ip;
+	use crate::icmp;
+	use crate::gre;
+
+	#[test]
+	fn ipv4() {
+		let packet = ip::v4::Builder::default()
+			.source("10.0.0.1".parse().unwrap()).unwrap()
+			.destination("10.0.0.2".parse().unwrap()).unwrap()
+			.gre().unwrap()
+				.checksum().unwrap()
+				.sequence(7).unwrap()
+				.ip().unwrap().v4().unwrap()
+					.source("192.168.0.1".parse().unwrap()).unwrap()
+					.destination("192.168.0.2".parse().unwrap()).unwrap()
+					.icmp().unwrap()
+						.echo().unwrap().request().unwrap()
+							.identifier(42).unwrap()
+							.sequence(2).unwrap()
+							.payload(b"test").unwrap()
+							.build().unwrap();
+
+		let ip = ip::v4::Packet::new(&packet[..]).unwrap();
+		assert_eq!(ip.protocol(), ip::Protocol::Gre);
+		assert!(ip.is_valid());
+
+		let gre = gre::Packet::new(ip.payload()).unwrap();
+		assert_eq!(gre.flags(), gre::flag::CHECKSUM | gre::flag::SEQUENCE);
+		assert_eq!(gre.protocol(), ether::Protocol::Ipv4);
+		assert_eq!(gre.sequence(), Some(7));
+		assert_eq!(gre.key(), None);
+		assert!(gre.is_valid());
+
+		let inner = match gre.ip().unwrap() {
+			ip::Packet::V4(inner) => inner,
+			_ => panic!("expected an IPv4 packet"),
+		};
+
+		assert_eq!(inner.source(), "192.168.0.1".parse::<Ipv4Addr>().unwrap());
+		assert_eq!(inner.length(), 20 + 8 + 4);
+		assert!(inner.is_valid());
+		assert!(icmp::Packet::new(inner.payload()).unwrap().is_valid());
+	}
+
+	#[test]
+	fn ipv6() {
+		let packet = ip::v4::Builder::default()
+			.gre().unwrap()
+				.ip().unwrap().v6().unwrap()
+					.source("2001:db8::1".parse().unwrap()).unwrap()
+					.destination("2001:db8::2".parse().unwrap()).unwrap()
+					.udp().unwrap()
+						.source(53).unwrap()
+						.destination(9001).unwrap()
+						.build().unwrap();
+
+		let ip  = ip::v4::Packet::new(&packet[..]).unwrap();
+		let gre = gre::Packet::new(ip.payload()).unwrap();
+		assert!(gre.flags().is_empty());
+		assert_eq!(gre.protocol(), ether::Protocol::Ipv6);
+		assert!(matches!(gre.ip().unwrap(), ip::Packet::V6(_)));
+	}
+
+	#[test]
+	fn ether() {
+		let packet = ip::v4::Builder::default()
+			.gre().unwrap()
+				.key(0xdead_beef).unwrap()
+				.ether().unwrap()
+					.source("00:11:22:33:44:55".parse().unwrap()).unwrap()
+					.payload(b"lol").unwrap()
+					.build().unwrap();
+
+		let ip  = ip::v4::Packet::new(&packet[..]).unwrap();
+		let gre = gre::Packet::new(ip.payload()).unwrap();
+		assert_eq!(gre.key(), Some(0xdead_beef));
+		assert_eq!(gre.protocol(), ether::Protocol::TransparentEthernetBridging);
+		assert_eq!(gre.ether().unwrap().payload(), b"lol");
+	}
+
+	#[test]
+	fn payload() {
+		let packet = gre::Builder::default()
+			.checksum().unwrap()
+			.protocol(ether::Protocol::Mpls).unwrap()
+			.payload(&[1, 2, 3]).unwrap()
+			.build().unwrap();
+
+		let gre = gre::Packet::new(&packet[..]).unwrap();
+		assert_eq!(gre.protocol(), ether::Protocol::Mpls);
+		assert_eq!(gre.payload(), &[1, 2, 3]);
+		assert!(gre.is_valid());
+
+		assert!(gre::Builder::default()
+			.payload(&[1, 2, 3]).unwrap()
+			.key(1).is_err());
+	}
+}
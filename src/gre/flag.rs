@@ -0,0 +1,42 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use bitflags::bitflags;
+
+bitflags! {
+	/// GRE flags.
+	pub struct Flags: u16 {
+		/// Checksum field is present.
+		const CHECKSUM = 0b1000_0000_0000_0000;
+
+		/// Routing field is present, deprecated by RFC 2784.
+		const ROUTING = 0b0100_0000_0000_0000;
+
+		/// Key field is present.
+		const KEY = 0b0010_0000_0000_0000;
+
+		/// Sequence number field is present.
+		const SEQUENCE = 0b0001_0000_0000_0000;
+
+		/// Strict source route, deprecated by RFC 2784.
+		const STRICT = 0b0000_1000_0000_0000;
+	}
+}
+
+pub const CHECKSUM: Flags = Flags::CHECKSUM;
+pub const ROUTING:  Flags = Flags::ROUTING;
+pub const KEY:      Flags = Flags::KEY;
+pub const SEQUENCE: Flags = Flags::SEQUENCE;
+pub const STRICT:   Flags = Flags::STRICT;
@@ -0,0 +1,335 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ether;
+use crate::ip;
+use crate::gre::Flags;
+use crate::gre::checksum;
+
+/// GRE packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  4,
+		max:  16,
+		size: p => p.header_length(),
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - p.header_length(),
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("gre::Packet")
+			.field("flags", &self.flags())
+			.field("version", &self.version())
+			.field("protocol", &self.protocol())
+			.field("checksum", &self.checksum())
+			.field("key", &self.key())
+			.field("sequence", &self.sequence())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a GRE packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a GRE packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		// Source routing was deprecated by RFC 2784 and isn't supported.
+		if packet.flags().intersects(Flags::ROUTING | Flags::STRICT) {
+			Err(Error::InvalidPacket)?
+		}
+
+		// The enhanced GRE header used by PPTP has a different layout.
+		if packet.version() != 0 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if packet.buffer.as_ref().len() < packet.header_length() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		let header = self.header_length();
+		self.buffer.as_ref().split_at(header)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		let header = self.header_length();
+		self.buffer.as_mut().split_at_mut(header)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Length of the header including the optional fields.
+	fn header_length(&self) -> usize {
+		let flags = self.flags();
+
+		4 + if flags.contains(Flags::CHECKSUM) { 4 } else { 0 }
+		  + if flags.contains(Flags::KEY) { 4 } else { 0 }
+		  + if flags.contains(Flags::SEQUENCE) { 4 } else { 0 }
+	}
+
+	/// Offset of the field enabled by the given flag.
+	fn field(&self, flag: Flags) -> Option<usize> {
+		let flags = self.flags();
+
+		if !flags.contains(flag) {
+			return None;
+		}
+
+		let mut offset = 4;
+
+		for &before in &[Flags::CHECKSUM, Flags::KEY, Flags::SEQUENCE] {
+			if before == flag {
+				break;
+			}
+
+			if flags.contains(before) {
+				offset += 4;
+			}
+		}
+
+		Some(offset)
+	}
+
+	/// Packet flags.
+	pub fn flags(&self) -> Flags {
+		Flags::from_bits_truncate((&self.buffer.as_ref()[0 ..]).read_u16::<BigEndian>().unwrap())
+	}
+
+	/// Protocol version, always 0.
+	pub fn version(&self) -> u8 {
+		self.buffer.as_ref()[1] & 0b111
+	}
+
+	/// Protocol type of the payload.
+	pub fn protocol(&self) -> ether::Protocol {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap().into()
+	}
+
+	/// Checksum, if present.
+	pub fn checksum(&self) -> Option<u16> {
+		self.field(Flags::CHECKSUM).map(|offset|
+			(&self.buffer.as_ref()[offset ..]).read_u16::<BigEndian>().unwrap())
+	}
+
+	/// Key, if present.
+	pub fn key(&self) -> Option<u32> {
+		self.field(Flags::KEY).map(|offset|
+			(&self.buffer.as_ref()[offset ..]).read_u32::<BigEndian>().unwrap())
+	}
+
+	/// Sequence number, if present.
+	pub fn sequence(&self) -> Option<u32> {
+		self.field(Flags::SEQUENCE).map(|offset|
+			(&self.buffer.as_ref()[offset ..]).read_u32::<BigEndian>().unwrap())
+	}
+
+	/// Verify the packet is valid by calculating the checksum, packets without
+	/// checksum are always valid.
+	pub fn is_valid(&self) -> bool {
+		self.checksum().is_none_or(|value| checksum(self.buffer.as_ref()) == value)
+	}
+
+	/// Parse the payload as a bridged Ethernet frame.
+	pub fn ether(&self) -> Result<ether::Packet<&[u8]>> {
+		if self.protocol() != ether::Protocol::TransparentEthernetBridging {
+			Err(Error::InvalidPacket)?
+		}
+
+		ether::Packet::new(self.payload())
+	}
+
+	/// Parse the payload as an IPv4 or IPv6 packet.
+	pub fn ip(&self) -> Result<ip::Packet<&[u8]>> {
+		match self.protocol() {
+			ether::Protocol::Ipv4 =>
+				Ok(ip::v4::Packet::new(self.payload())?.into()),
+
+			ether::Protocol::Ipv6 =>
+				Ok(ip::v6::Packet::new(self.payload())?.into()),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Protocol type of the payload.
+	pub fn set_protocol(&mut self, value: ether::Protocol) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>(value.into())?;
+
+		Ok(self)
+	}
+
+	/// Key, the field has to be present.
+	pub fn set_key(&mut self, value: u32) -> Result<&mut Self> {
+		let offset = self.field(Flags::KEY).ok_or(Error::InvalidPacket)?;
+		Cursor::new(&mut self.buffer.as_mut()[offset ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Sequence number, the field has to be present.
+	pub fn set_sequence(&mut self, value: u32) -> Result<&mut Self> {
+		let offset = self.field(Flags::SEQUENCE).ok_or(Error::InvalidPacket)?;
+		Cursor::new(&mut self.buffer.as_mut()[offset ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Recalculate and set the checksum value, if present.
+	pub fn update_checksum(&mut self) -> Result<&mut Self> {
+		if let Some(offset) = self.field(Flags::CHECKSUM) {
+			let value = checksum(self.buffer.as_ref());
+			Cursor::new(&mut self.buffer.as_mut()[offset ..])
+				.write_u16::<BigEndian>(value)?;
+		}
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::ip;
+	use crate::gre;
+
+	#[test]
+	fn simple() {
+		let raw = hex::decode("00000800450000140001000040ff65e80a0000010a000002").unwrap();
+		let gre = gre::Packet::new(&raw[..]).unwrap();
+
+		assert!(gre.flags().is_empty());
+		assert_eq!(gre.version(), 0);
+		assert_eq!(gre.protocol(), ether::Protocol::Ipv4);
+		assert_eq!(gre.checksum(), None);
+		assert_eq!(gre.key(), None);
+		assert!(gre.is_valid());
+		assert_eq!(gre.payload().len(), 20);
+
+		match gre.ip().unwrap() {
+			ip::Packet::V4(ip) => assert_eq!(ip.source(), "10.0.0.1".parse::<std::net::Ipv4Addr>().unwrap()),
+			_ => panic!("expected an IPv4 packet"),
+		}
+
+		assert!(gre.ether().is_err());
+	}
+
+	#[test]
+	fn optional() {
+		let mut raw = hex::decode("b00065580000000000000001000000020011223344556677").unwrap();
+		gre::Packet::unchecked(&mut raw[..]).update_checksum().unwrap();
+
+		let gre = gre::Packet::new(&raw[..]).unwrap();
+		assert_eq!(gre.flags(), gre::flag::CHECKSUM | gre::flag::KEY | gre::flag::SEQUENCE);
+		assert_eq!(gre.protocol(), ether::Protocol::TransparentEthernetBridging);
+		assert_eq!(gre.key(), Some(1));
+		assert_eq!(gre.sequence(), Some(2));
+		assert!(gre.is_valid());
+		assert_eq!(gre.payload(), &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77]);
+
+		gre::Packet::unchecked(&mut raw[..]).set_sequence(3).unwrap();
+		assert!(!gre::Packet::new(&raw[..]).unwrap().is_valid());
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(gre::Packet::new(&[0x00, 0x00, 0x08][..]).is_err());
+		assert!(gre::Packet::new(&hex::decode("200008000000").unwrap()[..]).is_err());
+		assert!(gre::Packet::new(&hex::decode("4000080000000000").unwrap()[..]).is_err());
+		assert!(gre::Packet::new(&hex::decode("3001880b00040000000000010000000200112233").unwrap()[..]).is_err());
+		assert!(gre::Packet::unchecked(&mut [0u8, 0, 8, 0][..]).set_key(1).is_err());
+	}
+}
@@ -0,0 +1,160 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::address_mask::Packet;
+
+/// Address Mask Request/Reply packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	kind: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(12)?;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			kind: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind {
+			Err(Error::InvalidPacket)?
+		}
+
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Make it a request.
+	pub fn request(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::AddressMaskRequest.into();
+
+		Ok(self)
+	}
+
+	/// Make it a reply.
+	pub fn reply(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::AddressMaskReply.into();
+
+		Ok(self)
+	}
+
+	/// Packet identifier.
+	pub fn identifier(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[4 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Packet sequence.
+	pub fn sequence(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Address mask.
+	pub fn mask(mut self, value: Ipv4Addr) -> Result<Self> {
+		self.buffer.data_mut()[8 .. 12].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let packet = ip::v4::Builder::default()
+			.source("192.168.0.1".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.address_mask().unwrap()
+					.reply().unwrap()
+					.identifier(42).unwrap()
+					.sequence(2).unwrap()
+					.mask(Ipv4Addr::new(255, 255, 255, 0)).unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(packet).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid());
+
+		let mask = icmp.address_mask().unwrap();
+		assert!(mask.is_reply());
+		assert_eq!(mask.identifier(), 42);
+		assert_eq!(mask.sequence(), 2);
+		assert_eq!(mask.mask(), Ipv4Addr::new(255, 255, 255, 0));
+	}
+
+	#[test]
+	fn missing_kind() {
+		assert!(icmp::Builder::default().address_mask().unwrap().build().is_err());
+	}
+}
@@ -18,8 +18,11 @@ use byteorder::{WriteBytesExt, BigEndian};
 use crate::error::*;
 use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
+use crate::packet::Packet as P;
+use crate::ip;
 use crate::icmp::checksum;
-use crate::icmp::{echo, timestamp, information};
+use crate::icmp::{echo, timestamp, information, previous, parameter_problem, redirect_message};
+use crate::icmp::{router_advertisement, router_solicitation, address_mask, trace_route};
 
 /// ICMP packet builder.
 #[derive(Debug)]
@@ -75,6 +78,83 @@ impl<B: Buffer> Builder<B> {
 
 		Ok(timestamp)
 	}
+
+	/// Create a Source Quench, Destination Unreachable or Time Exceeded packet.
+	pub fn previous(self) -> Result<previous::Builder<B>> {
+		let mut previous = previous::Builder::with(self.buffer)?;
+		previous.finalizer().extend(self.finalizer);
+
+		Ok(previous)
+	}
+
+	/// Create a Parameter Problem packet.
+	pub fn parameter_problem(self) -> Result<parameter_problem::Builder<B>> {
+		let mut parameter_problem = parameter_problem::Builder::with(self.buffer)?;
+		parameter_problem.finalizer().extend(self.finalizer);
+
+		Ok(parameter_problem)
+	}
+
+	/// Create a Redirect Message packet.
+	pub fn redirect_message(self) -> Result<redirect_message::Builder<B>> {
+		let mut redirect_message = redirect_message::Builder::with(self.buffer)?;
+		redirect_message.finalizer().extend(self.finalizer);
+
+		Ok(redirect_message)
+	}
+
+	/// Create a Router Advertisement packet.
+	pub fn router_advertisement(self) -> Result<router_advertisement::Builder<B>> {
+		let mut router_advertisement = router_advertisement::Builder::with(self.buffer)?;
+		router_advertisement.finalizer().extend(self.finalizer);
+
+		Ok(router_advertisement)
+	}
+
+	/// Create a Router Solicitation packet.
+	pub fn router_solicitation(self) -> Result<router_solicitation::Builder<B>> {
+		let mut router_solicitation = router_solicitation::Builder::with(self.buffer)?;
+		router_solicitation.finalizer().extend(self.finalizer);
+
+		Ok(router_solicitation)
+	}
+
+	/// Create an Address Mask Request/Reply packet.
+	pub fn address_mask(self) -> Result<address_mask::Builder<B>> {
+		let mut address_mask = address_mask::Builder::with(self.buffer)?;
+		address_mask.finalizer().extend(self.finalizer);
+
+		Ok(address_mask)
+	}
+
+	/// Create a Traceroute packet.
+	pub fn trace_route(self) -> Result<trace_route::Builder<B>> {
+		let mut trace_route = trace_route::Builder::with(self.buffer)?;
+		trace_route.finalizer().extend(self.finalizer);
+
+		Ok(trace_route)
+	}
+}
+
+/// Octets of the invoking packet payload quoted in error messages by default.
+pub(in crate::icmp) const QUOTE: usize = 8;
+
+/// Maximum amount of the invoking packet quoted in error messages, so that the
+/// whole message doesn't exceed the minimum IPv4 reassembly size.
+pub(in crate::icmp) const MAXIMUM: usize = 576 - 20 - 8;
+
+/// Append the header of the invoking packet and the given amount of its
+/// payload.
+pub(in crate::icmp) fn quote<B: Buffer, T: AsRef<[u8]>>(buffer: &mut B, packet: &ip::v4::Packet<T>, length: usize) -> Result<()> {
+	let (header, payload) = packet.split();
+	let length = length.min(payload.len()).min(MAXIMUM.saturating_sub(header.len()));
+
+	for byte in header.iter().chain(&payload[.. length]) {
+		buffer.more(1)?;
+		*buffer.data_mut().last_mut().unwrap() = *byte;
+	}
+
+	Ok(())
 }
 
 pub(in crate::icmp) fn prepare<B: Buffer>(finalizer: &mut Finalization, buffer: &B) {
@@ -102,6 +102,19 @@ pub enum ParameterProblem {
 	Unknown(u8),
 }
 
+/// Codes for Time Exceeded packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimeExceeded {
+	/// Time to live reached zero in transit.
+	TtlExpiredInTransit,
+
+	/// Fragments were missing when reassembly timed out.
+	FragmentReassemblyTimeExceeded,
+
+	/// Unknown code.
+	Unknown(u8),
+}
+
 impl From<u8> for DestinationUnreachable {
 	fn from(value: u8) -> Self {
 		use self::DestinationUnreachable::*;
@@ -207,3 +220,64 @@ impl Into<u8> for ParameterProblem {
 		}
 	}
 }
+
+impl From<u8> for TimeExceeded {
+	fn from(value: u8) -> Self {
+		use self::TimeExceeded::*;
+
+		match value {
+			0 => TtlExpiredInTransit,
+			1 => FragmentReassemblyTimeExceeded,
+			v => Unknown(v),
+		}
+	}
+}
+
+impl From<TimeExceeded> for u8 {
+	fn from(value: TimeExceeded) -> u8 {
+		use self::TimeExceeded::*;
+
+		match value {
+			TtlExpiredInTransit            => 0,
+			FragmentReassemblyTimeExceeded => 1,
+			Unknown(v)                     => v,
+		}
+	}
+}
+
+/// Codes for Traceroute packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TraceRoute {
+	/// Outbound packet successfully forwarded.
+	Forwarded,
+
+	/// No route for outbound packet, packet discarded.
+	NoRoute,
+
+	/// Unknown code.
+	Unknown(u8),
+}
+
+impl From<u8> for TraceRoute {
+	fn from(value: u8) -> Self {
+		use self::TraceRoute::*;
+
+		match value {
+			0 => Forwarded,
+			1 => NoRoute,
+			v => Unknown(v),
+		}
+	}
+}
+
+impl From<TraceRoute> for u8 {
+	fn from(value: TraceRoute) -> u8 {
+		use self::TraceRoute::*;
+
+		match value {
+			Forwarded  => 0,
+			NoRoute    => 1,
+			Unknown(v) => v,
+		}
+	}
+}
@@ -0,0 +1,581 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::Buffer;
+use crate::icmp::checksum;
+
+/// Version of the extension structure.
+pub const VERSION: u8 = 2;
+
+/// Minimum length of the original datagram when extensions are present.
+pub const ORIGINAL: usize = 128;
+
+/// Class of an extension object.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Class {
+	/// MPLS Label Stack, RFC 4950.
+	MplsLabelStack,
+
+	/// Interface Information, RFC 5837.
+	InterfaceInformation,
+
+	/// Unknown class.
+	Unknown(u8),
+}
+
+impl From<u8> for Class {
+	fn from(value: u8) -> Self {
+		use self::Class::*;
+
+		match value {
+			1 => MplsLabelStack,
+			2 => InterfaceInformation,
+			v => Unknown(v),
+		}
+	}
+}
+
+impl From<Class> for u8 {
+	fn from(value: Class) -> u8 {
+		use self::Class::*;
+
+		match value {
+			MplsLabelStack       => 1,
+			InterfaceInformation => 2,
+			Unknown(v)           => v,
+		}
+	}
+}
+
+/// Extension structure appended to an ICMP error message.
+#[derive(Copy, Clone, Debug)]
+pub struct Extension<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Extension<'a> {
+	/// Parse the extension structure, checking the header is correct.
+	pub fn new(buffer: &'a [u8]) -> Result<Extension<'a>> {
+		if buffer.len() < 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		if buffer[0] >> 4 != VERSION {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(Extension { buffer })
+	}
+
+	/// Version of the structure.
+	pub fn version(&self) -> u8 {
+		self.buffer[0] >> 4
+	}
+
+	/// Checksum of the structure, zero when not transmitted.
+	pub fn checksum(&self) -> u16 {
+		(&self.buffer[2 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Verify the structure is valid by calculating the checksum.
+	pub fn is_valid(&self) -> bool {
+		self.checksum() == 0 || checksum(self.buffer) == self.checksum()
+	}
+
+	/// Iterator over the extension objects.
+	pub fn objects(&self) -> Objects<'a> {
+		Objects {
+			buffer: &self.buffer[4 ..],
+		}
+	}
+}
+
+/// Iterator over extension objects.
+#[derive(Debug)]
+pub struct Objects<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for Objects<'a> {
+	type Item = Result<Object<'a>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.buffer.is_empty() {
+			return None;
+		}
+
+		if self.buffer.len() < 4 {
+			self.buffer = &[];
+			return Some(Err(Error::SmallBuffer));
+		}
+
+		let length = (&self.buffer[..]).read_u16::<BigEndian>().unwrap() as usize;
+
+		if length < 4 || length > self.buffer.len() {
+			self.buffer = &[];
+			return Some(Err(Error::InvalidPacket));
+		}
+
+		let object  = Object { buffer: &self.buffer[.. length] };
+		self.buffer = &self.buffer[length ..];
+
+		Some(Ok(object))
+	}
+}
+
+/// Extension object.
+#[derive(Copy, Clone, Debug)]
+pub struct Object<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Object<'a> {
+	/// Object class.
+	pub fn class(&self) -> Class {
+		Class::from(self.buffer[2])
+	}
+
+	/// Object sub-type.
+	pub fn kind(&self) -> u8 {
+		self.buffer[3]
+	}
+
+	/// Object payload.
+	pub fn payload(&self) -> &'a [u8] {
+		&self.buffer[4 ..]
+	}
+
+	/// Parse the object as an MPLS label stack.
+	pub fn labels(&self) -> Result<Labels<'a>> {
+		if self.class() != Class::MplsLabelStack || self.kind() != 1 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if !self.payload().len().is_multiple_of(4) {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(Labels {
+			buffer: self.payload(),
+		})
+	}
+
+	/// Parse the object as interface information.
+	pub fn interface(&self) -> Result<Interface> {
+		if self.class() != Class::InterfaceInformation {
+			Err(Error::InvalidPacket)?
+		}
+
+		Interface::parse(self.kind(), self.payload())
+	}
+}
+
+/// MPLS label stack entry.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Label {
+	/// Label value.
+	pub label: u32,
+
+	/// Traffic class, formerly the experimental bits.
+	pub traffic_class: u8,
+
+	/// Whether it's the bottom of the stack.
+	pub bottom: bool,
+
+	/// Time to live.
+	pub ttl: u8,
+}
+
+impl From<u32> for Label {
+	fn from(value: u32) -> Self {
+		Label {
+			label:         value >> 12,
+			traffic_class: ((value >> 9) & 0b111) as u8,
+			bottom:        value & 0x100 != 0,
+			ttl:           value as u8,
+		}
+	}
+}
+
+impl From<Label> for u32 {
+	fn from(value: Label) -> u32 {
+		(value.label & 0xf_ffff) << 12
+			| u32::from(value.traffic_class & 0b111) << 9
+			| if value.bottom { 0x100 } else { 0 }
+			| u32::from(value.ttl)
+	}
+}
+
+/// Iterator over MPLS label stack entries.
+#[derive(Debug)]
+pub struct Labels<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for Labels<'a> {
+	type Item = Label;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.buffer.len() < 4 {
+			return None;
+		}
+
+		let value   = (&self.buffer[..]).read_u32::<BigEndian>().unwrap();
+		self.buffer = &self.buffer[4 ..];
+
+		Some(Label::from(value))
+	}
+}
+
+/// Role of the interface described by an Interface Information object.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Role {
+	/// Interface the datagram arrived on.
+	Incoming,
+
+	/// Sub-IP component of the incoming interface.
+	SubIp,
+
+	/// Interface the datagram would have been forwarded on.
+	Outgoing,
+
+	/// Next hop the datagram would have been forwarded to.
+	NextHop,
+}
+
+/// Interface Information object.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Interface {
+	/// Role of the interface.
+	pub role: Role,
+
+	/// Interface index.
+	pub index: Option<u32>,
+
+	/// Interface address.
+	pub address: Option<IpAddr>,
+
+	/// Interface name.
+	pub name: Option<String>,
+
+	/// Interface MTU.
+	pub mtu: Option<u32>,
+}
+
+const INDEX:   u8 = 0b1000;
+const ADDRESS: u8 = 0b0100;
+const NAME:    u8 = 0b0010;
+const MTU:     u8 = 0b0001;
+
+impl Interface {
+	/// Create an empty interface description with the given role.
+	pub fn new(role: Role) -> Self {
+		Interface {
+			role,
+			index:   None,
+			address: None,
+			name:    None,
+			mtu:     None,
+		}
+	}
+
+	fn parse(kind: u8, mut buffer: &[u8]) -> Result<Interface> {
+		let mut interface = Interface::new(match kind >> 6 {
+			0 => Role::Incoming,
+			1 => Role::SubIp,
+			2 => Role::Outgoing,
+			_ => Role::NextHop,
+		});
+
+		if kind & INDEX != 0 {
+			interface.index = Some(buffer.read_u32::<BigEndian>().map_err(|_| Error::SmallBuffer)?);
+		}
+
+		if kind & ADDRESS != 0 {
+			if buffer.len() < 4 {
+				Err(Error::SmallBuffer)?
+			}
+
+			let family = (&buffer[..]).read_u16::<BigEndian>().unwrap();
+			buffer = &buffer[4 ..];
+
+			interface.address = Some(match family {
+				1 if buffer.len() >= 4 => {
+					let mut octets = [0; 4];
+					octets.copy_from_slice(&buffer[.. 4]);
+					buffer = &buffer[4 ..];
+
+					IpAddr::V4(Ipv4Addr::from(octets))
+				}
+
+				2 if buffer.len() >= 16 => {
+					let mut octets = [0; 16];
+					octets.copy_from_slice(&buffer[.. 16]);
+					buffer = &buffer[16 ..];
+
+					IpAddr::V6(Ipv6Addr::from(octets))
+				}
+
+				1 | 2 =>
+					Err(Error::SmallBuffer)?,
+
+				_ =>
+					Err(Error::InvalidPacket)?
+			});
+		}
+
+		if kind & NAME != 0 {
+			let length = *buffer.first().ok_or(Error::SmallBuffer)? as usize;
+
+			if length == 0 || !length.is_multiple_of(4) || length > 64 {
+				Err(Error::InvalidPacket)?
+			}
+
+			if buffer.len() < length {
+				Err(Error::SmallBuffer)?
+			}
+
+			let name = &buffer[1 .. length];
+			let end  = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+			interface.name = Some(String::from_utf8_lossy(&name[.. end]).into_owned());
+			buffer = &buffer[length ..];
+		}
+
+		if kind & MTU != 0 {
+			interface.mtu = Some(buffer.read_u32::<BigEndian>().map_err(|_| Error::SmallBuffer)?);
+		}
+
+		Ok(interface)
+	}
+
+	/// Encode the sub-type and payload of the object.
+	fn encode(&self) -> Result<(u8, Vec<u8>)> {
+		let mut kind = match self.role {
+			Role::Incoming => 0,
+			Role::SubIp    => 1,
+			Role::Outgoing => 2,
+			Role::NextHop  => 3,
+		} << 6;
+
+		let mut payload = Vec::new();
+
+		if let Some(index) = self.index {
+			kind |= INDEX;
+			payload.extend_from_slice(&index.to_be_bytes());
+		}
+
+		if let Some(address) = self.address {
+			kind |= ADDRESS;
+
+			match address {
+				IpAddr::V4(address) => {
+					payload.extend_from_slice(&[0, 1, 0, 0]);
+					payload.extend_from_slice(&address.octets());
+				}
+
+				IpAddr::V6(address) => {
+					payload.extend_from_slice(&[0, 2, 0, 0]);
+					payload.extend_from_slice(&address.octets());
+				}
+			}
+		}
+
+		if let Some(name) = &self.name {
+			kind |= NAME;
+
+			if name.len() > 63 {
+				Err(Error::InvalidValue)?
+			}
+
+			let length = (name.len() + 1).div_ceil(4) * 4;
+			payload.push(length as u8);
+			payload.extend_from_slice(name.as_bytes());
+			payload.resize(payload.len() + length - 1 - name.len(), 0);
+		}
+
+		if let Some(mtu) = self.mtu {
+			kind |= MTU;
+			payload.extend_from_slice(&mtu.to_be_bytes());
+		}
+
+		Ok((kind, payload))
+	}
+}
+
+/// Extension objects waiting to be appended to an error message.
+#[derive(Clone, Default, Debug)]
+pub(in crate::icmp) struct Pending {
+	buffer: Vec<u8>,
+}
+
+impl Pending {
+	/// Check if no objects have been added.
+	pub fn is_empty(&self) -> bool {
+		self.buffer.is_empty()
+	}
+
+	/// Add an object with the given class, sub-type and payload.
+	pub fn object(&mut self, class: Class, kind: u8, payload: &[u8]) -> Result<()> {
+		if payload.len() > u16::MAX as usize - 4 {
+			Err(Error::InvalidValue)?
+		}
+
+		self.buffer.extend_from_slice(&((payload.len() + 4) as u16).to_be_bytes());
+		self.buffer.push(class.into());
+		self.buffer.push(kind);
+		self.buffer.extend_from_slice(payload);
+
+		Ok(())
+	}
+
+	/// Add an MPLS label stack object.
+	pub fn labels(&mut self, labels: &[Label]) -> Result<()> {
+		let payload = labels.iter()
+			.flat_map(|&label| u32::from(label).to_be_bytes().to_vec())
+			.collect::<Vec<u8>>();
+
+		self.object(Class::MplsLabelStack, 1, &payload)
+	}
+
+	/// Add an Interface Information object.
+	pub fn interface(&mut self, interface: &Interface) -> Result<()> {
+		let (kind, payload) = interface.encode()?;
+		self.object(Class::InterfaceInformation, kind, &payload)
+	}
+
+	/// Pad the quoted datagram, set its length and append the extension
+	/// structure, the buffer must contain the whole ICMP message.
+	pub fn append<B: Buffer>(&self, buffer: &mut B) -> Result<()> {
+		if self.is_empty() {
+			return Ok(());
+		}
+
+		let length = buffer.data().len() - 8;
+		let padded = ORIGINAL.max(length.div_ceil(4) * 4);
+
+		if padded / 4 > u8::MAX as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		buffer.more(padded - length)?;
+		for byte in &mut buffer.data_mut()[8 + length ..] {
+			*byte = 0;
+		}
+
+		buffer.data_mut()[5] = (padded / 4) as u8;
+
+		let start = buffer.data().len();
+		for byte in [VERSION << 4, 0, 0, 0].iter().chain(&self.buffer) {
+			buffer.more(1)?;
+			*buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		let value = checksum(&buffer.data()[start ..]);
+		buffer.data_mut()[start + 2 .. start + 4].copy_from_slice(&value.to_be_bytes());
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::{IpAddr, Ipv6Addr};
+	use crate::icmp::extension::*;
+
+	#[test]
+	fn parse() {
+		let raw = hex::decode("20003c0a0008010100010101001c020f0000000a00010000c0a80001086574682d302f31000005dc").unwrap();
+		let extension = Extension::new(&raw[..]).unwrap();
+		assert_eq!(extension.version(), 2);
+		assert!(extension.is_valid());
+
+		let objects = extension.objects().collect::<Result<Vec<_>>>().unwrap();
+		assert_eq!(objects.len(), 2);
+
+		assert_eq!(objects[0].class(), Class::MplsLabelStack);
+		assert_eq!(objects[0].labels().unwrap().collect::<Vec<_>>(), vec![Label {
+			label:         16,
+			traffic_class: 0,
+			bottom:        true,
+			ttl:           1,
+		}]);
+		assert!(objects[0].interface().is_err());
+
+		assert_eq!(objects[1].class(), Class::InterfaceInformation);
+		assert_eq!(objects[1].interface().unwrap(), Interface {
+			role:    Role::Incoming,
+			index:   Some(10),
+			address: Some("192.168.0.1".parse().unwrap()),
+			name:    Some("eth-0/1".into()),
+			mtu:     Some(1500),
+		});
+		assert!(objects[1].labels().is_err());
+	}
+
+	#[test]
+	fn invalid() {
+		let mut raw = hex::decode("20003c0a0008010100010101001c020f0000000a00010000c0a80001086574682d302f31000005dc").unwrap();
+		raw[10] = 0x02;
+		assert!(!Extension::new(&raw[..]).unwrap().is_valid());
+
+		raw[2] = 0;
+		raw[3] = 0;
+		assert!(Extension::new(&raw[..]).unwrap().is_valid());
+
+		raw[0] = 0x10;
+		assert!(Extension::new(&raw[..]).is_err());
+
+		let raw = hex::decode("200000000010010100010101").unwrap();
+		let mut objects = Extension::new(&raw[..]).unwrap().objects();
+		assert!(objects.next().unwrap().is_err());
+		assert!(objects.next().is_none());
+	}
+
+	#[test]
+	fn label() {
+		let label = Label {
+			label:         0xf_ffff,
+			traffic_class: 5,
+			bottom:        false,
+			ttl:           64,
+		};
+
+		assert_eq!(u32::from(label), 0xffff_fa40);
+		assert_eq!(Label::from(0xffff_fa40), label);
+	}
+
+	#[test]
+	fn interface() {
+		let interface = Interface {
+			role:    Role::NextHop,
+			address: Some(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+			mtu:     Some(9000),
+			.. Interface::new(Role::NextHop)
+		};
+
+		let (kind, payload) = interface.encode().unwrap();
+		assert_eq!(kind, 0xc5);
+		assert_eq!(payload.len(), 4 + 16 + 4);
+		assert_eq!(Interface::parse(kind, &payload).unwrap(), interface);
+
+		let mut long = Interface::new(Role::Outgoing);
+		long.name = Some("x".repeat(64));
+		assert!(long.encode().is_err());
+	}
+}
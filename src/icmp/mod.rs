@@ -42,6 +42,25 @@ pub mod redirect_message;
 /// Timestamp Request/Reply.
 pub mod timestamp;
 
+/// Router Advertisement.
+pub mod router_advertisement;
+
+/// Router Solicitation.
+pub mod router_solicitation;
+
+/// Address Mask Request/Reply.
+pub mod address_mask;
+
+/// Traceroute.
+pub mod trace_route;
+
+/// Packets quoted in error messages.
+pub mod quoted;
+pub use self::quoted::Quoted;
+
+/// Multi-part message extensions.
+pub mod extension;
+
 /// Calculate the checksum for an ICMP packet.
 pub fn checksum(buffer: &[u8]) -> u16 {
 	use std::io::Cursor;
@@ -63,5 +82,19 @@ pub fn checksum(buffer: &[u8]) -> u16 {
 		}
 	}
 
+	if let Ok(value) = buffer.read_u8() {
+		// if we have a trailing byte, make a padded 16-bit value.
+		let value = (value as u16) << 8;
+
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
 	!result as u16
 }
+
+/// ICMPv6 packet parser and builder.
+pub mod v6;
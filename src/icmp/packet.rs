@@ -174,6 +174,18 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 	kind!(/// Parse a Source Quench, Destination Unreachable or Time Exceeded packet.
 		fn previous[previous_mut]);
+
+	kind!(/// Parse a Router Advertisement packet.
+		fn router_advertisement[router_advertisement_mut]);
+
+	kind!(/// Parse a Router Solicitation packet.
+		fn router_solicitation[router_solicitation_mut]);
+
+	kind!(/// Parse an Address Mask Request/Reply packet.
+		fn address_mask[address_mask_mut]);
+
+	kind!(/// Parse a Traceroute packet.
+		fn trace_route[trace_route_mut]);
 }
 
 /// Checked wrapper for ICMP packets.
@@ -0,0 +1,227 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::code;
+use crate::icmp::extension::{self, Class, Label, Interface};
+use crate::icmp::parameter_problem::Packet;
+
+/// Parameter Problem packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	kind:      bool,
+	quote:     usize,
+	payload:   bool,
+	extension: extension::Pending,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::ParameterProblem.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			kind:      false,
+			quote:     builder::QUOTE,
+			payload:   false,
+			extension: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind || !self.payload {
+			Err(Error::InvalidPacket)?
+		}
+
+		self.extension.append(&mut self.buffer)?;
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Code of the problem.
+	pub fn code(mut self, value: code::ParameterProblem) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Pointer to the octet of the packet that caused the problem.
+	pub fn pointer(mut self, value: u8) -> Result<Self> {
+		self.buffer.data_mut()[4] = value;
+
+		Ok(self)
+	}
+
+	/// Octets of the packet payload to quote, 8 by default.
+	pub fn quote(mut self, value: usize) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.quote = value;
+		Ok(self)
+	}
+
+	/// Packet to cause the message, its header and the start of its payload
+	/// are quoted.
+	pub fn packet<T: AsRef<[u8]>>(mut self, value: &ip::v4::Packet<T>) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+		builder::quote(&mut self.buffer, value, self.quote)?;
+
+		Ok(self)
+	}
+
+	/// Append an extension object with the given class, sub-type and payload.
+	pub fn object(mut self, class: Class, kind: u8, payload: &[u8]) -> Result<Self> {
+		self.extension.object(class, kind, payload)?;
+		Ok(self)
+	}
+
+	/// Append an MPLS label stack extension object.
+	pub fn labels(mut self, value: &[Label]) -> Result<Self> {
+		self.extension.labels(value)?;
+		Ok(self)
+	}
+
+	/// Append an Interface Information extension object.
+	pub fn interface(mut self, value: &Interface) -> Result<Self> {
+		self.extension.interface(value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let raw = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(40000).unwrap()
+				.destination(80).unwrap()
+				.build().unwrap();
+
+		let original = ip::v4::Packet::new(&raw[..]).unwrap();
+		let packet   = ip::v4::Builder::default()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.parameter_problem().unwrap()
+					.code(icmp::code::ParameterProblem::PointerIndicatesError).unwrap()
+					.pointer(8).unwrap()
+					.packet(&original).unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(&packet[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(icmp.kind(), icmp::Kind::ParameterProblem);
+		assert!(icmp.is_valid());
+
+		let problem = icmp::parameter_problem::Packet::new(ip.payload()).unwrap();
+		assert_eq!(problem.pointer(), 8);
+		assert_eq!(&ip.payload()[8 ..], &raw[.. 28]);
+	}
+
+	#[test]
+	fn extension() {
+		let raw = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(40000).unwrap()
+				.destination(80).unwrap()
+				.build().unwrap();
+
+		let original = ip::v4::Packet::new(&raw[..]).unwrap();
+		let packet   = ip::v4::Builder::default()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.parameter_problem().unwrap()
+					.code(icmp::code::ParameterProblem::PointerIndicatesError).unwrap()
+					.pointer(8).unwrap()
+					.object(icmp::extension::Class::Unknown(42), 7, &[1, 2, 3, 4]).unwrap()
+					.packet(&original).unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(&packet[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid());
+		assert_eq!(icmp.payload().len(), 4 + 128 + 4 + 8);
+
+		let problem = icmp::parameter_problem::Packet::new(ip.payload()).unwrap();
+		assert_eq!(problem.pointer(), 8);
+		assert_eq!(problem.length(), 32);
+		assert_eq!(problem.packet().unwrap().source(), "192.168.0.79".parse::<std::net::Ipv4Addr>().unwrap());
+
+		let extension = problem.extension().unwrap().unwrap();
+		assert!(extension.is_valid());
+
+		let object = extension.objects().next().unwrap().unwrap();
+		assert_eq!(object.class(), icmp::extension::Class::Unknown(42));
+		assert_eq!(object.kind(), 7);
+		assert_eq!(object.payload(), &[1, 2, 3, 4]);
+	}
+}
@@ -14,3 +14,6 @@
 
 mod packet;
 pub use self::packet::Packet;
+
+mod builder;
+pub use self::builder::Builder;
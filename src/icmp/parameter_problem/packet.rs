@@ -18,7 +18,8 @@ use crate::error::*;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
 use crate::size;
 use crate::ip;
-use crate::icmp::Kind;
+use crate::icmp::{Kind, Quoted};
+use crate::icmp::extension::Extension;
 
 /// Parameter Problem packet parser.
 pub struct Packet<B> {
@@ -50,6 +51,7 @@ impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
 		f.debug_struct("icmp::parameter_problem::Packet")
 			.field("pointer", &self.pointer())
 			.field("packet", &self.packet())
+			.field("extension", &self.extension())
 			.finish()
 	}
 }
@@ -146,6 +148,44 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 	/// The packet that caused the problem.
 	pub fn packet(&self) -> Result<ip::v4::Packet<&[u8]>> {
-		ip::v4::Packet::new(&self.buffer.as_ref()[8 ..])
+		ip::v4::Packet::new(self.datagram())
+	}
+
+	/// View of the quoted packet that tolerates its truncated payload.
+	pub fn quoted(&self) -> Result<Quoted<'_>> {
+		Quoted::new(self.datagram())
+	}
+
+	/// Length of the quoted packet in 32-bit words when extensions follow it,
+	/// zero otherwise.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[5]
+	}
+
+	/// Extension structure following the quoted packet, if any.
+	pub fn extension(&self) -> Result<Option<Extension<'_>>> {
+		let length = self.length() as usize * 4;
+
+		if length == 0 {
+			return Ok(None);
+		}
+
+		if self.buffer.as_ref().len() < 8 + length {
+			Err(Error::SmallBuffer)?
+		}
+
+		Extension::new(&self.buffer.as_ref()[8 + length ..]).map(Some)
+	}
+
+	fn datagram(&self) -> &[u8] {
+		let buffer = &self.buffer.as_ref()[8 ..];
+		let length = self.length() as usize * 4;
+
+		if length != 0 && length <= buffer.len() {
+			&buffer[.. length]
+		}
+		else {
+			buffer
+		}
 	}
 }
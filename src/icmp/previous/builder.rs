@@ -0,0 +1,309 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::code;
+use crate::icmp::extension::{self, Class, Label, Interface};
+use crate::icmp::previous::Packet;
+
+/// Source Quench, Destination Unreachable and Time Exceeded packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	kind:      bool,
+	quote:     usize,
+	payload:   bool,
+	extension: extension::Pending,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			kind:      false,
+			quote:     builder::QUOTE,
+			payload:   false,
+			extension: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind || !self.payload {
+			Err(Error::InvalidPacket)?
+		}
+
+		if !self.extension.is_empty() && Kind::from(self.buffer.data()[0]) == Kind::SourceQuench {
+			Err(Error::InvalidPacket)?
+		}
+
+		self.extension.append(&mut self.buffer)?;
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Make it a Source Quench.
+	pub fn source_quench(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::SourceQuench.into();
+		self.buffer.data_mut()[1] = 0;
+
+		Ok(self)
+	}
+
+	/// Make it a Destination Unreachable with the given code.
+	pub fn destination_unreachable(mut self, value: code::DestinationUnreachable) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::DestinationUnreachable.into();
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Make it a Time Exceeded with the given code.
+	pub fn time_exceeded(mut self, value: code::TimeExceeded) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::TimeExceeded.into();
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Next-hop MTU for Fragmentation Required messages.
+	pub fn mtu(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Octets of the packet payload to quote, 8 by default.
+	pub fn quote(mut self, value: usize) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.quote = value;
+		Ok(self)
+	}
+
+	/// Packet to cause the message, its header and the start of its payload
+	/// are quoted.
+	pub fn packet<T: AsRef<[u8]>>(mut self, value: &ip::v4::Packet<T>) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+		builder::quote(&mut self.buffer, value, self.quote)?;
+
+		Ok(self)
+	}
+
+	/// Append an extension object with the given class, sub-type and payload.
+	pub fn object(mut self, class: Class, kind: u8, payload: &[u8]) -> Result<Self> {
+		self.extension.object(class, kind, payload)?;
+		Ok(self)
+	}
+
+	/// Append an MPLS label stack extension object.
+	pub fn labels(mut self, value: &[Label]) -> Result<Self> {
+		self.extension.labels(value)?;
+		Ok(self)
+	}
+
+	/// Append an Interface Information extension object.
+	pub fn interface(mut self, value: &Interface) -> Result<Self> {
+		self.extension.interface(value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	fn original() -> Vec<u8> {
+		ip::v4::Builder::default()
+			.id(0x2d87).unwrap()
+			.ttl(1).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(40000).unwrap()
+				.destination(33434).unwrap()
+				.payload(&[0x42; 100]).unwrap()
+				.build().unwrap()
+	}
+
+	fn error<F>(f: F) -> Vec<u8>
+		where F: FnOnce(icmp::previous::Builder) -> icmp::previous::Builder
+	{
+		let raw      = original();
+		let original = ip::v4::Packet::new(&raw[..]).unwrap();
+
+		f(ip::v4::Builder::default()
+			.source("10.0.0.1".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.previous().unwrap())
+			.packet(&original).unwrap()
+			.build().unwrap()
+	}
+
+	#[test]
+	fn time_exceeded() {
+		let raw = error(|b| b.time_exceeded(icmp::code::TimeExceeded::TtlExpiredInTransit).unwrap());
+
+		let ip   = ip::v4::Packet::new(&raw[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(icmp.kind(), icmp::Kind::TimeExceeded);
+		assert_eq!(icmp.code(), 0);
+		assert!(icmp.is_valid());
+		assert_eq!(icmp.payload().len(), 4 + 20 + 8);
+
+		let quoted = &icmp.payload()[4 ..];
+		assert_eq!(quoted, &original()[.. 28]);
+	}
+
+	#[test]
+	fn fragmentation_required() {
+		let raw = error(|b| b
+			.destination_unreachable(icmp::code::DestinationUnreachable::FragmentationRequired).unwrap()
+			.mtu(1400).unwrap()
+			.quote(usize::MAX).unwrap());
+
+		let ip   = ip::v4::Packet::new(&raw[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid());
+
+		let previous = icmp::previous::Packet::new(ip.payload()).unwrap();
+		assert_eq!(previous.mtu(), 1400);
+		assert_eq!(previous.packet().unwrap().as_ref(), &original()[..]);
+	}
+
+	#[test]
+	fn extension() {
+		let raw = error(|b| b
+			.time_exceeded(icmp::code::TimeExceeded::TtlExpiredInTransit).unwrap()
+			.labels(&[icmp::extension::Label {
+				label:         24000,
+				traffic_class: 0,
+				bottom:        true,
+				ttl:           1,
+			}]).unwrap()
+			.interface(&icmp::extension::Interface {
+				index: Some(3),
+				name:  Some("ge-0/0/1".into()),
+				.. icmp::extension::Interface::new(icmp::extension::Role::Incoming)
+			}).unwrap());
+
+		let ip   = ip::v4::Packet::new(&raw[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid());
+
+		let previous = icmp::previous::Packet::new(ip.payload()).unwrap();
+		assert_eq!(previous.length(), 32);
+		assert_eq!(&previous.quoted().unwrap().packet().as_ref()[.. 28], &original()[.. 28]);
+		assert_eq!(&icmp.payload()[4 + 28 .. 4 + 128], &[0; 100][..]);
+
+		let extension = previous.extension().unwrap().unwrap();
+		assert!(extension.is_valid());
+
+		let objects = extension.objects().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(objects.len(), 2);
+		assert_eq!(objects[0].labels().unwrap().next().unwrap().label, 24000);
+
+		let interface = objects[1].interface().unwrap();
+		assert_eq!(interface.index, Some(3));
+		assert_eq!(interface.name.as_deref(), Some("ge-0/0/1"));
+		assert_eq!(interface.address, None);
+	}
+
+	#[test]
+	fn no_extension() {
+		let raw = error(|b| b.time_exceeded(icmp::code::TimeExceeded::TtlExpiredInTransit).unwrap());
+
+		let ip       = ip::v4::Packet::new(&raw[..]).unwrap();
+		let previous = icmp::previous::Packet::new(ip.payload()).unwrap();
+		assert_eq!(previous.length(), 0);
+		assert!(previous.extension().unwrap().is_none());
+	}
+
+	#[test]
+	fn missing() {
+		let raw      = original();
+		let original = ip::v4::Packet::new(&raw[..]).unwrap();
+
+		assert!(icmp::previous::Builder::default()
+			.packet(&original).unwrap()
+			.build().is_err());
+
+		assert!(icmp::previous::Builder::default()
+			.source_quench().unwrap()
+			.packet(&original).unwrap()
+			.quote(16).is_err());
+
+		assert!(icmp::previous::Builder::default()
+			.source_quench().unwrap()
+			.packet(&original).unwrap()
+			.labels(&[]).unwrap()
+			.build().is_err());
+	}
+}
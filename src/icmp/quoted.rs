@@ -0,0 +1,274 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::{self, Protocol};
+use crate::icmp::Kind;
+use crate::flow;
+
+/// Transport header quoted in an ICMP error message.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Transport {
+	/// TCP ports and sequence number.
+	Tcp {
+		/// Source port.
+		source: u16,
+
+		/// Destination port.
+		destination: u16,
+
+		/// Sequence number.
+		sequence: u32,
+	},
+
+	/// UDP ports.
+	Udp {
+		/// Source port.
+		source: u16,
+
+		/// Destination port.
+		destination: u16,
+	},
+
+	/// ICMP Echo Request/Reply identifier and sequence.
+	Echo {
+		/// Echo Request or Echo Reply.
+		kind: Kind,
+
+		/// Identifier.
+		identifier: u16,
+
+		/// Sequence.
+		sequence: u16,
+	},
+
+	/// Any other ICMP message.
+	Icmp {
+		/// Message type.
+		kind: Kind,
+
+		/// Message code.
+		code: u8,
+	},
+
+	/// Other protocols, or fragments after the first.
+	Unknown,
+}
+
+/// View of the packet quoted in an ICMP error message.
+///
+/// # Note
+///
+/// Only the IP header has to be complete, the payload is usually truncated
+/// after the first 8 octets.
+#[derive(Copy, Clone, Debug)]
+pub struct Quoted<'a> {
+	packet: ip::v4::Packet<&'a [u8]>,
+}
+
+impl<'a> Quoted<'a> {
+	/// Parse the quoted packet, only checking the IP header.
+	pub fn new(buffer: &'a [u8]) -> Result<Quoted<'a>> {
+		Ok(Quoted {
+			packet: ip::v4::Packet::no_payload(buffer)?,
+		})
+	}
+
+	/// The quoted IP packet, its payload may be truncated.
+	pub fn packet(&self) -> &ip::v4::Packet<&'a [u8]> {
+		&self.packet
+	}
+
+	/// Whether the quoted packet is shorter than its total length.
+	pub fn is_truncated(&self) -> bool {
+		self.packet.as_ref().len() < self.packet.length() as usize
+	}
+
+	/// Decode the quoted transport header, as far as it was quoted.
+	pub fn transport(&self) -> Result<Transport> {
+		let payload = self.packet.payload();
+
+		if self.packet.offset() != 0 {
+			return Ok(Transport::Unknown);
+		}
+
+		let minimum = match self.packet.protocol() {
+			Protocol::Tcp  => 8,
+			Protocol::Udp  => 4,
+			Protocol::Icmp => 2,
+			_              => return Ok(Transport::Unknown),
+		};
+
+		if payload.len() < minimum {
+			Err(Error::SmallBuffer)?
+		}
+
+		let word = |offset: usize| (&payload[offset ..]).read_u16::<BigEndian>().unwrap();
+
+		Ok(match self.packet.protocol() {
+			Protocol::Tcp => Transport::Tcp {
+				source:      word(0),
+				destination: word(2),
+				sequence:    (&payload[4 ..]).read_u32::<BigEndian>()?,
+			},
+
+			Protocol::Udp => Transport::Udp {
+				source:      word(0),
+				destination: word(2),
+			},
+
+			_ => match Kind::from(payload[0]) {
+				kind @ Kind::EchoRequest | kind @ Kind::EchoReply if payload.len() >= 8 =>
+					Transport::Echo {
+						kind,
+						identifier: word(4),
+						sequence:   word(6),
+					},
+
+				kind =>
+					Transport::Icmp {
+						kind,
+						code: payload[1],
+					},
+			},
+		})
+	}
+
+	/// Key of the flow the quoted packet belongs to, in the direction of the
+	/// quoted packet; reverse it to match the direction of the error.
+	pub fn key(&self) -> Result<flow::Key> {
+		flow::Key::new(&ip::Packet::V4(self.packet))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::quoted::{Quoted, Transport};
+	use crate::flow;
+
+	fn error(original: &[u8]) -> Vec<u8> {
+		let original = ip::v4::Packet::new(original).unwrap();
+
+		ip::v4::Builder::default()
+			.source("10.0.0.1".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.previous().unwrap()
+					.time_exceeded(icmp::code::TimeExceeded::TtlExpiredInTransit).unwrap()
+					.packet(&original).unwrap()
+					.build().unwrap()
+	}
+
+	#[test]
+	fn tcp() {
+		let original = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(40000).unwrap()
+				.destination(443).unwrap()
+				.sequence(0xdead_beef).unwrap()
+				.payload(b"hello").unwrap()
+				.build().unwrap();
+
+		let raw      = error(&original);
+		let ip       = ip::v4::Packet::new(&raw[..]).unwrap();
+		let previous = icmp::previous::Packet::new(ip.payload()).unwrap();
+		let quoted   = previous.quoted().unwrap();
+
+		assert!(previous.packet().is_err());
+		assert!(quoted.is_truncated());
+		assert_eq!(quoted.transport().unwrap(), Transport::Tcp {
+			source:      40000,
+			destination: 443,
+			sequence:    0xdead_beef,
+		});
+
+		let key = flow::Key::new(&ip::Packet::new(&original[..]).unwrap()).unwrap();
+		assert_eq!(quoted.key().unwrap(), key);
+	}
+
+	#[test]
+	fn udp() {
+		let original = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(40000).unwrap()
+				.destination(33434).unwrap()
+				.payload(&[0; 32]).unwrap()
+				.build().unwrap();
+
+		let raw      = error(&original);
+		let ip       = ip::v4::Packet::new(&raw[..]).unwrap();
+		let previous = icmp::previous::Packet::new(ip.payload()).unwrap();
+		let quoted   = previous.quoted().unwrap();
+
+		assert_eq!(quoted.transport().unwrap(), Transport::Udp {
+			source:      40000,
+			destination: 33434,
+		});
+
+		assert_eq!(quoted.key().unwrap().reverse().destination_port, 40000);
+	}
+
+	#[test]
+	fn echo() {
+		let original = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.echo().unwrap().request().unwrap()
+					.identifier(42).unwrap()
+					.sequence(7).unwrap()
+					.payload(&[0; 56]).unwrap()
+					.build().unwrap();
+
+		let raw      = error(&original);
+		let ip       = ip::v4::Packet::new(&raw[..]).unwrap();
+		let previous = icmp::previous::Packet::new(ip.payload()).unwrap();
+		let quoted   = previous.quoted().unwrap();
+
+		assert_eq!(quoted.transport().unwrap(), Transport::Echo {
+			kind:       icmp::Kind::EchoRequest,
+			identifier: 42,
+			sequence:   7,
+		});
+
+		assert_eq!(quoted.key().unwrap().source_port, 42);
+	}
+
+	#[test]
+	fn short() {
+		let original = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(40000).unwrap()
+				.destination(443).unwrap()
+				.build().unwrap();
+
+		let quoted = Quoted::new(&original[.. 24]).unwrap();
+		assert!(quoted.transport().is_err());
+		assert!(Quoted::new(&original[.. 19]).is_err());
+	}
+}
@@ -0,0 +1,170 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::net::Ipv4Addr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::code;
+use crate::icmp::redirect_message::Packet;
+
+/// Redirect Message packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	kind:    bool,
+	quote:   usize,
+	payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::RedirectMessage.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			kind:    false,
+			quote:   builder::QUOTE,
+			payload: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind || !self.payload {
+			Err(Error::InvalidPacket)?
+		}
+
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Code of the redirect.
+	pub fn code(mut self, value: code::RedirectMessage) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Gateway to redirect the packet to.
+	pub fn gateway(mut self, value: Ipv4Addr) -> Result<Self> {
+		self.buffer.data_mut()[4 .. 8].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Octets of the packet payload to quote, 8 by default.
+	pub fn quote(mut self, value: usize) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.quote = value;
+		Ok(self)
+	}
+
+	/// Packet to redirect, its header and the start of its payload are
+	/// quoted.
+	pub fn packet<T: AsRef<[u8]>>(mut self, value: &ip::v4::Packet<T>) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+		builder::quote(&mut self.buffer, value, self.quote)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let raw = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(40000).unwrap()
+				.destination(53).unwrap()
+				.payload(b"abc").unwrap()
+				.build().unwrap();
+
+		let original = ip::v4::Packet::new(&raw[..]).unwrap();
+		let packet   = ip::v4::Builder::default()
+			.source("192.168.0.1".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.redirect_message().unwrap()
+					.code(icmp::code::RedirectMessage::RedirectDatagramForHost).unwrap()
+					.gateway("192.168.0.254".parse().unwrap()).unwrap()
+					.packet(&original).unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(&packet[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(icmp.kind(), icmp::Kind::RedirectMessage);
+		assert_eq!(icmp.code(), 1);
+		assert!(icmp.is_valid());
+
+		let redirect = icmp::redirect_message::Packet::new(ip.payload()).unwrap();
+		assert_eq!(redirect.gateway(), "192.168.0.254".parse::<Ipv4Addr>().unwrap());
+
+		assert_eq!(&ip.payload()[8 ..], &raw[.. 28]);
+	}
+}
@@ -19,7 +19,7 @@ use crate::error::*;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
 use crate::size;
 use crate::ip;
-use crate::icmp::Kind;
+use crate::icmp::{Kind, Quoted};
 
 /// Redirect Message packet parser.
 pub struct Packet<B> {
@@ -150,4 +150,9 @@ impl<B: AsRef<[u8]>> Packet<B> {
 	pub fn packet(&self) -> Result<ip::v4::Packet<&[u8]>> {
 		ip::v4::Packet::new(&self.buffer.as_ref()[8 ..])
 	}
+
+	/// View of the quoted packet that tolerates its truncated payload.
+	pub fn quoted(&self) -> Result<Quoted<'_>> {
+		Quoted::new(&self.buffer.as_ref()[8 ..])
+	}
 }
@@ -0,0 +1,136 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::router_advertisement::Packet;
+
+/// Router Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::RouterAdvertisement.into();
+		buffer.data_mut()[5] = 2;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Seconds the addresses are valid for.
+	pub fn lifetime(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Advertise an address with the given preference.
+	pub fn entry(mut self, address: Ipv4Addr, preference: i32) -> Result<Self> {
+		if self.buffer.data()[4] == u8::MAX {
+			Err(Error::InvalidValue)?
+		}
+
+		self.buffer.data_mut()[4] += 1;
+
+		for byte in address.octets().iter().chain(&preference.to_be_bytes()) {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let packet = ip::v4::Builder::default()
+			.ttl(1).unwrap()
+			.source("192.168.0.1".parse().unwrap()).unwrap()
+			.destination("224.0.0.1".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.router_advertisement().unwrap()
+					.lifetime(1800).unwrap()
+					.entry(Ipv4Addr::new(192, 168, 0, 1), 10).unwrap()
+					.entry(Ipv4Addr::new(192, 168, 0, 2), i32::MIN).unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(packet).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid());
+
+		let advertisement = icmp.router_advertisement().unwrap();
+		assert_eq!(advertisement.lifetime(), 1800);
+		assert_eq!(advertisement.entry_size(), 2);
+		assert_eq!(advertisement.entries().collect::<Vec<_>>(), vec![
+			(Ipv4Addr::new(192, 168, 0, 1), 10),
+			(Ipv4Addr::new(192, 168, 0, 2), i32::MIN),
+		]);
+	}
+}
@@ -0,0 +1,231 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::icmp::Kind;
+
+/// Router Advertisement packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  255 * 255 * 4,
+		size: p => p.count() as usize * p.entry_size() as usize * 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmp::router_advertisement::Packet")
+			.field("count", &self.count())
+			.field("entry_size", &self.entry_size())
+			.field("lifetime", &self.lifetime())
+			.field("entries", &self.entries().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a Router Advertisement packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a Router Advertisement packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		match Kind::from(packet.buffer.as_ref()[0]) {
+			Kind::RouterAdvertisement =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		if packet.entry_size() < 2 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if packet.buffer.as_ref().len() < 8 + packet.count() as usize * packet.entry_size() as usize * 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::Size;
+
+		self.buffer.as_ref()[.. self.size()].split_at(8)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		use crate::size::Size;
+
+		let size = self.size();
+		self.buffer.as_mut()[.. size].split_at_mut(8)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Number of advertised addresses.
+	pub fn count(&self) -> u8 {
+		self.buffer.as_ref()[4]
+	}
+
+	/// Size of each entry in units of 4 octets.
+	pub fn entry_size(&self) -> u8 {
+		self.buffer.as_ref()[5]
+	}
+
+	/// Seconds the addresses are valid for.
+	pub fn lifetime(&self) -> u16 {
+		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Iterator over the advertised addresses and their preference.
+	pub fn entries(&self) -> Entries<'_> {
+		use crate::packet::Packet;
+
+		Entries {
+			buffer: self.payload(),
+			size:   self.entry_size() as usize * 4,
+		}
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Seconds the addresses are valid for.
+	pub fn set_lifetime(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
+
+/// Iterator over the entries of a Router Advertisement.
+#[derive(Debug)]
+pub struct Entries<'a> {
+	buffer: &'a [u8],
+	size:   usize,
+}
+
+impl<'a> Iterator for Entries<'a> {
+	type Item = (Ipv4Addr, i32);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.buffer.len() < self.size {
+			return None;
+		}
+
+		let address    = Ipv4Addr::new(self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]);
+		let preference = (&self.buffer[4 ..]).read_i32::<BigEndian>().unwrap();
+		self.buffer = &self.buffer[self.size ..];
+
+		Some((address, preference))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let raw = hex::decode("0900000002020708c0a800010000000ac0a80002ffffffff").unwrap();
+		let icmp = icmp::Packet::new(&raw[..]).unwrap();
+		let advertisement = icmp.router_advertisement().unwrap();
+
+		assert_eq!(advertisement.count(), 2);
+		assert_eq!(advertisement.lifetime(), 1800);
+		assert_eq!(advertisement.entries().collect::<Vec<_>>(), vec![
+			(Ipv4Addr::new(192, 168, 0, 1), 10),
+			(Ipv4Addr::new(192, 168, 0, 2), -1),
+		]);
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(icmp::router_advertisement::Packet::new(&hex::decode("0900000002020708c0a800010000000a").unwrap()[..]).is_err());
+		assert!(icmp::router_advertisement::Packet::new(&hex::decode("0900000001010708c0a80001").unwrap()[..]).is_err());
+	}
+}
@@ -0,0 +1,96 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::router_solicitation::Packet;
+
+/// Router Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::RouterSolicitation.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let packet = ip::v4::Builder::default()
+			.ttl(1).unwrap()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("224.0.0.2".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.router_solicitation().unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(packet).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(icmp.kind(), icmp::Kind::RouterSolicitation);
+		assert!(icmp.is_valid());
+		assert!(icmp.router_solicitation().is_ok());
+	}
+}
@@ -0,0 +1,161 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::builder;
+use crate::icmp::Kind;
+use crate::icmp::code;
+use crate::icmp::trace_route::Packet;
+
+/// Traceroute packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		buffer.next(20)?;
+		buffer.data_mut()[0] = Kind::TraceRoute.into();
+		buffer.data_mut()[6 .. 8].copy_from_slice(&[0xff, 0xff]);
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl Default for Builder<buffer::Dynamic> {
+	fn default() -> Self {
+		Builder::with(buffer::Dynamic::default()).unwrap()
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Packet code.
+	pub fn code(mut self, value: code::TraceRoute) -> Result<Self> {
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Identifier of the traced IP Traceroute option.
+	pub fn identifier(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[4 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Outbound hop count.
+	pub fn outbound_hops(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[8 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Return hop count.
+	pub fn return_hops(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[10 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Speed of the outbound link in bytes per second.
+	pub fn link_speed(mut self, value: u32) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[12 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// MTU of the outbound link in bytes.
+	pub fn link_mtu(mut self, value: u32) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[16 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let packet = ip::v4::Builder::default()
+			.source("10.0.0.1".parse().unwrap()).unwrap()
+			.destination("10.0.0.2".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.trace_route().unwrap()
+					.code(icmp::code::TraceRoute::Forwarded).unwrap()
+					.identifier(0x1234).unwrap()
+					.outbound_hops(2).unwrap()
+					.return_hops(0xffff).unwrap()
+					.link_speed(1_000_000).unwrap()
+					.link_mtu(1500).unwrap()
+					.build().unwrap();
+
+		let ip   = ip::v4::Packet::new(packet).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert!(icmp.is_valid());
+		assert_eq!(icmp.kind(), icmp::Kind::TraceRoute);
+
+		let trace = icmp.trace_route().unwrap();
+		assert_eq!(trace.code(), icmp::code::TraceRoute::Forwarded);
+		assert_eq!(trace.identifier(), 0x1234);
+		assert_eq!(trace.outbound_hops(), 2);
+		assert_eq!(trace.return_hops(), 0xffff);
+		assert_eq!(trace.link_speed(), 1_000_000);
+		assert_eq!(trace.link_mtu(), 1500);
+	}
+}
@@ -0,0 +1,251 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::icmp::Kind;
+use crate::icmp::code;
+use crate::icmp::packet::Checked;
+
+/// Traceroute packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  20,
+		max:  20,
+		size: 20,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmp::trace_route::Packet")
+			.field("code", &self.code())
+			.field("identifier", &self.identifier())
+			.field("outbound_hops", &self.outbound_hops())
+			.field("return_hops", &self.return_hops())
+			.field("link_speed", &self.link_speed())
+			.field("link_mtu", &self.link_mtu())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a Traceroute packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a Traceroute packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		match Kind::from(packet.buffer.as_ref()[0]) {
+			Kind::TraceRoute =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(20)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(20)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Packet code.
+	pub fn code(&self) -> code::TraceRoute {
+		code::TraceRoute::from(self.buffer.as_ref()[1])
+	}
+
+	/// Identifier of the traced IP Traceroute option.
+	pub fn identifier(&self) -> u16 {
+		(&self.buffer.as_ref()[4 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Outbound hop count copied from the IP Traceroute option.
+	pub fn outbound_hops(&self) -> u16 {
+		(&self.buffer.as_ref()[8 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Return hop count copied from the IP Traceroute option.
+	pub fn return_hops(&self) -> u16 {
+		(&self.buffer.as_ref()[10 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Speed of the outbound link in bytes per second.
+	pub fn link_speed(&self) -> u32 {
+		(&self.buffer.as_ref()[12 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// MTU of the outbound link in bytes.
+	pub fn link_mtu(&self) -> u32 {
+		(&self.buffer.as_ref()[16 ..]).read_u32::<BigEndian>().unwrap()
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Packet code.
+	pub fn set_code(&mut self, value: code::TraceRoute) -> Result<&mut Self> {
+		self.buffer.as_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Outbound hop count.
+	pub fn set_outbound_hops(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[8 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Return hop count.
+	pub fn set_return_hops(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[10 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Create a checksumed setter.
+	pub fn checked(&mut self) -> Checked<'_, Self> {
+		Checked {
+			packet: self
+		}
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]> + 'a> Checked<'a, Packet<B>> {
+	/// Packet code.
+	pub fn set_code(&mut self, value: code::TraceRoute) -> Result<&mut Self> {
+		self.packet.set_code(value)?;
+		Ok(self)
+	}
+
+	/// Outbound hop count.
+	pub fn set_outbound_hops(&mut self, value: u16) -> Result<&mut Self> {
+		self.packet.set_outbound_hops(value)?;
+		Ok(self)
+	}
+
+	/// Return hop count.
+	pub fn set_return_hops(&mut self, value: u16) -> Result<&mut Self> {
+		self.packet.set_return_hops(value)?;
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::icmp;
+
+	#[test]
+	fn simple() {
+		let raw  = hex::decode("1e010122abcdffff000300ff01312d00000005dc").unwrap();
+		let icmp = icmp::Packet::new(&raw[..]).unwrap();
+		assert!(icmp.is_valid());
+
+		let trace = icmp.trace_route().unwrap();
+		assert_eq!(trace.code(), icmp::code::TraceRoute::NoRoute);
+		assert_eq!(trace.identifier(), 0xabcd);
+		assert_eq!(trace.outbound_hops(), 3);
+		assert_eq!(trace.return_hops(), 0xff);
+		assert_eq!(trace.link_speed(), 20_000_000);
+		assert_eq!(trace.link_mtu(), 1500);
+	}
+
+	#[test]
+	fn checked() {
+		let mut raw  = hex::decode("1e000000abcdffff000300ff01312d00000005dc").unwrap();
+		let mut icmp = icmp::Packet::new(&mut raw[..]).unwrap();
+
+		icmp.trace_route_mut().unwrap().checked()
+			.set_outbound_hops(4).unwrap();
+
+		assert!(icmp.is_valid());
+		assert_eq!(icmp.trace_route().unwrap().outbound_hops(), 4);
+	}
+}
@@ -0,0 +1,132 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::ip;
+use crate::icmp::v6::checksum;
+use crate::icmp::v6::{echo, previous, packet_too_big, parameter_problem, ndp};
+
+/// ICMPv6 packet builder.
+///
+/// # Note
+///
+/// The checksum covers a pseudo-header taken from the enclosing IPv6 packet,
+/// so the builder has to be created on top of an IPv6 layer.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(buffer: B) -> Result<Self> {
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(self) -> Result<B::Inner> {
+		Err(Error::InvalidPacket)
+	}
+}
+
+macro_rules! kind {
+	($(#[$attr:meta])* fn $name:ident($($module:ident)::+)) => (
+		$(#[$attr])*
+		pub fn $name(self) -> Result<$($module)::+::Builder<B>> {
+			let mut builder = $($module)::+::Builder::with(self.buffer)?;
+			builder.finalizer().extend(self.finalizer);
+
+			Ok(builder)
+		}
+	)
+}
+
+impl<B: Buffer> Builder<B> {
+	kind!(/// Build an Echo Request/Reply packet.
+		fn echo(echo));
+
+	kind!(/// Build a Destination Unreachable or Time Exceeded packet.
+		fn previous(previous));
+
+	kind!(/// Build a Packet Too Big packet.
+		fn packet_too_big(packet_too_big));
+
+	kind!(/// Build a Parameter Problem packet.
+		fn parameter_problem(parameter_problem));
+
+	kind!(/// Build a Router Solicitation packet.
+		fn router_solicitation(ndp::router_solicitation));
+
+	kind!(/// Build a Router Advertisement packet.
+		fn router_advertisement(ndp::router_advertisement));
+
+	kind!(/// Build a Neighbor Solicitation packet.
+		fn neighbor_solicitation(ndp::neighbor_solicitation));
+
+	kind!(/// Build a Neighbor Advertisement packet.
+		fn neighbor_advertisement(ndp::neighbor_advertisement));
+}
+
+/// Maximum amount of the invoking packet quoted in error messages, so that the
+/// whole message doesn't exceed the minimum IPv6 MTU.
+pub(in crate::icmp::v6) const QUOTE: usize = 1280 - 40 - 8;
+
+pub(in crate::icmp::v6) fn prepare<B: Buffer>(finalizer: &mut Finalization, ip: (usize, usize), buffer: &B) {
+	let offset = buffer.offset();
+	let length = buffer.length();
+
+	finalizer.add(move |out| {
+		let (before, after) = out.split_at_mut(offset);
+		let ip              = ip::v6::Packet::no_payload(&before[ip.0 .. ip.0 + ip.1])?;
+		let icmp            = &mut after[.. length];
+
+		let checksum = checksum(&ip, icmp);
+		Cursor::new(&mut icmp[2 ..])
+			.write_u16::<BigEndian>(checksum)?;
+
+		Ok(())
+	});
+}
+
+#[cfg(test)]
+pub(in crate::icmp::v6) mod test {
+	use crate::buffer::{self, Buffer};
+	use crate::ip;
+
+	/// Create a buffer with an IPv6 header for the ICMPv6 builders to sit on.
+	pub fn buffer() -> buffer::Dynamic {
+		let mut buffer = buffer::Dynamic::new();
+		buffer.next(40).unwrap();
+
+		ip::v6::Packet::unchecked(buffer.data_mut())
+			.set_next_header(ip::Protocol::Ipv6Icmp.into()).unwrap()
+			.set_hop_limit(255).unwrap()
+			.set_source("fe80::1".parse().unwrap()).unwrap()
+			.set_destination("fe80::2".parse().unwrap()).unwrap();
+
+		buffer.data_mut()[0] = 6 << 4;
+		buffer
+	}
+}
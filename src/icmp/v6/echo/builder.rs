@@ -0,0 +1,177 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::echo::Packet;
+
+/// Echo Request/Reply packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip:      (usize, usize),
+	kind:    bool,
+	payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+			kind:    false,
+			payload: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind {
+			Err(Error::InvalidPacket)?
+		}
+
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Make it a request.
+	pub fn request(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::EchoRequest.into();
+
+		Ok(self)
+	}
+
+	/// Make it a reply.
+	pub fn reply(mut self) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::EchoReply.into();
+
+		Ok(self)
+	}
+
+	/// Packet identifier.
+	pub fn identifier(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[4 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Packet sequence.
+	pub fn sequence(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Payload for the packet.
+	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+
+		for byte in value {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.echo().unwrap().request().unwrap()
+				.identifier(42).unwrap()
+				.sequence(2).unwrap()
+				.payload(b"test").unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert_eq!(icmp.kind(), icmp::v6::Kind::EchoRequest);
+		assert!(icmp.is_valid(&ip));
+
+		let echo = icmp.echo().unwrap();
+		assert!(echo.is_request());
+		assert_eq!(echo.identifier(), 42);
+		assert_eq!(echo.sequence(), 2);
+		assert_eq!(echo.payload(), b"test");
+	}
+
+	#[test]
+	fn checked() {
+		let mut packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.echo().unwrap().request().unwrap()
+				.identifier(42).unwrap()
+				.build().unwrap();
+
+		let (ip, icmp) = packet.split_at_mut(40);
+		let ip         = ip::v6::Packet::no_payload(&ip[..]).unwrap();
+		let mut echo   = icmp::v6::echo::Packet::new(icmp).unwrap();
+
+		echo.checked(&ip).make_reply().unwrap().set_identifier(1337).unwrap();
+		assert!(echo.is_reply());
+
+		let icmp = icmp::v6::Packet::new(echo.as_ref()).unwrap();
+		assert!(icmp.is_valid(&ip));
+	}
+}
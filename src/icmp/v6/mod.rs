@@ -0,0 +1,100 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+mod kind;
+pub use self::kind::Kind;
+
+/// ICMPv6 codes.
+pub mod code;
+
+mod packet;
+pub use self::packet::{Packet, Checked};
+
+mod builder;
+pub use self::builder::Builder;
+
+/// Echo Request/Reply.
+pub mod echo;
+
+/// Destination Unreachable and Time Exceeded.
+pub mod previous;
+
+/// Packet Too Big.
+pub mod packet_too_big;
+
+/// Parameter Problem.
+pub mod parameter_problem;
+
+/// Neighbor Discovery messages and options.
+pub mod ndp;
+
+use crate::ip;
+use crate::ip::Protocol;
+
+/// Calculate the checksum for an ICMPv6 packet.
+///
+/// # Note
+///
+/// Since the checksum for ICMPv6 packets includes a pseudo-header based on
+/// the enclosing IPv6 packet, one has to be given.
+pub fn checksum<B: AsRef<[u8]>>(ip: &ip::v6::Packet<B>, buffer: &[u8]) -> u16 {
+	use std::io::Cursor;
+	use byteorder::{WriteBytesExt, ReadBytesExt, BigEndian};
+
+	let mut prefix = [0u8; 40];
+	prefix[0 .. 16].copy_from_slice(&ip.source().octets());
+	prefix[16 .. 32].copy_from_slice(&ip.destination().octets());
+
+	Cursor::new(&mut prefix[32 ..])
+		.write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+	prefix[39] = Protocol::Ipv6Icmp.into();
+
+	let mut result = 0xffffu32;
+	let mut buffer = Cursor::new(buffer);
+	let mut prefix = Cursor::new(&prefix[..]);
+
+	while let Ok(value) = prefix.read_u16::<BigEndian>() {
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
+	while let Ok(value) = buffer.read_u16::<BigEndian>() {
+		// Skip checksum field.
+		if buffer.position() == 4 {
+			continue;
+		}
+
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
+	if let Ok(value) = buffer.read_u8() {
+		// if we have a trailing byte, make a padded 16-bit value.
+		let value = (value as u16) << 8;
+
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
+	!result as u16
+}
@@ -0,0 +1,86 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// Neighbor Discovery options.
+pub mod option;
+pub use self::option::{Option, PrefixInformation};
+
+/// Router Solicitation.
+pub mod router_solicitation;
+
+/// Router Advertisement.
+pub mod router_advertisement;
+
+/// Neighbor Solicitation.
+pub mod neighbor_solicitation;
+
+/// Neighbor Advertisement.
+pub mod neighbor_advertisement;
+
+use crate::error::*;
+use crate::buffer::Buffer;
+
+/// Iterator over Neighbor Discovery options.
+pub struct OptionIter<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> OptionIter<'a> {
+	pub(in crate::icmp::v6) fn new(buffer: &'a [u8]) -> Self {
+		OptionIter { buffer }
+	}
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+	type Item = Result<option::Option<&'a [u8]>>;
+
+	fn next(&mut self) -> ::std::option::Option<Self::Item> {
+		use crate::size::Size;
+
+		if self.buffer.is_empty() {
+			return None;
+		}
+
+		match option::Option::new(self.buffer) {
+			Ok(option) => {
+				self.buffer = &self.buffer[option.size() ..];
+				Some(Ok(option))
+			}
+
+			Err(error) => {
+				self.buffer = &[];
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+/// Append an option to the current layer, padding it to 8 octets.
+pub(in crate::icmp::v6) fn option<B: Buffer>(buffer: &mut B, number: option::Number, payload: &[u8]) -> Result<()> {
+	let length = (payload.len() + 2).div_ceil(8);
+
+	if length > 0xff {
+		Err(Error::InvalidValue)?
+	}
+
+	let offset = buffer.length();
+	buffer.more(length * 8)?;
+
+	let data = &mut buffer.data_mut()[offset ..];
+	data[0] = number.into();
+	data[1] = length as u8;
+	data[2 .. 2 + payload.len()].copy_from_slice(payload);
+
+	Ok(())
+}
@@ -0,0 +1,153 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv6Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::ndp;
+use crate::icmp::v6::ndp::option::Number;
+use crate::icmp::v6::ndp::neighbor_advertisement::Packet;
+
+/// Neighbor Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(24)?;
+		buffer.data_mut()[0] = Kind::NeighborAdvertisement.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Whether the sender is a router.
+	pub fn router(mut self, value: bool) -> Result<Self> {
+		let old = self.buffer.data()[4];
+		self.buffer.data_mut()[4] = if value { old | 0b1000_0000 } else { old & !0b1000_0000 };
+
+		Ok(self)
+	}
+
+	/// Whether the advertisement was sent in response to a solicitation.
+	pub fn solicited(mut self, value: bool) -> Result<Self> {
+		let old = self.buffer.data()[4];
+		self.buffer.data_mut()[4] = if value { old | 0b0100_0000 } else { old & !0b0100_0000 };
+
+		Ok(self)
+	}
+
+	/// Whether the advertisement should override an existing cache entry.
+	pub fn overrides(mut self, value: bool) -> Result<Self> {
+		let old = self.buffer.data()[4];
+		self.buffer.data_mut()[4] = if value { old | 0b0010_0000 } else { old & !0b0010_0000 };
+
+		Ok(self)
+	}
+
+	/// Target address.
+	pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+		self.buffer.data_mut()[8 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Target Link-Layer Address option.
+	pub fn target_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		ndp::option(&mut self.buffer, Number::TargetLinkLayerAddress, &value.octets())?;
+
+		Ok(self)
+	}
+
+	/// Raw option, the payload is padded to a multiple of 8 octets.
+	pub fn option(mut self, number: Number, payload: &[u8]) -> Result<Self> {
+		ndp::option(&mut self.buffer, number, payload)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.neighbor_advertisement().unwrap()
+				.solicited(true).unwrap()
+				.overrides(true).unwrap()
+				.target("fe80::1".parse().unwrap()).unwrap()
+				.target_link_layer_address("00:0c:29:3c:1c:1a".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let na = icmp.neighbor_advertisement().unwrap();
+		assert!(!na.is_router());
+		assert!(na.is_solicited());
+		assert!(na.is_override());
+		assert_eq!(na.target(), "fe80::1".parse::<std::net::Ipv6Addr>().unwrap());
+
+		let option = na.options().next().unwrap().unwrap();
+		assert_eq!(option.number(), icmp::v6::ndp::option::Number::TargetLinkLayerAddress);
+	}
+}
@@ -0,0 +1,233 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::net::Ipv6Addr;
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::Checked;
+use crate::icmp::v6::ndp::OptionIter;
+
+/// Neighbor Advertisement packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  24,
+		max:  24,
+		size: 24,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 24,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmp::v6::ndp::neighbor_advertisement::Packet")
+			.field("router", &self.is_router())
+			.field("solicited", &self.is_solicited())
+			.field("override", &self.is_override())
+			.field("target", &self.target())
+			.field("options", &self.options().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a Neighbor Advertisement packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a Neighbor Advertisement packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		match Kind::from(packet.buffer.as_ref()[0]) {
+			Kind::NeighborAdvertisement =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(24)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(24)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Whether the sender is a router.
+	pub fn is_router(&self) -> bool {
+		self.buffer.as_ref()[4] & 0b1000_0000 != 0
+	}
+
+	/// Whether the advertisement was sent in response to a solicitation.
+	pub fn is_solicited(&self) -> bool {
+		self.buffer.as_ref()[4] & 0b0100_0000 != 0
+	}
+
+	/// Whether the advertisement should override an existing cache entry.
+	pub fn is_override(&self) -> bool {
+		self.buffer.as_ref()[4] & 0b0010_0000 != 0
+	}
+
+	/// Target address being advertised.
+	pub fn target(&self) -> Ipv6Addr {
+		let mut octets = [0u8; 16];
+		octets.copy_from_slice(&self.buffer.as_ref()[8 .. 24]);
+
+		octets.into()
+	}
+
+	/// Neighbor Discovery options.
+	pub fn options(&self) -> OptionIter<'_> {
+		OptionIter::new(&self.buffer.as_ref()[24 ..])
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Whether the sender is a router.
+	pub fn set_router(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[4];
+		self.buffer.as_mut()[4] = if value { old | 0b1000_0000 } else { old & !0b1000_0000 };
+
+		Ok(self)
+	}
+
+	/// Whether the advertisement was sent in response to a solicitation.
+	pub fn set_solicited(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[4];
+		self.buffer.as_mut()[4] = if value { old | 0b0100_0000 } else { old & !0b0100_0000 };
+
+		Ok(self)
+	}
+
+	/// Whether the advertisement should override an existing cache entry.
+	pub fn set_override(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[4];
+		self.buffer.as_mut()[4] = if value { old | 0b0010_0000 } else { old & !0b0010_0000 };
+
+		Ok(self)
+	}
+
+	/// Target address being advertised.
+	pub fn set_target(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+		self.buffer.as_mut()[8 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Create a checksumed setter.
+	pub fn checked<'a, 'b, BI: AsRef<[u8]> + 'b>(&'a mut self, ip: &'b ip::v6::Packet<BI>) -> Checked<'a, 'b, Self, BI> {
+		Checked {
+			packet: self,
+			ip,
+		}
+	}
+}
+
+impl<'a, 'b, B, BI> Checked<'a, 'b, Packet<B>, BI>
+	where B:  AsRef<[u8]> + AsMut<[u8]> + 'a,
+	      BI: AsRef<[u8]> + 'b
+{
+	/// Whether the sender is a router.
+	pub fn set_router(&mut self, value: bool) -> Result<&mut Self> {
+		self.packet.set_router(value)?;
+		Ok(self)
+	}
+
+	/// Whether the advertisement was sent in response to a solicitation.
+	pub fn set_solicited(&mut self, value: bool) -> Result<&mut Self> {
+		self.packet.set_solicited(value)?;
+		Ok(self)
+	}
+
+	/// Whether the advertisement should override an existing cache entry.
+	pub fn set_override(&mut self, value: bool) -> Result<&mut Self> {
+		self.packet.set_override(value)?;
+		Ok(self)
+	}
+
+	/// Target address being advertised.
+	pub fn set_target(&mut self, value: Ipv6Addr) -> Result<&mut Self> {
+		self.packet.set_target(value)?;
+		Ok(self)
+	}
+}
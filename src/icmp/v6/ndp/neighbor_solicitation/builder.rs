@@ -0,0 +1,122 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::net::Ipv6Addr;
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::ndp;
+use crate::icmp::v6::ndp::option::Number;
+use crate::icmp::v6::ndp::neighbor_solicitation::Packet;
+
+/// Neighbor Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(24)?;
+		buffer.data_mut()[0] = Kind::NeighborSolicitation.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Target address.
+	pub fn target(mut self, value: Ipv6Addr) -> Result<Self> {
+		self.buffer.data_mut()[8 .. 24].copy_from_slice(&value.octets());
+
+		Ok(self)
+	}
+
+	/// Source Link-Layer Address option.
+	pub fn source_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		ndp::option(&mut self.buffer, Number::SourceLinkLayerAddress, &value.octets())?;
+
+		Ok(self)
+	}
+
+	/// Raw option, the payload is padded to a multiple of 8 octets.
+	pub fn option(mut self, number: Number, payload: &[u8]) -> Result<Self> {
+		ndp::option(&mut self.buffer, number, payload)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.neighbor_solicitation().unwrap()
+				.target("fe80::20c:29ff:fe3c:1c1b".parse().unwrap()).unwrap()
+				.source_link_layer_address("00:0c:29:3c:1c:1a".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let ns = icmp.neighbor_solicitation().unwrap();
+		assert_eq!(ns.target(), "fe80::20c:29ff:fe3c:1c1b".parse::<std::net::Ipv6Addr>().unwrap());
+		assert_eq!(ns.options().count(), 1);
+	}
+}
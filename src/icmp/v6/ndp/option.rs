@@ -0,0 +1,287 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::net::Ipv6Addr;
+use byteorder::{ReadBytesExt, BigEndian};
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+
+/// Neighbor Discovery option parser.
+pub struct Option<B> {
+	buffer: B,
+}
+
+sized!(Option,
+	header {
+		min:  2,
+		max:  2,
+		size: 2,
+	}
+
+	payload {
+		min:  6,
+		max:  255 * 8 - 2,
+		size: p => p.length() as usize * 8 - 2,
+	});
+
+/// Neighbor Discovery option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+	/// Link-layer address of the sender.
+	SourceLinkLayerAddress,
+
+	/// Link-layer address of the target.
+	TargetLinkLayerAddress,
+
+	/// On-link and autoconfiguration prefix.
+	PrefixInformation,
+
+	/// Packet being redirected.
+	RedirectedHeader,
+
+	/// Link MTU.
+	Mtu,
+
+	/// Nonce for duplicate address detection.
+	Nonce,
+
+	/// More-specific route.
+	RouteInformation,
+
+	/// Recursive DNS servers.
+	RecursiveDnsServer,
+
+	/// DNS search list.
+	DnsSearchList,
+
+	/// Unknown option.
+	Unknown(u8),
+}
+
+/// Prefix Information option value.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct PrefixInformation {
+	/// Number of leading bits in the prefix that are valid.
+	pub prefix_length: u8,
+
+	/// Whether the prefix can be used for on-link determination.
+	pub on_link: bool,
+
+	/// Whether the prefix can be used for stateless address configuration.
+	pub autonomous: bool,
+
+	/// Seconds the prefix is valid for on-link determination.
+	pub valid_lifetime: u32,
+
+	/// Seconds addresses generated from the prefix remain preferred.
+	pub preferred_lifetime: u32,
+
+	/// The prefix.
+	pub prefix: Ipv6Addr,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmp::v6::ndp::Option")
+			.field("number", &self.number())
+			.field("length", &self.length())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Parse a Neighbor Discovery option, checking the buffer contents are
+	/// correct.
+	pub fn new(buffer: B) -> Result<Option<B>> {
+		use crate::size::header::Min;
+
+		let option = Option { buffer };
+
+		if option.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if option.length() == 0 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if option.buffer.as_ref().len() < option.length() as usize * 8 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(option)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Option<&[u8]>> {
+		Option::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref()[.. self.length() as usize * 8].split_at(2)
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Option number.
+	pub fn number(&self) -> Number {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Option length in units of 8 octets.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Link-layer address for Source/Target Link-Layer Address options.
+	pub fn link_layer_address(&self) -> Result<HwAddr> {
+		match self.number() {
+			Number::SourceLinkLayerAddress |
+			Number::TargetLinkLayerAddress =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload()[.. 6].into())
+	}
+
+	/// Value of a Prefix Information option.
+	pub fn prefix_information(&self) -> Result<PrefixInformation> {
+		if self.number() != Number::PrefixInformation || self.length() != 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let payload = self.payload();
+		let mut prefix = [0u8; 16];
+		prefix.copy_from_slice(&payload[14 .. 30]);
+
+		Ok(PrefixInformation {
+			prefix_length:      payload[0],
+			on_link:            payload[1] & 0b1000_0000 != 0,
+			autonomous:         payload[1] & 0b0100_0000 != 0,
+			valid_lifetime:     (&payload[2 ..]).read_u32::<BigEndian>()?,
+			preferred_lifetime: (&payload[6 ..]).read_u32::<BigEndian>()?,
+			prefix:             prefix.into(),
+		})
+	}
+
+	/// Value of an MTU option.
+	pub fn mtu(&self) -> Result<u32> {
+		if self.number() != Number::Mtu || self.length() != 1 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok((&self.payload()[2 ..]).read_u32::<BigEndian>()?)
+	}
+
+	/// Packet being redirected for Redirected Header options.
+	pub fn redirected_header(&self) -> Result<&[u8]> {
+		if self.number() != Number::RedirectedHeader {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(&self.payload()[6 ..])
+	}
+}
+
+impl PrefixInformation {
+	pub(in crate::icmp::v6) fn to_bytes(self) -> [u8; 30] {
+		let mut bytes = [0u8; 30];
+
+		bytes[0] = self.prefix_length;
+		bytes[1] = if self.on_link { 0b1000_0000 } else { 0 } |
+			if self.autonomous { 0b0100_0000 } else { 0 };
+		bytes[2 .. 6].copy_from_slice(&self.valid_lifetime.to_be_bytes());
+		bytes[6 .. 10].copy_from_slice(&self.preferred_lifetime.to_be_bytes());
+		bytes[14 .. 30].copy_from_slice(&self.prefix.octets());
+
+		bytes
+	}
+}
+
+impl From<u8> for Number {
+	fn from(value: u8) -> Self {
+		use self::Number::*;
+
+		match value {
+			1  => SourceLinkLayerAddress,
+			2  => TargetLinkLayerAddress,
+			3  => PrefixInformation,
+			4  => RedirectedHeader,
+			5  => Mtu,
+			14 => Nonce,
+			24 => RouteInformation,
+			25 => RecursiveDnsServer,
+			31 => DnsSearchList,
+			n  => Unknown(n),
+		}
+	}
+}
+
+impl From<Number> for u8 {
+	fn from(value: Number) -> u8 {
+		use self::Number::*;
+
+		match value {
+			SourceLinkLayerAddress => 1,
+			TargetLinkLayerAddress => 2,
+			PrefixInformation      => 3,
+			RedirectedHeader       => 4,
+			Mtu                    => 5,
+			Nonce                  => 14,
+			RouteInformation       => 24,
+			RecursiveDnsServer     => 25,
+			DnsSearchList          => 31,
+			Unknown(n)             => n,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::icmp::v6::ndp;
+
+	#[test]
+	fn link_layer_address() {
+		let raw    = [0x01u8, 0x01, 0x00, 0x0c, 0x29, 0x3c, 0x1c, 0x1a];
+		let option = ndp::Option::new(&raw[..]).unwrap();
+
+		assert_eq!(option.number(), ndp::option::Number::SourceLinkLayerAddress);
+		assert_eq!(option.link_layer_address().unwrap(), "00:0c:29:3c:1c:1a".parse().unwrap());
+		assert!(option.mtu().is_err());
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(ndp::Option::new(&[0x01u8, 0x00, 0, 0, 0, 0, 0, 0][..]).is_err());
+		assert!(ndp::Option::new(&[0x01u8, 0x02, 0, 0, 0, 0, 0, 0][..]).is_err());
+	}
+}
@@ -0,0 +1,203 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::ndp;
+use crate::icmp::v6::ndp::PrefixInformation;
+use crate::icmp::v6::ndp::option::Number;
+use crate::icmp::v6::ndp::router_advertisement::Packet;
+
+/// Router Advertisement packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(16)?;
+		buffer.data_mut()[0] = Kind::RouterAdvertisement.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Default hop limit for outgoing packets.
+	pub fn hop_limit(mut self, value: u8) -> Result<Self> {
+		self.buffer.data_mut()[4] = value;
+
+		Ok(self)
+	}
+
+	/// Whether addresses are available via DHCPv6.
+	pub fn managed(mut self, value: bool) -> Result<Self> {
+		let old = self.buffer.data()[5];
+		self.buffer.data_mut()[5] = if value { old | 0b1000_0000 } else { old & !0b1000_0000 };
+
+		Ok(self)
+	}
+
+	/// Whether other configuration is available via DHCPv6.
+	pub fn other(mut self, value: bool) -> Result<Self> {
+		let old = self.buffer.data()[5];
+		self.buffer.data_mut()[5] = if value { old | 0b0100_0000 } else { old & !0b0100_0000 };
+
+		Ok(self)
+	}
+
+	/// Lifetime of the default router in seconds.
+	pub fn lifetime(mut self, value: u16) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Time in milliseconds a node assumes a neighbor is reachable.
+	pub fn reachable_time(mut self, value: u32) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[8 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Time in milliseconds between retransmitted Neighbor Solicitations.
+	pub fn retransmit_timer(mut self, value: u32) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[12 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Source Link-Layer Address option.
+	pub fn source_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		ndp::option(&mut self.buffer, Number::SourceLinkLayerAddress, &value.octets())?;
+
+		Ok(self)
+	}
+
+	/// MTU option.
+	pub fn mtu(mut self, value: u32) -> Result<Self> {
+		let mut payload = [0u8; 6];
+		payload[2 ..].copy_from_slice(&value.to_be_bytes());
+
+		ndp::option(&mut self.buffer, Number::Mtu, &payload)?;
+
+		Ok(self)
+	}
+
+	/// Prefix Information option.
+	pub fn prefix_information(mut self, value: PrefixInformation) -> Result<Self> {
+		ndp::option(&mut self.buffer, Number::PrefixInformation, &value.to_bytes())?;
+
+		Ok(self)
+	}
+
+	/// Raw option, the payload is padded to a multiple of 8 octets.
+	pub fn option(mut self, number: Number, payload: &[u8]) -> Result<Self> {
+		ndp::option(&mut self.buffer, number, payload)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let prefix = icmp::v6::ndp::PrefixInformation {
+			prefix_length:      64,
+			on_link:            true,
+			autonomous:         true,
+			valid_lifetime:     2592000,
+			preferred_lifetime: 604800,
+			prefix:             "2001:db8::".parse().unwrap(),
+		};
+
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.router_advertisement().unwrap()
+				.hop_limit(64).unwrap()
+				.other(true).unwrap()
+				.lifetime(1800).unwrap()
+				.source_link_layer_address("00:0c:29:3c:1c:1a".parse().unwrap()).unwrap()
+				.mtu(1500).unwrap()
+				.prefix_information(prefix).unwrap()
+				.build().unwrap();
+
+		assert_eq!(packet.len(), 40 + 16 + 8 + 8 + 32);
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert!(icmp.is_valid(&ip));
+
+		let ra = icmp.router_advertisement().unwrap();
+		assert_eq!(ra.hop_limit(), 64);
+		assert!(!ra.is_managed());
+		assert!(ra.is_other());
+		assert_eq!(ra.lifetime(), 1800);
+
+		let options = ra.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 3);
+		assert_eq!(options[1].mtu().unwrap(), 1500);
+		assert_eq!(options[2].prefix_information().unwrap(), prefix);
+	}
+}
@@ -0,0 +1,271 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::Checked;
+use crate::icmp::v6::ndp::OptionIter;
+
+/// Router Advertisement packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  16,
+		max:  16,
+		size: 16,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 16,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmp::v6::ndp::router_advertisement::Packet")
+			.field("hop_limit", &self.hop_limit())
+			.field("managed", &self.is_managed())
+			.field("other", &self.is_other())
+			.field("lifetime", &self.lifetime())
+			.field("reachable_time", &self.reachable_time())
+			.field("retransmit_timer", &self.retransmit_timer())
+			.field("options", &self.options().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create a Router Advertisement packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse a Router Advertisement packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		match Kind::from(packet.buffer.as_ref()[0]) {
+			Kind::RouterAdvertisement =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(16)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(16)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Default hop limit for outgoing packets.
+	pub fn hop_limit(&self) -> u8 {
+		self.buffer.as_ref()[4]
+	}
+
+	/// Whether addresses are available via DHCPv6.
+	pub fn is_managed(&self) -> bool {
+		self.buffer.as_ref()[5] & 0b1000_0000 != 0
+	}
+
+	/// Whether other configuration is available via DHCPv6.
+	pub fn is_other(&self) -> bool {
+		self.buffer.as_ref()[5] & 0b0100_0000 != 0
+	}
+
+	/// Lifetime of the default router in seconds.
+	pub fn lifetime(&self) -> u16 {
+		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Time in milliseconds a node assumes a neighbor is reachable.
+	pub fn reachable_time(&self) -> u32 {
+		(&self.buffer.as_ref()[8 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Time in milliseconds between retransmitted Neighbor Solicitations.
+	pub fn retransmit_timer(&self) -> u32 {
+		(&self.buffer.as_ref()[12 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Neighbor Discovery options.
+	pub fn options(&self) -> OptionIter<'_> {
+		OptionIter::new(&self.buffer.as_ref()[16 ..])
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Default hop limit for outgoing packets.
+	pub fn set_hop_limit(&mut self, value: u8) -> Result<&mut Self> {
+		self.buffer.as_mut()[4] = value;
+
+		Ok(self)
+	}
+
+	/// Whether addresses are available via DHCPv6.
+	pub fn set_managed(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[5];
+		self.buffer.as_mut()[5] = if value { old | 0b1000_0000 } else { old & !0b1000_0000 };
+
+		Ok(self)
+	}
+
+	/// Whether other configuration is available via DHCPv6.
+	pub fn set_other(&mut self, value: bool) -> Result<&mut Self> {
+		let old = self.buffer.as_ref()[5];
+		self.buffer.as_mut()[5] = if value { old | 0b0100_0000 } else { old & !0b0100_0000 };
+
+		Ok(self)
+	}
+
+	/// Lifetime of the default router in seconds.
+	pub fn set_lifetime(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[6 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Time in milliseconds a node assumes a neighbor is reachable.
+	pub fn set_reachable_time(&mut self, value: u32) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[8 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Time in milliseconds between retransmitted Neighbor Solicitations.
+	pub fn set_retransmit_timer(&mut self, value: u32) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[12 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Create a checksumed setter.
+	pub fn checked<'a, 'b, BI: AsRef<[u8]> + 'b>(&'a mut self, ip: &'b ip::v6::Packet<BI>) -> Checked<'a, 'b, Self, BI> {
+		Checked {
+			packet: self,
+			ip,
+		}
+	}
+}
+
+impl<'a, 'b, B, BI> Checked<'a, 'b, Packet<B>, BI>
+	where B:  AsRef<[u8]> + AsMut<[u8]> + 'a,
+	      BI: AsRef<[u8]> + 'b
+{
+	/// Default hop limit for outgoing packets.
+	pub fn set_hop_limit(&mut self, value: u8) -> Result<&mut Self> {
+		self.packet.set_hop_limit(value)?;
+		Ok(self)
+	}
+
+	/// Whether addresses are available via DHCPv6.
+	pub fn set_managed(&mut self, value: bool) -> Result<&mut Self> {
+		self.packet.set_managed(value)?;
+		Ok(self)
+	}
+
+	/// Whether other configuration is available via DHCPv6.
+	pub fn set_other(&mut self, value: bool) -> Result<&mut Self> {
+		self.packet.set_other(value)?;
+		Ok(self)
+	}
+
+	/// Lifetime of the default router in seconds.
+	pub fn set_lifetime(&mut self, value: u16) -> Result<&mut Self> {
+		self.packet.set_lifetime(value)?;
+		Ok(self)
+	}
+
+	/// Time in milliseconds a node assumes a neighbor is reachable.
+	pub fn set_reachable_time(&mut self, value: u32) -> Result<&mut Self> {
+		self.packet.set_reachable_time(value)?;
+		Ok(self)
+	}
+
+	/// Time in milliseconds between retransmitted Neighbor Solicitations.
+	pub fn set_retransmit_timer(&mut self, value: u32) -> Result<&mut Self> {
+		self.packet.set_retransmit_timer(value)?;
+		Ok(self)
+	}
+}
@@ -0,0 +1,118 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use hwaddr::HwAddr;
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::ndp;
+use crate::icmp::v6::ndp::option::Number;
+use crate::icmp::v6::ndp::router_solicitation::Packet;
+
+/// Router Solicitation packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip: (usize, usize),
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::RouterSolicitation.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Source Link-Layer Address option.
+	pub fn source_link_layer_address(mut self, value: HwAddr) -> Result<Self> {
+		ndp::option(&mut self.buffer, Number::SourceLinkLayerAddress, &value.octets())?;
+
+		Ok(self)
+	}
+
+	/// Raw option, the payload is padded to a multiple of 8 octets.
+	pub fn option(mut self, number: Number, payload: &[u8]) -> Result<Self> {
+		ndp::option(&mut self.buffer, number, payload)?;
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.router_solicitation().unwrap()
+				.source_link_layer_address("00:0c:29:3c:1c:1a".parse().unwrap()).unwrap()
+				.build().unwrap();
+
+		assert_eq!(packet.len(), 40 + 16);
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert_eq!(icmp.kind(), icmp::v6::Kind::RouterSolicitation);
+		assert!(icmp.is_valid(&ip));
+
+		let rs      = icmp.router_solicitation().unwrap();
+		let options = rs.options().collect::<Vec<_>>();
+		assert_eq!(options.len(), 1);
+		assert_eq!(options[0].as_ref().unwrap().link_layer_address().unwrap(),
+			"00:0c:29:3c:1c:1a".parse().unwrap());
+	}
+}
@@ -0,0 +1,244 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use std::io::Cursor;
+use byteorder::{ReadBytesExt, WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
+use crate::ip;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::checksum;
+
+/// ICMPv6 packet parser.
+pub struct Packet<B> {
+	buffer: B,
+}
+
+sized!(Packet,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  0,
+		size: p => p.buffer.as_ref().len() - 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Packet<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("icmp::v6::Packet")
+			.field("kind", &self.kind())
+			.field("code", &self.code())
+			.field("checksum", &self.checksum())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Create an ICMPv6 packet without checking the buffer.
+	pub fn unchecked(buffer: B) -> Packet<B> {
+		Packet { buffer }
+	}
+
+	/// Parse an ICMPv6 packet, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Packet<B>> {
+		use crate::size::header::Min;
+
+		let packet = Packet::unchecked(buffer);
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Convert the packet to its owned version.
+	///
+	/// # Notes
+	///
+	/// It would be nice if `ToOwned` could be implemented, but `Packet` already
+	/// implements `Clone` and the impl would conflict.
+	pub fn to_owned(&self) -> Packet<Vec<u8>> {
+		Packet::unchecked(self.buffer.as_ref().to_vec())
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
+	fn as_mut(&mut self) -> &mut [u8] {
+		use crate::size::Size;
+
+		let size = self.size();
+		&mut self.buffer.as_mut()[.. size]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Packet<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.as_ref())
+	}
+}
+
+impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> AsPacketMut<'a, Packet<&'a mut [u8]>> for B {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.as_mut())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Packet<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.buffer.as_ref().split_at(4)
+	}
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PM for Packet<B> {
+	fn split_mut(&mut self) -> (&mut [u8], &mut [u8]) {
+		self.buffer.as_mut().split_at_mut(4)
+	}
+}
+
+macro_rules! kind {
+	($(#[$attr:meta])* fn $name:ident[$mutable:ident]($($module:ident)::+)) => (
+		$(#[$attr])*
+		pub fn $name(&self) -> Result<crate::icmp::v6::$($module)::+::Packet<&B>> {
+			crate::icmp::v6::$($module)::+::Packet::new(&self.buffer)
+		}
+
+		$(#[$attr])*
+		pub fn $mutable(&mut self) -> Result<crate::icmp::v6::$($module)::+::Packet<&mut B>> {
+			crate::icmp::v6::$($module)::+::Packet::new(&mut self.buffer)
+		}
+	)
+}
+
+impl<B: AsRef<[u8]>> Packet<B> {
+	/// Packet type.
+	pub fn kind(&self) -> Kind {
+		Kind::from(self.buffer.as_ref()[0])
+	}
+
+	/// Packet code.
+	pub fn code(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Packet checksum.
+	pub fn checksum(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap()
+	}
+
+	/// Verify the packet is valid by calculating the checksum.
+	pub fn is_valid<BI: AsRef<[u8]>>(&self, ip: &ip::v6::Packet<BI>) -> bool {
+		checksum(ip, self.buffer.as_ref()) == self.checksum()
+	}
+
+	kind!(/// Parse an Echo Request/Reply packet.
+		fn echo[echo_mut](echo));
+
+	kind!(/// Parse a Destination Unreachable or Time Exceeded packet.
+		fn previous[previous_mut](previous));
+
+	kind!(/// Parse a Packet Too Big packet.
+		fn packet_too_big[packet_too_big_mut](packet_too_big));
+
+	kind!(/// Parse a Parameter Problem packet.
+		fn parameter_problem[parameter_problem_mut](parameter_problem));
+
+	kind!(/// Parse a Router Solicitation packet.
+		fn router_solicitation[router_solicitation_mut](ndp::router_solicitation));
+
+	kind!(/// Parse a Router Advertisement packet.
+		fn router_advertisement[router_advertisement_mut](ndp::router_advertisement));
+
+	kind!(/// Parse a Neighbor Solicitation packet.
+		fn neighbor_solicitation[neighbor_solicitation_mut](ndp::neighbor_solicitation));
+
+	kind!(/// Parse a Neighbor Advertisement packet.
+		fn neighbor_advertisement[neighbor_advertisement_mut](ndp::neighbor_advertisement));
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Set the checksum value.
+	pub fn set_checksum(&mut self, value: u16) -> Result<&mut Self> {
+		Cursor::new(&mut self.buffer.as_mut()[2 ..])
+			.write_u16::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Recalculate and set the checksum value.
+	pub fn update_checksum<BI: AsRef<[u8]>>(&mut self, ip: &ip::v6::Packet<BI>) -> Result<&mut Self> {
+		let checksum = checksum(ip, self.buffer.as_ref());
+		self.set_checksum(checksum)
+	}
+}
+
+/// Checked wrapper for ICMPv6 packets.
+///
+/// # Note
+///
+/// The checksum recalculation happens on `Drop`, so don't leak it.
+pub struct Checked<'a, 'b, P, BI>
+	where P:  AsRef<[u8]> + AsMut<[u8]> + 'a,
+	      BI: AsRef<[u8]> + 'b
+{
+	pub(in crate::icmp::v6) packet: &'a mut P,
+	pub(in crate::icmp::v6) ip:     &'b ip::v6::Packet<BI>,
+}
+
+impl<'a, 'b, P, BI> Drop for Checked<'a, 'b, P, BI>
+	where P:  AsRef<[u8]> + AsMut<[u8]> + 'a,
+	      BI: AsRef<[u8]> + 'b
+{
+	fn drop(&mut self) {
+		let checksum = checksum(self.ip, self.packet.as_ref());
+		Cursor::new(&mut self.packet.as_mut()[2 ..])
+			.write_u16::<BigEndian>(checksum).unwrap();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+
+	#[test]
+	fn values() {
+		let raw = hex::decode("6000000000203afffe80000000000000020c29fffe3c1c1aff0200000000000000000001ff3c1c1b87008e1e00000000fe80000000000000020c29fffe3c1c1b0101000c293c1c1a").unwrap();
+
+		let ip   = ip::v6::Packet::new(&raw[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(icmp.kind(), icmp::v6::Kind::NeighborSolicitation);
+		assert!(icmp.is_valid(&ip));
+		assert!(icmp.echo().is_err());
+		assert!(icmp.neighbor_solicitation().is_ok());
+	}
+}
@@ -0,0 +1,136 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::packet_too_big::Packet;
+
+/// Packet Too Big packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip:      (usize, usize),
+	payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::PacketTooBig.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+			payload: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Maximum Transmission Unit of the next-hop link.
+	pub fn mtu(mut self, value: u32) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[4 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Packet to cause the message, it's truncated to keep the message within
+	/// the minimum IPv6 MTU.
+	pub fn packet<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+
+		for byte in value.into_iter().take(builder::QUOTE) {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let quoted = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.payload(&[0u8; 1400][..]).unwrap()
+			.build().unwrap();
+
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.packet_too_big().unwrap()
+				.mtu(1280).unwrap()
+				.packet(&quoted).unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert_eq!(icmp.kind(), icmp::v6::Kind::PacketTooBig);
+		assert!(icmp.is_valid(&ip));
+
+		let too_big = icmp.packet_too_big().unwrap();
+		assert_eq!(too_big.mtu(), 1280);
+		assert_eq!(too_big.payload().len(), 1280 - 40 - 8);
+		assert_eq!(too_big.packet().unwrap().destination(), "2001:db8::2".parse::<std::net::Ipv6Addr>().unwrap());
+	}
+}
@@ -0,0 +1,137 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::code;
+use crate::icmp::v6::parameter_problem::Packet;
+
+/// Parameter Problem packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip:      (usize, usize),
+	payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+		buffer.data_mut()[0] = Kind::ParameterProblem.into();
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+			payload: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Packet code.
+	pub fn code(mut self, value: code::ParameterProblem) -> Result<Self> {
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Offset in the invoking packet where the error was detected.
+	pub fn pointer(mut self, value: u32) -> Result<Self> {
+		Cursor::new(&mut self.buffer.data_mut()[4 ..])
+			.write_u32::<BigEndian>(value)?;
+
+		Ok(self)
+	}
+
+	/// Packet to cause the message, it's truncated to keep the message within
+	/// the minimum IPv6 MTU.
+	pub fn packet<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+
+		for byte in value.into_iter().take(builder::QUOTE) {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.parameter_problem().unwrap()
+				.code(icmp::v6::code::ParameterProblem::UnrecognizedNextHeader).unwrap()
+				.pointer(6).unwrap()
+				.packet(&[0x60u8, 0, 0, 0, 0, 0, 0xfe, 0x40][..]).unwrap()
+				.build().unwrap();
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert_eq!(icmp.kind(), icmp::v6::Kind::ParameterProblem);
+		assert_eq!(icmp::v6::code::ParameterProblem::from(icmp.code()), icmp::v6::code::ParameterProblem::UnrecognizedNextHeader);
+		assert!(icmp.is_valid(&ip));
+
+		let problem = icmp.parameter_problem().unwrap();
+		assert_eq!(problem.pointer(), 6);
+		assert!(problem.packet().is_err());
+	}
+}
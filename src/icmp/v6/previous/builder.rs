@@ -0,0 +1,144 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use crate::error::*;
+use crate::buffer::{self, Buffer};
+use crate::builder::{Builder as Build, Finalization};
+use crate::packet::{AsPacket, AsPacketMut};
+use crate::icmp::v6::builder;
+use crate::icmp::v6::Kind;
+use crate::icmp::v6::code;
+use crate::icmp::v6::previous::Packet;
+
+/// Destination Unreachable and Time Exceeded packet builder.
+#[derive(Debug)]
+pub struct Builder<B: Buffer = buffer::Dynamic> {
+	buffer:    B,
+	finalizer: Finalization,
+
+	ip:      (usize, usize),
+	kind:    bool,
+	payload: bool,
+}
+
+impl<B: Buffer> Build<B> for Builder<B> {
+	fn with(mut buffer: B) -> Result<Self> {
+		let ip = (buffer.offset(), buffer.length());
+		buffer.next(8)?;
+
+		Ok(Builder {
+			buffer,
+			finalizer: Default::default(),
+
+			ip,
+			kind:    false,
+			payload: false,
+		})
+	}
+
+	fn finalizer(&mut self) -> &mut Finalization {
+		&mut self.finalizer
+	}
+
+	fn build(mut self) -> Result<B::Inner> {
+		if !self.kind {
+			Err(Error::InvalidPacket)?
+		}
+
+		builder::prepare(&mut self.finalizer, self.ip, &self.buffer);
+
+		let mut buffer = self.buffer.into_inner();
+		self.finalizer.finalize(buffer.as_mut())?;
+		Ok(buffer)
+	}
+}
+
+impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
+	fn as_packet(&self) -> Result<Packet<&[u8]>> {
+		Packet::new(self.buffer.data())
+	}
+}
+
+impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
+	fn as_packet_mut(&mut self) -> Result<Packet<&mut [u8]>> {
+		Packet::new(self.buffer.data_mut())
+	}
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Make it a Destination Unreachable with the given code.
+	pub fn destination_unreachable(mut self, value: code::DestinationUnreachable) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::DestinationUnreachable.into();
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Make it a Time Exceeded with the given code.
+	pub fn time_exceeded(mut self, value: code::TimeExceeded) -> Result<Self> {
+		self.kind = true;
+		self.buffer.data_mut()[0] = Kind::TimeExceeded.into();
+		self.buffer.data_mut()[1] = value.into();
+
+		Ok(self)
+	}
+
+	/// Packet to cause the message, it's truncated to keep the message within
+	/// the minimum IPv6 MTU.
+	pub fn packet<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		self.payload = true;
+
+		for byte in value.into_iter().take(builder::QUOTE) {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		Ok(self)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::icmp;
+	use crate::icmp::v6::builder::test::buffer;
+
+	#[test]
+	fn simple() {
+		let quoted = [0x60u8; 2000];
+		let packet = icmp::v6::Builder::with(buffer()).unwrap()
+			.previous().unwrap()
+				.destination_unreachable(icmp::v6::code::DestinationUnreachable::PortUnreachable).unwrap()
+				.packet(&quoted[..]).unwrap()
+				.build().unwrap();
+
+		assert_eq!(packet.len(), 1280);
+
+		let ip   = ip::v6::Packet::no_payload(&packet[..]).unwrap();
+		let icmp = icmp::v6::Packet::new(&packet[40 ..]).unwrap();
+		assert_eq!(icmp.kind(), icmp::v6::Kind::DestinationUnreachable);
+		assert_eq!(icmp.code(), 4);
+		assert!(icmp.is_valid(&ip));
+
+		let previous = icmp.previous().unwrap();
+		assert!(previous.is_destination_unreachable());
+		assert!(previous.packet().is_ok());
+	}
+}
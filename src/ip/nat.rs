@@ -0,0 +1,365 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::net::IpAddr;
+
+use crate::error::*;
+use crate::checksum;
+use crate::ip::{Packet, Protocol};
+
+/// Which endpoint of a packet is rewritten.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+enum Side {
+	Source,
+	Destination,
+}
+
+impl Side {
+	fn opposite(self) -> Side {
+		match self {
+			Side::Source      => Side::Destination,
+			Side::Destination => Side::Source,
+		}
+	}
+}
+
+/// Old and new contents of changed fields, all at even offsets of the
+/// checksummed data.
+type Change = (Vec<u8>, Vec<u8>);
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
+	/// Rewrite the source address and, if given, the source port or ICMP echo
+	/// identifier.
+	///
+	/// # Note
+	///
+	/// The IPv4 header checksum and the transport checksum are updated along,
+	/// and in ICMP error messages the quoted packet is rewritten as well.
+	pub fn rewrite_source(&mut self, address: IpAddr, port: Option<u16>) -> Result<&mut Self> {
+		rewrite(self.as_mut(), Side::Source, address, port, false, &mut Vec::new())?;
+		Ok(self)
+	}
+
+	/// Rewrite the destination address and, if given, the destination port or
+	/// ICMP echo identifier.
+	///
+	/// # Note
+	///
+	/// The IPv4 header checksum and the transport checksum are updated along,
+	/// and in ICMP error messages the quoted packet is rewritten as well.
+	pub fn rewrite_destination(&mut self, address: IpAddr, port: Option<u16>) -> Result<&mut Self> {
+		rewrite(self.as_mut(), Side::Destination, address, port, false, &mut Vec::new())?;
+		Ok(self)
+	}
+}
+
+/// Write `value` at `offset`, recording the change.
+fn write(buffer: &mut [u8], offset: usize, value: &[u8], changes: &mut Vec<Change>) -> Change {
+	let change = (buffer[offset .. offset + value.len()].to_vec(), value.to_vec());
+	buffer[offset .. offset + value.len()].copy_from_slice(value);
+	changes.push(change.clone());
+
+	change
+}
+
+/// Adjust the checksum at `offset` for the given changes to the data it covers.
+///
+/// A zero UDP checksum means there's no checksum, and a computed zero is sent
+/// as all ones instead.
+fn adjust(buffer: &mut [u8], offset: usize, udp: bool, covered: &[Change], changes: &mut Vec<Change>) {
+	let current = u16::from_be_bytes([buffer[offset], buffer[offset + 1]]);
+
+	if udp && current == 0 {
+		return;
+	}
+
+	let mut value = covered.iter().fold(current, |sum, (old, new)| checksum::update(sum, old, new));
+	if udp && value == 0 {
+		value = 0xffff;
+	}
+
+	write(buffer, offset, &value.to_be_bytes(), changes);
+}
+
+/// Rewrite an IP packet, recording every change made to the buffer.
+fn rewrite(buffer: &mut [u8], side: Side, address: IpAddr, port: Option<u16>, quoted: bool, changes: &mut Vec<Change>) -> Result<()> {
+	let (offset, upper, new) = match (buffer.first().map(|b| b >> 4), address) {
+		(Some(4), IpAddr::V4(address)) if buffer.len() >= 20 => {
+			let header = (buffer[0] & 0x0f) as usize * 4;
+			let first  = u16::from_be_bytes([buffer[6], buffer[7]]) & 0x1fff == 0;
+			let upper  = if first { Some((Protocol::from(buffer[9]), header)) } else { None };
+
+			(if side == Side::Source { 12 } else { 16 }, upper, address.octets().to_vec())
+		}
+
+		(Some(6), IpAddr::V6(address)) if buffer.len() >= 40 => {
+			let packet = crate::ip::v6::Packet::unchecked(&buffer[..]);
+			// Only the first fragment carries the upper-layer header.
+			let upper  = packet.protocol().ok().zip(packet.payload_offset().ok())
+				.filter(|&(protocol, _)| protocol != Protocol::Ipv6Frag);
+
+			(if side == Side::Source { 8 } else { 24 }, upper, address.octets().to_vec())
+		}
+
+		(Some(4), IpAddr::V6(_)) | (Some(6), IpAddr::V4(_)) =>
+			Err(Error::InvalidValue)?,
+
+		(Some(4), _) | (Some(6), _) =>
+			Err(Error::SmallBuffer)?,
+
+		_ =>
+			Err(Error::InvalidPacket)?,
+	};
+
+	let changed = write(buffer, offset, &new, changes);
+
+	if buffer[0] >> 4 == 4 {
+		adjust(buffer, 10, false, std::slice::from_ref(&changed), changes);
+	}
+
+	let (protocol, header) = match upper {
+		Some((protocol, header)) if header <= buffer.len() =>
+			(protocol, header),
+
+		_ =>
+			return Ok(()),
+	};
+
+	let payload = &mut buffer[header ..];
+	let field   = if side == Side::Source { 0 } else { 2 };
+
+	match protocol {
+		Protocol::Tcp | Protocol::Udp => {
+			let udp         = protocol == Protocol::Udp;
+			let checksum    = if udp { 6 } else { 16 };
+			let mut covered = vec![changed];
+
+			if let Some(port) = port.filter(|_| payload.len() >= 4) {
+				covered.push(write(payload, field, &port.to_be_bytes(), changes));
+			}
+
+			// Quoted packets may be truncated before the checksum.
+			if payload.len() >= checksum + 2 {
+				adjust(payload, checksum, udp, &covered, changes);
+			}
+		}
+
+		Protocol::Icmp | Protocol::Ipv6Icmp if payload.len() >= 8 => {
+			let v6          = protocol == Protocol::Ipv6Icmp;
+			let mut covered = if v6 { vec![changed] } else { Vec::new() };
+
+			match (v6, payload[0]) {
+				// Echo Request/Reply.
+				(false, 0) | (false, 8) | (true, 128) | (true, 129) => {
+					if let Some(port) = port {
+						covered.push(write(payload, 4, &port.to_be_bytes(), changes));
+					}
+				}
+
+				// Errors quoting the packet that caused them, which went the
+				// other way.
+				(false, 3) | (false, 4) | (false, 5) | (false, 11) | (false, 12) |
+				(true, 1) | (true, 2) | (true, 3) | (true, 4) if !quoted => {
+					let mut inner = Vec::new();
+
+					if rewrite(&mut payload[8 ..], side.opposite(), address, port, true, &mut inner).is_ok() {
+						covered.extend(inner.iter().cloned());
+						changes.extend(inner);
+					}
+				}
+
+				_ =>
+					(),
+			}
+
+			adjust(payload, 2, false, &covered, changes);
+		}
+
+		_ =>
+			(),
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::IpAddr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::icmp;
+	use crate::tcp;
+	use crate::udp;
+
+	fn address(value: &str) -> IpAddr {
+		value.parse().unwrap()
+	}
+
+	#[test]
+	fn tcp_v4() {
+		let mut raw = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(40000).unwrap()
+				.destination(80).unwrap()
+				.payload(b"GET / HTTP/1.1\r\n\r\n").unwrap()
+				.build().unwrap();
+
+		ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_source(address("203.0.113.1"), Some(1024)).unwrap();
+
+		let ip  = ip::v4::Packet::new(&raw[..]).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(IpAddr::from(ip.source()), address("203.0.113.1"));
+		assert_eq!(tcp.source(), 1024);
+		assert_eq!(tcp.destination(), 80);
+		assert!(ip.is_valid());
+		assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn udp_v6() {
+		let mut raw = ip::v6::Builder::default()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(53).unwrap()
+				.destination(40000).unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_destination(address("2001:db8::42"), Some(5353)).unwrap();
+
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(IpAddr::from(ip.destination()), address("2001:db8::42"));
+		assert_eq!(udp.destination(), 5353);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn fragment_v6() {
+		let mut raw = hex::decode("6000000000102c4020010db800000000000000000000000120010db80000000000000000000000021100032012345678003500350008dead").unwrap();
+
+		ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_source(address("2001:db8::42"), Some(1)).unwrap()
+			.rewrite_destination(address("2001:db8::43"), Some(2)).unwrap();
+
+		let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+		assert_eq!(IpAddr::from(ip.source()), address("2001:db8::42"));
+		assert_eq!(IpAddr::from(ip.destination()), address("2001:db8::43"));
+		assert_eq!(&raw[48 ..], &[0x00, 0x35, 0x00, 0x35, 0x00, 0x08, 0xde, 0xad]);
+	}
+
+	#[test]
+	fn udp_without_checksum() {
+		let mut raw = ip::v4::Builder::default()
+			.udp().unwrap()
+				.source(53).unwrap()
+				.destination(40000).unwrap()
+				.build().unwrap();
+
+		raw[26] = 0;
+		raw[27] = 0;
+
+		ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_destination(address("10.0.0.1"), Some(1)).unwrap();
+
+		let ip = ip::v4::Packet::new(&raw[..]).unwrap();
+		assert!(ip.is_valid());
+		assert_eq!(udp::Packet::new(ip.payload()).unwrap().checksum(), 0);
+	}
+
+	#[test]
+	fn mismatched() {
+		let mut raw = ip::v4::Builder::default()
+			.udp().unwrap()
+				.build().unwrap();
+
+		assert!(ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_source(address("2001:db8::1"), None).is_err());
+	}
+
+	#[test]
+	fn echo() {
+		let mut raw = ip::v4::Builder::default()
+			.source("192.168.0.79".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.echo().unwrap().request().unwrap()
+					.identifier(42).unwrap()
+					.sequence(1).unwrap()
+					.payload(b"test").unwrap()
+					.build().unwrap();
+
+		ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_source(address("203.0.113.1"), Some(1337)).unwrap();
+
+		let ip   = ip::v4::Packet::new(&raw[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+
+		assert!(ip.is_valid());
+		assert!(icmp.is_valid());
+		assert_eq!(icmp.echo().unwrap().identifier(), 1337);
+	}
+
+	#[test]
+	fn error() {
+		// Datagram sent by the translated host, as seen outside.
+		let quoted = ip::v4::Builder::default()
+			.source("203.0.113.1".parse().unwrap()).unwrap()
+			.destination("66.102.1.108".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(1024).unwrap()
+				.destination(53).unwrap()
+				.payload(b"query").unwrap()
+				.build().unwrap();
+
+		let mut icmp = vec![3, 3, 0, 0, 0, 0, 0, 0];
+		icmp.extend_from_slice(&quoted[.. 28]);
+		let checksum = icmp::checksum(&icmp);
+		icmp[2 .. 4].copy_from_slice(&checksum.to_be_bytes());
+
+		let mut raw = ip::v4::Builder::default()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("203.0.113.1".parse().unwrap()).unwrap()
+			.protocol(ip::Protocol::Icmp).unwrap()
+			.payload(&icmp).unwrap()
+			.build().unwrap();
+
+		ip::Packet::new(&mut raw[..]).unwrap()
+			.rewrite_destination(address("192.168.0.79"), Some(40000)).unwrap();
+
+		let ip   = ip::v4::Packet::new(&raw[..]).unwrap();
+		let icmp = icmp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(IpAddr::from(ip.destination()), address("192.168.0.79"));
+		assert!(ip.is_valid());
+		assert!(icmp.is_valid());
+
+		let inner = ip::v4::Packet::no_payload(&icmp.payload()[4 ..]).unwrap();
+		assert_eq!(IpAddr::from(inner.source()), address("192.168.0.79"));
+		assert!(inner.is_valid());
+
+		let mut original = quoted.clone();
+		ip::Packet::new(&mut original[..]).unwrap()
+			.rewrite_source(address("192.168.0.79"), Some(40000)).unwrap();
+		assert_eq!(&icmp.payload()[4 ..], &original[.. 28]);
+	}
+}
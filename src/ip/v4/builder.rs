@@ -24,6 +24,7 @@ use crate::ip::Protocol;
 use crate::ip::v4::Packet;
 use crate::ip::v4::Flags;
 use crate::ip::v4::checksum;
+use crate::ip::v4::option::{Number, TimeStampFlag};
 
 /// IPv4 packet builder.
 #[derive(Debug)]
@@ -31,7 +32,7 @@ pub struct Builder<B: Buffer = buffer::Dynamic> {
 	buffer:    B,
 	finalizer: Finalization,
 
-	options: bool,
+	options: usize,
 	payload: bool,
 }
 
@@ -52,7 +53,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
 			buffer:    buffer,
 			finalizer: Default::default(),
 
-			options: false,
+			options: 0,
 			payload: false,
 		})
 	}
@@ -62,7 +63,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
 	}
 
 	fn build(mut self) -> Result<B::Inner> {
-		self.prepare();
+		self.prepare()?;
 
 		let mut buffer = self.buffer.into_inner();
 		self.finalizer.finalize(buffer.as_mut())?;
@@ -97,7 +98,7 @@ macro_rules! protocol {
 			}
 
 			self = self.protocol(Protocol::$protocol)?;
-			self.prepare();
+			self.prepare()?;
 
 			let mut builder = crate::$module::Builder::with(self.buffer)?;
 			builder.finalizer().extend(self.finalizer);
@@ -168,6 +169,7 @@ impl<B: Buffer> Builder<B> {
 			Err(Error::AlreadyDefined)?
 		}
 
+		self.pad()?;
 		self.payload = true;
 
 		for byte in value {
@@ -178,17 +180,131 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
-	fn prepare(&mut self) {
+	/// No Operation option, useful to align the following option.
+	pub fn nop(self) -> Result<Self> {
+		self.option(&[Number::NoOperation.into()])
+	}
+
+	/// Record Route option with room for the given number of addresses.
+	pub fn record_route(self, count: usize) -> Result<Self> {
+		self.route(Number::RecordRoute.into(), &vec![Ipv4Addr::UNSPECIFIED; count])
+	}
+
+	/// Loose Source and Record Route option through the given addresses.
+	pub fn loose_source_route(self, route: &[Ipv4Addr]) -> Result<Self> {
+		self.route(0x80 | u8::from(Number::LooseSourceRoute), route)
+	}
+
+	/// Strict Source and Record Route option through the given addresses.
+	pub fn strict_source_route(self, route: &[Ipv4Addr]) -> Result<Self> {
+		self.route(0x80 | u8::from(Number::StrictSourceRoute), route)
+	}
+
+	/// Timestamp option with room for the given number of entries.
+	pub fn timestamp(self, flag: TimeStampFlag, count: usize) -> Result<Self> {
+		let size = match flag {
+			TimeStampFlag::TimestampOnly =>
+				4,
+
+			TimeStampFlag::AddressAndTimestamp =>
+				8,
+
+			_ =>
+				Err(Error::InvalidValue)?
+		};
+
+		let mut option = vec![(2 << 5) | u8::from(Number::TimeStamp), (4 + count * size) as u8, 5, flag.into()];
+		option.resize(4 + count * size, 0);
+
+		self.option(&option)
+	}
+
+	/// Timestamp option recorded only by the given hosts.
+	pub fn prespecified_timestamp(self, hosts: &[Ipv4Addr]) -> Result<Self> {
+		let mut option = vec![(2 << 5) | u8::from(Number::TimeStamp), (4 + hosts.len() * 8) as u8, 5,
+			TimeStampFlag::Prespecified.into()];
+
+		for host in hosts {
+			option.extend_from_slice(&host.octets());
+			option.extend_from_slice(&[0, 0, 0, 0]);
+		}
+
+		self.option(&option)
+	}
+
+	/// Router Alert option.
+	pub fn router_alert(self, value: u16) -> Result<Self> {
+		let value = value.to_be_bytes();
+		self.option(&[0x80 | u8::from(Number::RouterAlert), 4, value[0], value[1]])
+	}
+
+	/// Option with the given type octet and payload.
+	pub fn unknown(self, kind: u8, payload: &[u8]) -> Result<Self> {
+		let mut option = vec![kind, (payload.len() + 2) as u8];
+		option.extend_from_slice(payload);
+
+		self.option(&option)
+	}
+
+	fn route(self, kind: u8, route: &[Ipv4Addr]) -> Result<Self> {
+		let mut option = vec![kind, (3 + route.len() * 4) as u8, 4];
+		for address in route {
+			option.extend_from_slice(&address.octets());
+		}
+
+		self.option(&option)
+	}
+
+	fn option(mut self, value: &[u8]) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		if self.options + value.len() > 40 {
+			Err(Error::InvalidValue)?
+		}
+
+		for byte in value {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = *byte;
+		}
+
+		self.options += value.len();
+		Ok(self)
+	}
+
+	/// Pad the options to a 32-bit boundary with End of Options List and update
+	/// the header length.
+	fn pad(&mut self) -> Result<()> {
+		use crate::size::header::Min;
+
+		while self.options & 0b11 != 0 {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = Number::End.into();
+			self.options += 1;
+		}
+
+		self.buffer.data_mut()[0] = (4 << 4) | ((Packet::<()>::min() + self.options) / 4) as u8;
+
+		Ok(())
+	}
+
+	fn prepare(&mut self) -> Result<()> {
+		self.pad()?;
+
 		let offset = self.buffer.offset();
-		let length = self.buffer.length();
+		let header = Packet::unchecked(self.buffer.data()).header() as usize;
 
 		self.finalizer.add(move |out| {
 			// Set the version to 4 and the header length.
-			let header = length / 4;
 			out[offset] = (4 << 4) | header as u8;
 
 			// Calculate and write the total length of the packet.
-			let length = length + (out.len() - (offset + length));
+			let length = out.len() - offset;
+			if length > u16::MAX as usize {
+				Err(Error::InvalidPacket)?
+			}
+
 			Cursor::new(&mut out[offset + 2 ..])
 				.write_u16::<BigEndian>(length as u16)?;
 
@@ -199,6 +315,8 @@ impl<B: Buffer> Builder<B> {
 
 			Ok(())
 		});
+
+		Ok(())
 	}
 
 	protocol!(/// Build an ICMP packet.
@@ -209,12 +327,16 @@ impl<B: Buffer> Builder<B> {
 
 	protocol!(/// Build a UDP packet.
 		fn udp(Udp));
+
+	protocol!(/// Build a GRE packet.
+		fn gre(Gre));
 }
 
 #[cfg(test)]
 mod test {
 	use std::net::Ipv4Addr;
 	use crate::builder::Builder;
+	use crate::packet::Packet;
 	use crate::ip;
 	use crate::tcp;
 
@@ -267,4 +389,67 @@ mod test {
 		assert_eq!(packet.destination(), "192.168.0.79".parse::<Ipv4Addr>().unwrap());
 		assert!(packet.is_valid());
 	}
+
+	#[test]
+	fn options() {
+		let packet = ip::v4::Builder::default()
+			.ttl(64).unwrap()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.router_alert(0).unwrap()
+			.record_route(2).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(9001).unwrap()
+				.flags(tcp::flag::SYN).unwrap()
+				.build().unwrap();
+
+		let packet = ip::v4::Packet::new(packet).unwrap();
+		assert_eq!(packet.header(), 9);
+		assert_eq!(packet.length(), 56);
+		assert!(packet.is_valid());
+
+		let options = packet.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 2);
+		assert_eq!(options[0].router_alert().unwrap(), 0);
+		assert_eq!(options[1].pointer().unwrap(), 4);
+		assert_eq!(options[1].route().unwrap().count(), 2);
+
+		let tcp = tcp::Packet::new(packet.payload()).unwrap();
+		assert_eq!(tcp.destination(), 9001);
+		assert!(tcp.is_valid(&ip::Packet::from(&packet)));
+	}
+
+	#[test]
+	fn options_payload() {
+		let route = ["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+		let packet = ip::v4::Builder::default()
+			.loose_source_route(&route).unwrap()
+			.timestamp(ip::v4::option::TimeStampFlag::TimestampOnly, 3).unwrap()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(b"lol").unwrap()
+			.build().unwrap();
+
+		let packet = ip::v4::Packet::new(packet).unwrap();
+		assert_eq!(packet.header(), 12);
+		assert_eq!(packet.payload(), b"lol");
+		assert!(packet.is_valid());
+
+		let options = packet.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert!(options[0].is_copied());
+		assert_eq!(options[0].route().unwrap().collect::<Vec<_>>(), route);
+		assert_eq!(options[1].timestamps().unwrap().count(), 3);
+	}
+
+	#[test]
+	fn options_limit() {
+		assert!(ip::v4::Builder::default()
+			.record_route(9).unwrap()
+			.nop().unwrap()
+			.router_alert(0).is_err());
+
+		assert!(ip::v4::Builder::default()
+			.payload(b"lol").unwrap()
+			.nop().is_err());
+	}
 }
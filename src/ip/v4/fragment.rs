@@ -0,0 +1,265 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Cursor;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::v4::{Packet, Flags};
+
+/// Iterator over the fragments of an IPv4 packet.
+#[derive(Debug)]
+pub struct Fragments<'a> {
+	first:   &'a [u8],
+	copied:  Vec<u8>,
+	payload: &'a [u8],
+	mtu:     usize,
+
+	position: usize,
+	offset:   u16,
+	more:     bool,
+}
+
+impl<'a> Fragments<'a> {
+	pub(in crate::ip::v4) fn new<B: AsRef<[u8]>>(packet: &'a Packet<B>, mtu: usize) -> Result<Self> {
+		let first   = packet.split().0;
+		let payload = packet.payload();
+
+		if first.len() + payload.len() > mtu && packet.flags().contains(Flags::DONT_FRAGMENT) {
+			Err(Error::InvalidPacket)?
+		}
+
+		// Later fragments only carry the options with the copied flag set.
+		let mut copied = first[.. 20].to_vec();
+		for option in packet.options() {
+			let option = option?;
+
+			if option.is_copied() {
+				copied.extend_from_slice(option.as_ref());
+			}
+		}
+
+		copied.resize((copied.len() + 3) & !3, 0);
+		copied[0] = (4 << 4) | (copied.len() / 4) as u8;
+
+		// Both kinds of fragments have to carry at least 8 octets of payload.
+		if first.len().max(copied.len()) + 8 > mtu && first.len() + payload.len() > mtu {
+			Err(Error::InvalidValue)?
+		}
+
+		Ok(Fragments {
+			first,
+			copied,
+			payload,
+			mtu,
+
+			position: 0,
+			offset:   packet.offset(),
+			more:     packet.flags().contains(Flags::MORE_FRAGMENTS),
+		})
+	}
+}
+
+impl<'a> Iterator for Fragments<'a> {
+	type Item = Packet<Vec<u8>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let header = if self.position == 0 {
+			self.first
+		}
+		else if self.position < self.payload.len() {
+			&self.copied[..]
+		}
+		else {
+			return None;
+		};
+
+		let remaining = self.payload.len() - self.position;
+		let (length, last) = if header.len() + remaining <= self.mtu {
+			(remaining, true)
+		}
+		else {
+			((self.mtu - header.len()) & !7, false)
+		};
+
+		let mut buffer = Vec::with_capacity(header.len() + length);
+		buffer.extend_from_slice(header);
+		buffer.extend_from_slice(&self.payload[self.position .. self.position + length]);
+
+		let total = buffer.len() as u16;
+		Cursor::new(&mut buffer[2 ..]).write_u16::<BigEndian>(total).unwrap();
+
+		let mut flags = Packet::unchecked(&buffer[..]).flags() - Flags::MORE_FRAGMENTS;
+		if !last || self.more {
+			flags |= Flags::MORE_FRAGMENTS;
+		}
+
+		let offset = self.offset + (self.position / 8) as u16;
+		self.position += length;
+
+		// The payload is empty, mark the iterator as done.
+		if length == 0 {
+			self.position = usize::MAX;
+		}
+
+		let mut packet = Packet::unchecked(buffer);
+		packet.set_flags(flags).ok()?;
+		packet.set_offset(offset).ok()?;
+		packet.update_checksum().ok()?;
+
+		Some(packet)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+
+	fn packet(options: &[u8], length: usize, flags: ip::v4::Flags) -> Vec<u8> {
+		let payload = (0 .. length).map(|i| i as u8).collect::<Vec<_>>();
+		let mut packet = ip::v4::Builder::default()
+			.id(0x2d87).unwrap()
+			.ttl(64).unwrap()
+			.flags(flags).unwrap()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(&payload).unwrap()
+			.build().unwrap();
+
+		if !options.is_empty() {
+			let mut with = packet[.. 20].to_vec();
+			with.extend_from_slice(options);
+			with.extend_from_slice(&packet[20 ..]);
+			with[0] = (4 << 4) | ((20 + options.len()) / 4) as u8;
+
+			let length = (with.len() as u16).to_be_bytes();
+			with[2 .. 4].copy_from_slice(&length);
+			ip::v4::Packet::unchecked(&mut with[..]).update_checksum().unwrap();
+
+			packet = with;
+		}
+
+		packet
+	}
+
+	#[test]
+	fn simple() {
+		let raw    = packet(&[], 3000, ip::v4::Flags::empty());
+		let ip     = ip::v4::Packet::new(&raw[..]).unwrap();
+		let frags  = ip.fragments(1500).unwrap().collect::<Vec<_>>();
+
+		assert_eq!(frags.len(), 3);
+		assert_eq!(frags[0].length(), 1500);
+		assert_eq!(frags[1].length(), 1500);
+		assert_eq!(frags[2].length(), 20 + 3000 - 2 * 1480);
+
+		let mut data = Vec::new();
+		for (i, frag) in frags.iter().enumerate() {
+			assert!(frag.is_valid());
+			assert_eq!(frag.id(), 0x2d87);
+			assert_eq!(frag.offset() as usize * 8, data.len());
+			assert_eq!(frag.flags().contains(ip::v4::Flags::MORE_FRAGMENTS), i != 2);
+
+			data.extend_from_slice(frag.payload());
+		}
+
+		assert_eq!(data, ip.payload());
+	}
+
+	#[test]
+	fn alignment() {
+		let raw   = packet(&[], 100, ip::v4::Flags::empty());
+		let ip    = ip::v4::Packet::new(&raw[..]).unwrap();
+		let frags = ip.fragments(50).unwrap().collect::<Vec<_>>();
+
+		assert_eq!(frags.len(), 4);
+		assert!(frags.iter().take(3).all(|f| f.payload().len() == 24));
+		assert_eq!(frags[3].payload().len(), 28);
+		assert_eq!(frags[3].offset(), 9);
+	}
+
+	#[test]
+	fn fits() {
+		let raw   = packet(&[], 100, ip::v4::Flags::DONT_FRAGMENT);
+		let ip    = ip::v4::Packet::new(&raw[..]).unwrap();
+		let frags = ip.fragments(1500).unwrap().collect::<Vec<_>>();
+
+		assert_eq!(frags.len(), 1);
+		assert_eq!(frags[0].as_ref(), &raw[..]);
+	}
+
+	#[test]
+	fn dont_fragment() {
+		let raw = packet(&[], 3000, ip::v4::Flags::DONT_FRAGMENT);
+		let ip  = ip::v4::Packet::new(&raw[..]).unwrap();
+
+		assert!(ip.fragments(1500).is_err());
+	}
+
+	#[test]
+	fn small_mtu() {
+		let raw = packet(&[], 3000, ip::v4::Flags::empty());
+		let ip  = ip::v4::Packet::new(&raw[..]).unwrap();
+
+		assert!(ip.fragments(27).is_err());
+		assert!(ip.fragments(28).is_ok());
+	}
+
+	#[test]
+	fn options() {
+		// Record Route is not copied, Loose Source Route is.
+		let options = [
+			0x07, 0x07, 0x04, 0x00, 0x00, 0x00, 0x00,
+			0x83, 0x07, 0x04, 0x0a, 0x00, 0x00, 0x01,
+			0x00, 0x00,
+		];
+
+		let raw   = packet(&options, 200, ip::v4::Flags::empty());
+		let ip    = ip::v4::Packet::new(&raw[..]).unwrap();
+		let frags = ip.fragments(100).unwrap().collect::<Vec<_>>();
+
+		assert_eq!(frags[0].header(), 9);
+		assert_eq!(frags[0].options().count(), 2);
+
+		for frag in &frags[1 ..] {
+			assert!(frag.is_valid());
+			assert_eq!(frag.header(), 7);
+
+			let options = frag.options().collect::<Result<Vec<_>, _>>().unwrap();
+			assert_eq!(options.len(), 1);
+			assert_eq!(options[0].number(), ip::v4::option::Number::LooseSourceRoute);
+		}
+
+		let data = frags.iter().flat_map(|f| f.payload().to_vec()).collect::<Vec<_>>();
+		assert_eq!(data, ip.payload());
+	}
+
+	#[test]
+	fn refragment() {
+		let mut raw = packet(&[], 1000, ip::v4::Flags::MORE_FRAGMENTS);
+		ip::v4::Packet::unchecked(&mut raw[..]).checked().set_offset(100).unwrap();
+
+		let ip    = ip::v4::Packet::new(&raw[..]).unwrap();
+		let frags = ip.fragments(524).unwrap().collect::<Vec<_>>();
+
+		assert_eq!(frags.len(), 2);
+		assert_eq!(frags[0].offset(), 100);
+		assert_eq!(frags[1].offset(), 100 + 504 / 8);
+		assert!(frags.iter().all(|f| f.flags().contains(ip::v4::Flags::MORE_FRAGMENTS)));
+	}
+}
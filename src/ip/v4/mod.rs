@@ -26,6 +26,13 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+mod fragment;
+pub use self::fragment::Fragments;
+
+/// IPv4 fragment reassembly.
+pub mod reassembler;
+pub use self::reassembler::Reassembler;
+
 /// Calculate the checksum for an IPv4 packet.
 pub fn checksum(buffer: &[u8]) -> u16 {
 	use std::io::Cursor;
@@ -13,6 +13,8 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::fmt;
+use std::net::Ipv4Addr;
+use byteorder::{ReadBytesExt, BigEndian};
 
 use crate::error::*;
 use crate::packet::{Packet as P, PacketMut as PM, AsPacket, AsPacketMut};
@@ -133,6 +135,75 @@ pub enum Number {
 	Unknown(u8),
 }
 
+/// Contents of a Timestamp option.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TimeStampFlag {
+	/// Only timestamps are recorded.
+	TimestampOnly,
+
+	/// Each timestamp is preceded by the address of the recording host.
+	AddressAndTimestamp,
+
+	/// Only the prespecified hosts record a timestamp.
+	Prespecified,
+
+	/// Unknown flag.
+	Unknown(u8),
+}
+
+/// Iterator over the addresses of a route option.
+#[derive(Debug)]
+pub struct Addresses<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for Addresses<'a> {
+	type Item = Ipv4Addr;
+
+	fn next(&mut self) -> std::option::Option<Self::Item> {
+		if self.buffer.len() < 4 {
+			return None;
+		}
+
+		let address = Ipv4Addr::new(self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]);
+		self.buffer = &self.buffer[4 ..];
+
+		Some(address)
+	}
+}
+
+/// Iterator over the entries of a Timestamp option.
+#[derive(Debug)]
+pub struct TimeStamps<'a> {
+	buffer:  &'a [u8],
+	address: bool,
+}
+
+impl<'a> Iterator for TimeStamps<'a> {
+	type Item = (std::option::Option<Ipv4Addr>, u32);
+
+	fn next(&mut self) -> std::option::Option<Self::Item> {
+		let address = if self.address {
+			if self.buffer.len() < 8 {
+				return None;
+			}
+
+			let address = Ipv4Addr::new(self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]);
+			self.buffer = &self.buffer[4 ..];
+
+			Some(address)
+		}
+		else {
+			None
+		};
+
+		let timestamp = (&self.buffer[..]).read_u32::<BigEndian>().ok()?;
+		self.buffer = &self.buffer[4 ..];
+
+		Some((address, timestamp))
+	}
+}
+
 impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		f.debug_struct("ip::v4::Option")
@@ -158,6 +229,10 @@ impl<B: AsRef<[u8]>> Option<B> {
 			Err(Error::SmallBuffer)?
 		}
 
+		if option.length() < 2 && !matches!(option.number(), Number::End | Number::NoOperation) {
+			Err(Error::InvalidPacket)?
+		}
+
 		if option.buffer.as_ref().len() < option.length() as usize {
 			Err(Error::SmallBuffer)?
 		}
@@ -251,6 +326,102 @@ impl<B: AsRef<[u8]>> Option<B> {
 				self.buffer.as_ref()[1]
 		}
 	}
+
+	/// Pointer of a route or Timestamp option, as an octet offset from the
+	/// start of the option.
+	pub fn pointer(&self) -> Result<u8> {
+		match self.number() {
+			Number::RecordRoute |
+			Number::LooseSourceRoute |
+			Number::StrictSourceRoute |
+			Number::TimeStamp =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		if self.length() < 3 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload()[0])
+	}
+
+	/// All the address slots of a Record Route, Loose Source Route or Strict
+	/// Source Route option, the pointer tells which have been used.
+	pub fn route(&self) -> Result<Addresses<'_>> {
+		match self.number() {
+			Number::RecordRoute |
+			Number::LooseSourceRoute |
+			Number::StrictSourceRoute =>
+				(),
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		}
+
+		if self.length() < 3 || (self.length() - 3) & 0b11 != 0 || self.pointer()? < 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(Addresses {
+			buffer: &self.payload()[1 ..],
+		})
+	}
+
+	/// Number of hosts that couldn't record a timestamp for lack of space.
+	pub fn overflow(&self) -> Result<u8> {
+		if self.number() != Number::TimeStamp || self.length() < 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload()[1] >> 4)
+	}
+
+	/// Contents of a Timestamp option.
+	pub fn timestamp_flag(&self) -> Result<TimeStampFlag> {
+		if self.number() != Number::TimeStamp || self.length() < 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok((self.payload()[1] & 0b1111).into())
+	}
+
+	/// All the entry slots of a Timestamp option, the pointer tells which have
+	/// been used.
+	pub fn timestamps(&self) -> Result<TimeStamps<'_>> {
+		let address = match self.timestamp_flag()? {
+			TimeStampFlag::TimestampOnly =>
+				false,
+
+			TimeStampFlag::AddressAndTimestamp |
+			TimeStampFlag::Prespecified =>
+				true,
+
+			TimeStampFlag::Unknown(_) =>
+				Err(Error::InvalidPacket)?
+		};
+
+		let size = if address { 8 } else { 4 };
+		if (self.length() as usize - 4) & (size - 1) != 0 || self.pointer()? < 5 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(TimeStamps {
+			buffer:  &self.payload()[2 ..],
+			address,
+		})
+	}
+
+	/// Value of a Router Alert option.
+	pub fn router_alert(&self) -> Result<u16> {
+		if self.number() != Number::RouterAlert || self.length() != 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok((&self.payload()[..]).read_u16::<BigEndian>()?)
+	}
 }
 
 impl From<u8> for Class {
@@ -311,11 +482,11 @@ impl From<u8> for Number {
 	}
 }
 
-impl Into<u8> for Number {
-	fn into(self) -> u8 {
+impl From<Number> for u8 {
+	fn from(value: Number) -> Self {
 		use self::Number::*;
 
-		match self {
+		match value {
 			End                        => 0,
 			NoOperation                => 1,
 			Security                   => 2,
@@ -344,3 +515,94 @@ impl Into<u8> for Number {
 		}
 	}
 }
+
+impl From<u8> for TimeStampFlag {
+	fn from(value: u8) -> Self {
+		use self::TimeStampFlag::*;
+
+		match value {
+			0 => TimestampOnly,
+			1 => AddressAndTimestamp,
+			3 => Prespecified,
+			n => Unknown(n),
+		}
+	}
+}
+
+impl From<TimeStampFlag> for u8 {
+	fn from(value: TimeStampFlag) -> Self {
+		use self::TimeStampFlag::*;
+
+		match value {
+			TimestampOnly       => 0,
+			AddressAndTimestamp => 1,
+			Prespecified        => 3,
+			Unknown(n)          => n,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv4Addr;
+	use crate::ip::v4::option::{Option, Number, TimeStampFlag};
+
+	#[test]
+	fn record_route() {
+		let option = Option::new(&[0x07, 0x0b, 0x08, 0x0a, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00][..]).unwrap();
+
+		assert_eq!(option.number(), Number::RecordRoute);
+		assert!(!option.is_copied());
+		assert_eq!(option.pointer().unwrap(), 8);
+		assert_eq!(option.route().unwrap().collect::<Vec<_>>(),
+			vec![Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(0, 0, 0, 0)]);
+		assert!(option.router_alert().is_err());
+	}
+
+	#[test]
+	fn loose_source_route() {
+		let option = Option::new(&[0x83, 0x07, 0x04, 0xc0, 0xa8, 0x00, 0x01][..]).unwrap();
+
+		assert_eq!(option.number(), Number::LooseSourceRoute);
+		assert!(option.is_copied());
+		assert_eq!(option.route().unwrap().collect::<Vec<_>>(), vec![Ipv4Addr::new(192, 168, 0, 1)]);
+	}
+
+	#[test]
+	fn timestamp() {
+		let option = Option::new(&[
+			0x44, 0x14, 0x0d, 0x21,
+			0x0a, 0x00, 0x00, 0x01, 0x00, 0x00, 0x03, 0xe8,
+			0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		][..]).unwrap();
+
+		assert_eq!(option.number(), Number::TimeStamp);
+		assert_eq!(option.pointer().unwrap(), 13);
+		assert_eq!(option.overflow().unwrap(), 2);
+		assert_eq!(option.timestamp_flag().unwrap(), TimeStampFlag::AddressAndTimestamp);
+		assert_eq!(option.timestamps().unwrap().collect::<Vec<_>>(), vec![
+			(Some(Ipv4Addr::new(10, 0, 0, 1)), 1000),
+			(Some(Ipv4Addr::new(0, 0, 0, 0)), 0),
+		]);
+
+		let option = Option::new(&[0x44, 0x0c, 0x09, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00][..]).unwrap();
+		assert_eq!(option.timestamps().unwrap().collect::<Vec<_>>(), vec![(None, 7), (None, 0)]);
+	}
+
+	#[test]
+	fn router_alert() {
+		let option = Option::new(&[0x94, 0x04, 0x00, 0x00][..]).unwrap();
+
+		assert!(option.is_copied());
+		assert_eq!(option.router_alert().unwrap(), 0);
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(Option::new(&[0x07, 0x00][..]).is_err());
+		assert!(Option::new(&[0x07, 0x06, 0x04, 0, 0, 0][..]).unwrap().route().is_err());
+		assert!(Option::new(&[0x07, 0x07, 0x02, 0, 0, 0, 0][..]).unwrap().route().is_err());
+		assert!(Option::new(&[0x44, 0x0a, 0x05, 0x01, 0, 0, 0, 0, 0, 0][..]).unwrap().timestamps().is_err());
+		assert!(Option::new(&[0x94, 0x03, 0x00][..]).unwrap().router_alert().is_err());
+	}
+}
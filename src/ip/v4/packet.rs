@@ -23,6 +23,7 @@ use crate::ip::Protocol;
 use crate::ip::v4::Flags;
 use crate::ip::v4::option;
 use crate::ip::v4::checksum;
+use crate::ip::v4::Fragments;
 
 /// IPv4 packet parser.
 #[derive(Copy, Clone)]
@@ -119,7 +120,9 @@ impl<B: AsRef<[u8]>> AsRef<[u8]> for Packet<B> {
 	fn as_ref(&self) -> &[u8] {
 		use crate::size::Size;
 
-		&self.buffer.as_ref()[.. self.size()]
+		// Packets parsed without their payload may be truncated.
+		let buffer = self.buffer.as_ref();
+		&buffer[.. self.size().min(buffer.len())]
 	}
 }
 
@@ -127,8 +130,10 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> AsMut<[u8]> for Packet<B> {
 	fn as_mut(&mut self) -> &mut [u8] {
 		use crate::size::Size;
 
-		let size = self.size();
-		&mut self.buffer.as_mut()[.. size]
+		let size   = self.size();
+		let buffer = self.buffer.as_mut();
+		let length = buffer.len();
+		&mut buffer[.. size.min(length)]
 	}
 }
 
@@ -215,11 +220,11 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 	/// Flags of the packet.
 	pub fn flags(&self) -> Flags {
-		Flags::from_bits((&self.buffer.as_ref()[6 ..])
-			.read_u16::<BigEndian>().unwrap() >> 13).unwrap()
+		Flags::from_bits_truncate((&self.buffer.as_ref()[6 ..])
+			.read_u16::<BigEndian>().unwrap() >> 13)
 	}
 
-	/// Offset of the packet.
+	/// Offset of the packet, in units of 8 octets.
 	pub fn offset(&self) -> u16 {
 		(&self.buffer.as_ref()[6 ..]).read_u16::<BigEndian>().unwrap() & 0x1fff
 	}
@@ -268,6 +273,16 @@ impl<B: AsRef<[u8]>> Packet<B> {
 			buffer: &self.buffer.as_ref()[20 .. (self.header() as usize * 4)],
 		}
 	}
+
+	/// Split the packet in fragments fitting the given MTU.
+	///
+	/// # Note
+	///
+	/// A packet already fitting the MTU is returned as the only fragment, a
+	/// packet that doesn't and has the Don't Fragment flag set is an error.
+	pub fn fragments(&self, mtu: usize) -> Result<Fragments<'_>> {
+		Fragments::new(self, mtu)
+	}
 }
 
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
@@ -305,16 +320,24 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 
 	/// Packet flags.
 	pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
+		let offset = self.offset();
+
 		Cursor::new(&mut self.header_mut()[6 ..])
-			.write_u16::<BigEndian>(value.bits())?;
+			.write_u16::<BigEndian>(value.bits() << 13 | offset)?;
 
 		Ok(self)
 	}
 
-	/// Packet fragment offset.
+	/// Packet fragment offset, in units of 8 octets.
 	pub fn set_offset(&mut self, value: u16) -> Result<&mut Self> {
+		if value > 0x1fff {
+			Err(Error::InvalidValue)?
+		}
+
+		let flags = self.flags();
+
 		Cursor::new(&mut self.header_mut()[6 ..])
-			.write_u16::<BigEndian>(value)?;
+			.write_u16::<BigEndian>(flags.bits() << 13 | value)?;
 
 		Ok(self)
 	}
@@ -350,7 +373,8 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 	/// Create a checksumed setter.
 	pub fn checked(&mut self) -> Checked<'_, B> {
 		Checked {
-			packet: self
+			packet:      self,
+			incremental: false,
 		}
 	}
 
@@ -367,76 +391,98 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 		let checksum = checksum(P::header(self));
 		self.set_checksum(checksum)
 	}
+
+	/// Adjust the checksum value for header data changed from `old` to `new`,
+	/// both starting at the same even offset.
+	pub fn adjust_checksum(&mut self, old: &[u8], new: &[u8]) -> Result<&mut Self> {
+		let checksum = crate::checksum::update(self.checksum(), old, new);
+		self.set_checksum(checksum)
+	}
 }
 
 /// Checked wrapper for IPv4 packets.
 ///
 /// # Note
 ///
-/// The checksum recalculation happens on `Drop`, so don't leak it.
+/// The checksum recalculation happens on `Drop`, so don't leak it, unless
+/// the incremental mode is enabled.
 pub struct Checked<'a, B: AsRef<[u8]> + AsMut<[u8]>> {
-	packet: &'a mut Packet<B>
+	packet:      &'a mut Packet<B>,
+	incremental: bool,
 }
 
 impl<'a, B: AsRef<[u8]> + AsMut<[u8]> + 'a> Checked<'a, B> {
+	/// Update the checksum from the old and new value of every changed field,
+	/// as described in RFC 1624, instead of recalculating it on `Drop`.
+	pub fn incremental(&mut self) -> &mut Self {
+		self.incremental = true;
+		self
+	}
+
+	fn change<F>(&mut self, offset: usize, length: usize, f: F) -> Result<&mut Self>
+		where F: FnOnce(&mut Packet<B>) -> Result<&mut Packet<B>>
+	{
+		if self.incremental {
+			crate::checksum::change(self.packet, offset, length, f, Packet::adjust_checksum)?;
+		}
+		else {
+			f(self.packet)?;
+		}
+
+		Ok(self)
+	}
+
 	/// Differentiated Services Code Point.
 	pub fn set_dscp(&mut self, value: u8) -> Result<&mut Self> {
-		self.packet.set_dscp(value)?;
-		Ok(self)
+		self.change(1, 1, |p| p.set_dscp(value))
 	}
 
 	/// Explicit Congestion Notification.
 	pub fn set_ecn(&mut self, value: u8) -> Result<&mut Self> {
-		self.packet.set_ecn(value)?;
-		Ok(self)
+		self.change(1, 1, |p| p.set_ecn(value))
 	}
 
 	/// Packet ID.
 	pub fn set_id(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_id(value)?;
-		Ok(self)
+		self.change(4, 2, |p| p.set_id(value))
 	}
 
 	/// Packet flags.
 	pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
-		self.packet.set_flags(value)?;
-		Ok(self)
+		self.change(6, 2, |p| p.set_flags(value))
 	}
 
 	/// Packet fragment offset.
 	pub fn set_offset(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_offset(value)?;
-		Ok(self)
+		self.change(6, 2, |p| p.set_offset(value))
 	}
 
 	/// Time to Live.
 	pub fn set_ttl(&mut self, value: u8) -> Result<&mut Self> {
-		self.packet.set_ttl(value)?;
-		Ok(self)
+		self.change(8, 1, |p| p.set_ttl(value))
 	}
 
 	/// Source address.
 	pub fn set_source(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
-		self.packet.set_source(value)?;
-		Ok(self)
+		self.change(12, 4, |p| p.set_source(value))
 	}
 
 	/// Destination address.
 	pub fn set_destination(&mut self, value: Ipv4Addr) -> Result<&mut Self> {
-		self.packet.set_destination(value)?;
-		Ok(self)
+		self.change(16, 4, |p| p.set_destination(value))
 	}
 
 	/// Inner protocol.
 	pub fn set_protocol(&mut self, value: Protocol) -> Result<&mut Self> {
-		self.packet.set_protocol(value)?;
-		Ok(self)
+		self.change(9, 1, |p| p.set_protocol(value))
 	}
 }
 
 impl<'a, B: AsRef<[u8]> + AsMut<[u8]>> Drop for Checked<'a, B> {
 	fn drop(&mut self) {
-		self.packet.update_checksum().unwrap();
+		if !self.incremental {
+			self.packet.update_checksum().unwrap();
+		}
 	}
 }
 
@@ -465,8 +511,10 @@ impl<'a> Iterator for OptionIter<'a> {
 				Some(Ok(option))
 			}
 
-			Err(error) =>
+			Err(error) => {
+				self.buffer = &[];
 				Some(Err(error))
+			}
 		}
 	}
 }
@@ -535,4 +583,22 @@ mod test {
 		ip.checked().set_id(0x4242).unwrap();
 		assert!(ip.is_valid());
 	}
+
+	#[test]
+	fn mutable_incremental() {
+		let mut raw = [0x45u8, 0x00, 0x00, 0x34, 0x2d, 0x87, 0x00, 0x00, 0x2c, 0x06, 0x5c, 0x74, 0x42, 0x66, 0x01, 0x6c, 0xc0, 0xa8, 0x00, 0x4f];
+		let mut ip  = ip::v4::Packet::no_payload(&mut raw[..]).unwrap();
+
+		ip.checked().incremental()
+			.set_ttl(0x2b).unwrap()
+			.set_dscp(10).unwrap()
+			.set_id(0x4242).unwrap()
+			.set_flags(ip::v4::Flags::DONT_FRAGMENT).unwrap()
+			.set_source("10.0.0.1".parse().unwrap()).unwrap()
+			.set_destination("10.0.0.2".parse().unwrap()).unwrap();
+
+		assert_eq!(ip.ttl(), 0x2b);
+		assert_eq!(ip.source(), "10.0.0.1".parse::<Ipv4Addr>().unwrap());
+		assert!(ip.is_valid());
+	}
 }
@@ -0,0 +1,551 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+use byteorder::{WriteBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::Protocol;
+use crate::ip::v4::{Packet, Flags};
+
+/// Policy applied when fragments overlap.
+#[derive(Eq, PartialEq, Copy, Clone, Default, Debug)]
+pub enum Policy {
+	/// Keep the data that was received first.
+	#[default]
+	First,
+
+	/// Overwrite with the data that was received last.
+	Last,
+
+	/// Discard the whole datagram.
+	Drop,
+}
+
+/// Key identifying the fragments of a datagram.
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub struct Key {
+	/// Source address.
+	pub source: Ipv4Addr,
+
+	/// Destination address.
+	pub destination: Ipv4Addr,
+
+	/// Inner protocol.
+	pub protocol: Protocol,
+
+	/// Packet ID.
+	pub id: u16,
+}
+
+impl Key {
+	/// Extract the key of the given packet.
+	pub fn new<B: AsRef<[u8]>>(packet: &Packet<B>) -> Self {
+		Key {
+			source:      packet.source(),
+			destination: packet.destination(),
+			protocol:    packet.protocol(),
+			id:          packet.id(),
+		}
+	}
+}
+
+/// A datagram being reassembled.
+#[derive(Debug)]
+struct Datagram {
+	header:  Option<Vec<u8>>,
+	data:    Vec<u8>,
+	ranges:  Vec<(usize, usize)>,
+	total:   Option<usize>,
+	started: Duration,
+}
+
+impl Datagram {
+	fn new(started: Duration) -> Self {
+		Datagram {
+			header:  None,
+			data:    Vec::new(),
+			ranges:  Vec::new(),
+			total:   None,
+			started,
+		}
+	}
+
+	/// Memory used by the datagram.
+	fn size(&self) -> usize {
+		self.header.as_ref().map_or(0, Vec::len) + self.data.len()
+	}
+
+	/// Whether any received data overlaps the given range.
+	fn overlaps(&self, start: usize, end: usize) -> bool {
+		self.ranges.iter().any(|&(s, e)| start < e && s < end)
+	}
+
+	/// Parts of the given range that haven't been received yet.
+	fn gaps(&self, mut start: usize, end: usize) -> Vec<(usize, usize)> {
+		let mut gaps = Vec::new();
+
+		for &(s, e) in &self.ranges {
+			if s >= end {
+				break;
+			}
+
+			if e <= start {
+				continue;
+			}
+
+			if s > start {
+				gaps.push((start, s));
+			}
+
+			start = e;
+		}
+
+		if start < end {
+			gaps.push((start, end));
+		}
+
+		gaps
+	}
+
+	/// Mark the given range as received.
+	fn mark(&mut self, start: usize, end: usize) {
+		self.ranges.push((start, end));
+		self.ranges.sort_unstable();
+
+		let mut merged: Vec<(usize, usize)> = Vec::with_capacity(self.ranges.len());
+		for &(s, e) in &self.ranges {
+			match merged.last_mut() {
+				Some(last) if s <= last.1 =>
+					last.1 = last.1.max(e),
+
+				_ =>
+					merged.push((s, e)),
+			}
+		}
+
+		self.ranges = merged;
+	}
+
+	fn is_complete(&self) -> bool {
+		match self.total {
+			Some(total) =>
+				self.header.is_some() && self.ranges == [(0, total)],
+
+			None =>
+				false,
+		}
+	}
+
+	fn into_packet(self) -> Result<Packet<Vec<u8>>> {
+		let mut buffer = self.header.ok_or(Error::InvalidPacket)?;
+		buffer.extend_from_slice(&self.data[.. self.total.ok_or(Error::InvalidPacket)?]);
+
+		let length = u16::try_from(buffer.len()).map_err(|_| Error::InvalidPacket)?;
+		Cursor::new(&mut buffer[2 ..]).write_u16::<BigEndian>(length)?;
+
+		let mut packet = Packet::unchecked(buffer);
+		let flags      = packet.flags() - Flags::MORE_FRAGMENTS;
+
+		packet.set_flags(flags)?;
+		packet.set_offset(0)?;
+		packet.update_checksum()?;
+
+		Ok(packet)
+	}
+}
+
+/// IPv4 fragment reassembler.
+///
+/// Fragments are grouped by source, destination, protocol and ID, datagrams
+/// that aren't completed within the timeout are discarded, and the oldest
+/// pending datagrams are evicted when the memory limit is exceeded.
+#[derive(Debug)]
+pub struct Reassembler {
+	policy:  Policy,
+	timeout: Duration,
+	limit:   usize,
+	used:    usize,
+
+	pending: HashMap<Key, Datagram>,
+}
+
+impl Default for Reassembler {
+	fn default() -> Self {
+		Reassembler {
+			policy:  Policy::default(),
+			timeout: Duration::from_secs(30),
+			limit:   4 * 1024 * 1024,
+			used:    0,
+
+			pending: HashMap::new(),
+		}
+	}
+}
+
+impl Reassembler {
+	/// Create a new reassembler with the default settings.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Policy applied to overlapping fragments.
+	pub fn policy(mut self, value: Policy) -> Self {
+		self.policy = value;
+		self
+	}
+
+	/// Time after which an incomplete datagram is discarded.
+	pub fn timeout(mut self, value: Duration) -> Self {
+		self.timeout = value;
+		self
+	}
+
+	/// Maximum amount of memory used by pending datagrams.
+	pub fn limit(mut self, value: usize) -> Self {
+		self.limit = value;
+		self
+	}
+
+	/// Number of pending datagrams.
+	pub fn len(&self) -> usize {
+		self.pending.len()
+	}
+
+	/// Whether there are no pending datagrams.
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+
+	/// Memory used by pending datagrams.
+	pub fn used(&self) -> usize {
+		self.used
+	}
+
+	/// Discard the datagrams that timed out, returning how many were dropped.
+	pub fn expire(&mut self, now: Duration) -> usize {
+		let timeout = self.timeout;
+		let before  = self.pending.len();
+		let mut freed = 0;
+
+		self.pending.retain(|_, datagram| {
+			if now.saturating_sub(datagram.started) < timeout {
+				true
+			}
+			else {
+				freed += datagram.size();
+				false
+			}
+		});
+
+		self.used -= freed;
+		before - self.pending.len()
+	}
+
+	/// Add a fragment, returning the reassembled packet once complete.
+	///
+	/// # Note
+	///
+	/// Packets that aren't fragments are returned as they are.
+	pub fn push<B: AsRef<[u8]>>(&mut self, packet: &Packet<B>, now: Duration) -> Result<Option<Packet<Vec<u8>>>> {
+		self.expire(now);
+
+		let (header, payload) = packet.split();
+		let more  = packet.flags().contains(Flags::MORE_FRAGMENTS);
+		let start = packet.offset() as usize * 8;
+		let end   = start + payload.len();
+
+		if start == 0 && !more {
+			return Ok(Some(Packet::unchecked(packet.as_ref().to_vec())));
+		}
+
+		if (more && payload.len() & 0b111 != 0) || header.len() + end > u16::MAX as usize {
+			Err(Error::InvalidPacket)?
+		}
+
+		let key      = Key::new(packet);
+		let policy   = self.policy;
+		let datagram = self.pending.entry(key).or_insert_with(|| Datagram::new(now));
+		let before   = datagram.size();
+
+		let inconsistent = match datagram.total {
+			Some(total) =>
+				end > total || (!more && end != total),
+
+			None =>
+				!more && datagram.ranges.last().is_some_and(|&(_, e)| e > end),
+		};
+
+		if inconsistent || (policy == Policy::Drop && datagram.overlaps(start, end)) {
+			self.used -= before;
+			self.pending.remove(&key);
+
+			Err(Error::InvalidPacket)?
+		}
+
+		let datagram = self.pending.get_mut(&key).unwrap();
+		if datagram.data.len() < end {
+			datagram.data.resize(end, 0);
+		}
+
+		let ranges = if policy == Policy::Last {
+			vec![(start, end)]
+		}
+		else {
+			datagram.gaps(start, end)
+		};
+
+		for (s, e) in ranges {
+			datagram.data[s .. e].copy_from_slice(&payload[s - start .. e - start]);
+		}
+
+		if start == 0 && (datagram.header.is_none() || policy == Policy::Last) {
+			datagram.header = Some(header.to_vec());
+		}
+
+		if !more {
+			datagram.total = Some(end);
+		}
+
+		datagram.mark(start, end);
+		self.used = self.used - before + datagram.size();
+
+		if datagram.is_complete() {
+			let datagram = self.pending.remove(&key).unwrap();
+			self.used -= datagram.size();
+
+			return datagram.into_packet().map(Some);
+		}
+
+		while self.used > self.limit {
+			let oldest = self.pending.iter()
+				.filter(|&(k, _)| *k != key)
+				.min_by_key(|&(_, d)| d.started)
+				.map(|(k, _)| *k)
+				.unwrap_or(key);
+
+			let datagram = self.pending.remove(&oldest).unwrap();
+			self.used -= datagram.size();
+
+			if oldest == key {
+				Err(Error::SmallBuffer)?
+			}
+		}
+
+		Ok(None)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::ip::v4::reassembler::{Reassembler, Policy};
+
+	fn packet(id: u16, length: usize) -> Vec<u8> {
+		let payload = (0 .. length).map(|i| i as u8).collect::<Vec<_>>();
+
+		ip::v4::Builder::default()
+			.id(id).unwrap()
+			.ttl(64).unwrap()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.protocol(ip::Protocol::Udp).unwrap()
+			.payload(&payload).unwrap()
+			.build().unwrap()
+	}
+
+	fn fragments(raw: &[u8], mtu: usize) -> Vec<ip::v4::Packet<Vec<u8>>> {
+		ip::v4::Packet::new(raw).unwrap().fragments(mtu).unwrap().collect()
+	}
+
+	#[test]
+	fn in_order() {
+		let raw   = packet(1, 3000);
+		let frags = fragments(&raw, 1000);
+		let mut reassembler = Reassembler::new();
+
+		for frag in &frags[.. frags.len() - 1] {
+			assert!(reassembler.push(frag, Duration::from_secs(0)).unwrap().is_none());
+		}
+
+		let packet = reassembler.push(&frags[frags.len() - 1], Duration::from_secs(1)).unwrap().unwrap();
+		assert_eq!(packet.as_ref(), &raw[..]);
+		assert!(packet.is_valid());
+		assert!(reassembler.is_empty());
+		assert_eq!(reassembler.used(), 0);
+	}
+
+	#[test]
+	fn out_of_order() {
+		let raw   = packet(1, 3000);
+		let frags = fragments(&raw, 500);
+		let mut reassembler = Reassembler::new();
+		let mut result = None;
+
+		for i in [3, 6, 0, 2, 5, 1, 4].iter() {
+			assert!(result.is_none());
+			result = reassembler.push(&frags[*i], Duration::from_secs(0)).unwrap();
+		}
+
+		assert_eq!(result.unwrap().as_ref(), &raw[..]);
+	}
+
+	#[test]
+	fn interleaved() {
+		let first  = packet(1, 200);
+		let second = packet(2, 200);
+		let a = fragments(&first, 100);
+		let b = fragments(&second, 100);
+		let mut reassembler = Reassembler::new();
+
+		assert!(reassembler.push(&a[0], Duration::from_secs(0)).unwrap().is_none());
+		assert!(reassembler.push(&b[2], Duration::from_secs(0)).unwrap().is_none());
+		assert!(reassembler.push(&b[0], Duration::from_secs(0)).unwrap().is_none());
+		assert!(reassembler.push(&a[2], Duration::from_secs(0)).unwrap().is_none());
+		assert_eq!(reassembler.push(&a[1], Duration::from_secs(0)).unwrap().unwrap().as_ref(), &first[..]);
+		assert_eq!(reassembler.push(&b[1], Duration::from_secs(0)).unwrap().unwrap().as_ref(), &second[..]);
+	}
+
+	#[test]
+	fn unfragmented() {
+		let raw = packet(1, 100);
+		let ip  = ip::v4::Packet::new(&raw[..]).unwrap();
+		let mut reassembler = Reassembler::new();
+
+		assert_eq!(reassembler.push(&ip, Duration::from_secs(0)).unwrap().unwrap().as_ref(), &raw[..]);
+		assert!(reassembler.is_empty());
+	}
+
+	fn overlap(policy: Policy) -> Result<Option<Vec<u8>>, ()> {
+		let raw   = packet(1, 240);
+		let frags = fragments(&raw, 100);
+		let mut reassembler = Reassembler::new().policy(policy);
+
+		// Same offset and length as the second fragment, different data.
+		let mut forged = frags[1].as_ref().to_vec();
+		for byte in &mut forged[20 ..] {
+			*byte = 0xff;
+		}
+
+		reassembler.push(&frags[0], Duration::from_secs(0)).unwrap();
+		reassembler.push(&frags[1], Duration::from_secs(0)).unwrap();
+		reassembler.push(&ip::v4::Packet::unchecked(&forged[..]), Duration::from_secs(0))
+			.map_err(|_| ())?;
+
+		Ok(reassembler.push(&frags[2], Duration::from_secs(0)).unwrap()
+			.map(|p| p.payload().to_vec()))
+	}
+
+	#[test]
+	fn overlap_first() {
+		let payload = overlap(Policy::First).unwrap().unwrap();
+		assert_eq!(payload, (0 .. 240).map(|i| i as u8).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn overlap_last() {
+		let payload = overlap(Policy::Last).unwrap().unwrap();
+		assert!(payload[80 .. 160].iter().all(|&b| b == 0xff));
+		assert_eq!(payload[.. 80], (0 .. 80).map(|i| i as u8).collect::<Vec<_>>()[..]);
+	}
+
+	#[test]
+	fn overlap_drop() {
+		assert!(overlap(Policy::Drop).is_err());
+	}
+
+	#[test]
+	fn timeout() {
+		let raw   = packet(1, 200);
+		let frags = fragments(&raw, 100);
+		let mut reassembler = Reassembler::new().timeout(Duration::from_secs(10));
+
+		reassembler.push(&frags[0], Duration::from_secs(0)).unwrap();
+		reassembler.push(&frags[1], Duration::from_secs(5)).unwrap();
+		assert_eq!(reassembler.len(), 1);
+
+		assert!(reassembler.push(&frags[2], Duration::from_secs(11)).unwrap().is_none());
+		assert_eq!(reassembler.len(), 1);
+		assert_eq!(reassembler.expire(Duration::from_secs(30)), 1);
+		assert_eq!(reassembler.used(), 0);
+	}
+
+	#[test]
+	fn limit() {
+		let first  = fragments(&packet(1, 200), 100);
+		let second = fragments(&packet(2, 200), 100);
+		let mut reassembler = Reassembler::new().limit(200);
+
+		reassembler.push(&first[0], Duration::from_secs(0)).unwrap();
+		reassembler.push(&second[0], Duration::from_secs(1)).unwrap();
+		assert_eq!(reassembler.len(), 2);
+
+		reassembler.push(&second[1], Duration::from_secs(2)).unwrap();
+		assert_eq!(reassembler.len(), 1);
+		assert!(reassembler.used() <= 200);
+
+		assert!(reassembler.push(&second[2], Duration::from_secs(3)).unwrap().is_some());
+		assert!(Reassembler::new().limit(50).push(&first[0], Duration::from_secs(0)).is_err());
+	}
+
+	#[test]
+	fn inconsistent() {
+		let frags = fragments(&packet(1, 200), 100);
+		let mut reassembler = Reassembler::new();
+
+		// Data past the end of the last fragment.
+		let mut beyond = frags[1].as_ref().to_vec();
+		ip::v4::Packet::unchecked(&mut beyond[..]).set_offset(30).unwrap();
+
+		reassembler.push(&frags[2], Duration::from_secs(0)).unwrap();
+		assert!(reassembler.push(&ip::v4::Packet::unchecked(&beyond[..]), Duration::from_secs(0)).is_err());
+		assert!(reassembler.is_empty());
+		assert_eq!(reassembler.used(), 0);
+	}
+
+	#[test]
+	fn oversized() {
+		fn fragment(header: usize, start: usize, length: usize, more: bool) -> Vec<u8> {
+			let mut raw = vec![0u8; header + length];
+			raw[0] = 0x40 | (header / 4) as u8;
+			raw[2 .. 4].copy_from_slice(&((header + length) as u16).to_be_bytes());
+			raw[6 .. 8].copy_from_slice(&(u16::from(more) << 13 | (start / 8) as u16).to_be_bytes());
+			raw[8] = 64;
+			raw[9] = ip::Protocol::Udp.into();
+
+			raw
+		}
+
+		// Each fragment fits, but the first one's options push the whole
+		// datagram past the maximum length.
+		let first  = fragment(60, 0, 8, true);
+		let middle = fragment(20, 8, 65504, true);
+		let last   = fragment(20, 65512, 3, false);
+		let mut reassembler = Reassembler::new().limit(usize::MAX);
+
+		assert!(reassembler.push(&ip::v4::Packet::unchecked(&first[..]), Duration::from_secs(0)).unwrap().is_none());
+		assert!(reassembler.push(&ip::v4::Packet::unchecked(&middle[..]), Duration::from_secs(0)).unwrap().is_none());
+		assert!(reassembler.push(&ip::v4::Packet::unchecked(&last[..]), Duration::from_secs(0)).is_err());
+		assert!(reassembler.is_empty());
+		assert_eq!(reassembler.used(), 0);
+	}
+}
@@ -12,71 +12,54 @@
 //
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
-
-
-
+use std::io::Cursor;
 use std::net::Ipv6Addr;
+use byteorder::{WriteBytesExt, BigEndian};
 
-use crate::{error::*};
+use crate::error::*;
 use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::packet::{AsPacket, AsPacketMut};
+use crate::ip::Protocol;
 use crate::ip::v6::Packet;
+
 /// IPv6 packet builder.
+#[derive(Debug)]
 pub struct Builder<B: Buffer = buffer::Dynamic> {
 	buffer:    B,
 	finalizer: Finalization,
-	payload: bool
+
+	payload: bool,
 }
 
 impl<B: Buffer> Build<B> for Builder<B> {
-	// fn with(buffer: B) -> Result<Self> {
-	// 	use crate::size::header::Min;
-	// 	buffer.next(Packet::<()>::min())?;
-
-	// 	Ok(Builder {
-	// 		buffer:    buffer,
-	// 		finalizer: Default::default(),
-	// 		payload: false
-	// 	})
-	// }
-fn with(mut buffer: B) -> Result<Self> {
+	fn with(mut buffer: B) -> Result<Self> {
 		use crate::size::header::Min;
 		buffer.next(Packet::<()>::min())?;
 
-		buffer.data_mut()[0] = (6 << 4);
+		// Set version to 6, the transport builders rely on it to find out which
+		// pseudo-header to use.
+		buffer.data_mut()[0] = 6 << 4;
 
 		Ok(Builder {
-			buffer:    buffer,
+			buffer,
 			finalizer: Default::default(),
+
 			payload: false,
 		})
 	}
+
 	fn finalizer(&mut self) -> &mut Finalization {
 		&mut self.finalizer
 	}
 
 	fn build(mut self) -> Result<B::Inner> {
 		self.prepare();
-		
+
 		let mut buffer = self.buffer.into_inner();
 		self.finalizer.finalize(buffer.as_mut())?;
 		Ok(buffer)
 	}
-	
-}
-
-impl<B: Buffer> Builder<B> {
-	fn prepare(&mut self) {
-		let offset = self.buffer.offset();
-		self.finalizer.add(move |out| {
-			// Set the version to 6 and the header length.
-			let old = out[offset];
-			out[offset] = (6 << 4) | (old & 0b0000_1111);
-
-			Ok(())
-		});
-	}
 }
 
 impl Default for Builder<buffer::Dynamic> {
@@ -84,6 +67,7 @@ impl Default for Builder<buffer::Dynamic> {
 		Builder::with(buffer::Dynamic::default()).unwrap()
 	}
 }
+
 impl<'a, B: Buffer> AsPacket<'a, Packet<&'a [u8]>> for Builder<B> {
 	fn as_packet(&self) -> Result<Packet<&[u8]>> {
 		Packet::new(self.buffer.data())
@@ -96,35 +80,73 @@ impl<'a, B: Buffer> AsPacketMut<'a, Packet<&'a mut [u8]>> for Builder<B> {
 	}
 }
 
-impl <B: Buffer> Builder<B> {
+macro_rules! protocol {
+	($(#[$attr:meta])* fn $name:ident($($module:ident)::+, $protocol:ident)) => (
+		$(#[$attr])*
+		pub fn $name(mut self) -> Result<crate::$($module)::+::Builder<B>> {
+			if self.payload {
+				Err(Error::AlreadyDefined)?
+			}
+
+			self = self.next_header(Protocol::$protocol.into())?;
+			self.prepare();
+
+			let mut builder = crate::$($module)::+::Builder::with(self.buffer)?;
+			builder.finalizer().extend(self.finalizer);
+
+			Ok(builder)
+		}
+	)
+}
+
+impl<B: Buffer> Builder<B> {
+	/// Traffic class.
 	pub fn traffic_class(mut self, value: u8) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_traffic_class(value)?;
 		Ok(self)
 	}
-	pub fn flow_label(mut self, value:u32) -> Result<Self> {
+
+	/// Flow label.
+	pub fn flow_label(mut self, value: u32) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_flow_label(value)?;
 		Ok(self)
 	}
-	pub fn payload_length(mut self, value: u16) ->Result<Self> {
+
+	/// Payload length.
+	///
+	/// # Note
+	///
+	/// The length is recomputed from the actual payload when the packet is
+	/// built.
+	pub fn payload_length(mut self, value: u16) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_payload_length(value)?;
 		Ok(self)
 	}
-	pub fn next_header(mut self, value:u8) -> Result<Self> {
+
+	/// Next header.
+	pub fn next_header(mut self, value: u8) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_next_header(value)?;
 		Ok(self)
 	}
+
+	/// Hop limit.
 	pub fn hop_limit(mut self, value: u8) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_hop_limit(value)?;
 		Ok(self)
 	}
+
+	/// Source address.
 	pub fn source(mut self, value: Ipv6Addr) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_source(value)?;
 		Ok(self)
 	}
+
+	/// Destination address.
 	pub fn destination(mut self, value: Ipv6Addr) -> Result<Self> {
 		Packet::unchecked(self.buffer.data_mut()).set_destination(value)?;
 		Ok(self)
 	}
+
 	/// Payload for the packet.
 	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
 		if self.payload {
@@ -133,15 +155,124 @@ impl <B: Buffer> Builder<B> {
 
 		self.payload = true;
 
-		let mut len = 0u16;
-
 		for byte in value {
-			len += 1;
 			self.buffer.more(1)?;
 			*self.buffer.data_mut().last_mut().unwrap() = *byte;
 		}
-		self = self.payload_length(len).unwrap();
 
 		Ok(self)
 	}
-}
\ No newline at end of file
+
+	fn prepare(&mut self) {
+		use crate::size::header::Min;
+		let offset = self.buffer.offset();
+
+		self.finalizer.add(move |out| {
+			// Set the version to 6.
+			let old = out[offset];
+			out[offset] = (6 << 4) | (old & 0b0000_1111);
+
+			// Calculate and write the payload length.
+			let length = out.len() - (offset + Packet::<()>::min());
+			if length > u16::MAX as usize {
+				Err(Error::InvalidPacket)?
+			}
+
+			Cursor::new(&mut out[offset + 4 ..])
+				.write_u16::<BigEndian>(length as u16)?;
+
+			Ok(())
+		});
+	}
+
+	protocol!(/// Build an ICMPv6 packet.
+		fn icmp(icmp::v6, Ipv6Icmp));
+
+	protocol!(/// Build a TCP packet.
+		fn tcp(tcp, Tcp));
+
+	protocol!(/// Build a UDP packet.
+		fn udp(udp, Udp));
+}
+
+#[cfg(test)]
+mod test {
+	use std::net::Ipv6Addr;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::tcp;
+	use crate::udp;
+	use crate::icmp;
+
+	#[test]
+	fn icmp() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.icmp().unwrap()
+				.echo().unwrap().request().unwrap()
+					.identifier(42).unwrap()
+					.sequence(2).unwrap()
+					.payload(b"test").unwrap()
+					.build().unwrap();
+
+		let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+		assert_eq!(ip.payload_length(), 12);
+		assert_eq!(ip::Protocol::from(ip.next_header()), ip::Protocol::Ipv6Icmp);
+		assert_eq!(ip.hop_limit(), 64);
+
+		let icmp = icmp::v6::Packet::new(ip.payload()).unwrap();
+		assert_eq!(icmp.kind(), icmp::v6::Kind::EchoRequest);
+		assert!(icmp.is_valid(&ip));
+	}
+
+	#[test]
+	fn tcp() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(9001).unwrap()
+				.flags(tcp::flag::SYN).unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+		assert_eq!(ip.payload_length(), 23);
+		assert_eq!(ip::Protocol::from(ip.next_header()), ip::Protocol::Tcp);
+		assert_eq!(ip.source(), "2001:db8::1".parse::<Ipv6Addr>().unwrap());
+		assert_eq!(ip.destination(), "2001:db8::2".parse::<Ipv6Addr>().unwrap());
+
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(tcp.source(), 1337);
+		assert_eq!(tcp.destination(), 9001);
+		assert_eq!(tcp.payload(), b"lol");
+		assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+	}
+
+	#[test]
+	fn udp() {
+		let packet = ip::v6::Builder::default()
+			.hop_limit(64).unwrap()
+			.source("2001:db8::1".parse().unwrap()).unwrap()
+			.destination("2001:db8::2".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(1337).unwrap()
+				.destination(9001).unwrap()
+				.payload(b"test").unwrap()
+				.build().unwrap();
+
+		let ip = ip::v6::Packet::new(&packet[..]).unwrap();
+		assert_eq!(ip.payload_length(), 12);
+		assert_eq!(ip::Protocol::from(ip.next_header()), ip::Protocol::Udp);
+
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(udp.source(), 1337);
+		assert_eq!(udp.destination(), 9001);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
+}
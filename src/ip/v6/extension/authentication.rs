@@ -0,0 +1,116 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+use crate::ip::Protocol;
+
+/// Authentication header parser.
+pub struct Authentication<B> {
+	buffer: B,
+}
+
+sized!(Authentication,
+	header {
+		min:  12,
+		max:  12,
+		size: 12,
+	}
+
+	payload {
+		min:  0,
+		max:  257 * 4 - 12,
+		size: p => (p.length() as usize + 2) * 4 - 12,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Authentication<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Authentication")
+			.field("next_header", &self.next_header())
+			.field("length", &self.length())
+			.field("spi", &self.spi())
+			.field("sequence", &self.sequence())
+			.field("icv", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+	/// Parse an authentication header, checking the buffer contents are
+	/// correct.
+	pub fn new(buffer: B) -> Result<Authentication<B>> {
+		use crate::size::header::Min;
+
+		let packet = Authentication { buffer };
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if (packet.length() as usize + 2) * 4 < Self::min() {
+			Err(Error::InvalidPacket)?
+		}
+
+		if packet.buffer.as_ref().len() < (packet.length() as usize + 2) * 4 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Authentication<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Authentication<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Authentication<&[u8]>> {
+		Authentication::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Authentication<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.as_ref().split_at(12)
+	}
+}
+
+impl<B: AsRef<[u8]>> Authentication<B> {
+	/// Protocol of the following header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the header in units of 4 octets, minus 2.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Security Parameters Index.
+	pub fn spi(&self) -> u32 {
+		(&self.buffer.as_ref()[4 ..]).read_u32::<BigEndian>().unwrap()
+	}
+
+	/// Sequence number.
+	pub fn sequence(&self) -> u32 {
+		(&self.buffer.as_ref()[8 ..]).read_u32::<BigEndian>().unwrap()
+	}
+}
@@ -0,0 +1,104 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+use crate::ip::Protocol;
+
+/// Fragment header parser.
+pub struct Fragment<B> {
+	buffer: B,
+}
+
+sized!(Fragment,
+	header {
+		min:  8,
+		max:  8,
+		size: 8,
+	}
+
+	payload {
+		min:  0,
+		max:  0,
+		size: 0,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Fragment<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Fragment")
+			.field("next_header", &self.next_header())
+			.field("offset", &self.offset())
+			.field("more", &self.is_more())
+			.field("identification", &self.identification())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Fragment<B> {
+	/// Parse a fragment header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Fragment<B>> {
+		use crate::size::header::Min;
+
+		let packet = Fragment { buffer };
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Fragment<B> {
+	fn as_ref(&self) -> &[u8] {
+		&self.buffer.as_ref()[.. 8]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Fragment<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Fragment<&[u8]>> {
+		Fragment::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Fragment<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.as_ref().split_at(8)
+	}
+}
+
+impl<B: AsRef<[u8]>> Fragment<B> {
+	/// Protocol of the following header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Fragment offset in units of 8 octets.
+	pub fn offset(&self) -> u16 {
+		(&self.buffer.as_ref()[2 ..]).read_u16::<BigEndian>().unwrap() >> 3
+	}
+
+	/// Whether more fragments follow.
+	pub fn is_more(&self) -> bool {
+		self.buffer.as_ref()[3] & 0b1 != 0
+	}
+
+	/// Fragment identification.
+	pub fn identification(&self) -> u32 {
+		(&self.buffer.as_ref()[4 ..]).read_u32::<BigEndian>().unwrap()
+	}
+}
@@ -0,0 +1,259 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// Hop-by-Hop and Destination option parser.
+pub mod option;
+
+mod options;
+pub use self::options::Options;
+
+mod routing;
+pub use self::routing::Routing;
+
+mod fragment;
+pub use self::fragment::Fragment;
+
+mod authentication;
+pub use self::authentication::Authentication;
+
+use crate::error::*;
+use crate::ip::Protocol;
+
+/// IPv6 extension header.
+#[derive(Debug)]
+pub enum Extension<B: AsRef<[u8]>> {
+	/// Hop-by-Hop Options header.
+	HopByHop(Options<B>),
+
+	/// Routing header.
+	Routing(Routing<B>),
+
+	/// Fragment header.
+	Fragment(Fragment<B>),
+
+	/// Destination Options header.
+	Destination(Options<B>),
+
+	/// Authentication header.
+	Authentication(Authentication<B>),
+}
+
+impl<B: AsRef<[u8]>> Extension<B> {
+	/// Parse the extension header for the given protocol, returns `None` if the
+	/// protocol isn't an extension header that can be walked over.
+	pub fn new(protocol: Protocol, buffer: B) -> Option<Result<Extension<B>>> {
+		Some(match protocol {
+			Protocol::Hopopt =>
+				Options::new(buffer).map(Extension::HopByHop),
+
+			Protocol::Ipv6Route =>
+				Routing::new(buffer).map(Extension::Routing),
+
+			Protocol::Ipv6Frag =>
+				Fragment::new(buffer).map(Extension::Fragment),
+
+			Protocol::Ipv6Opts =>
+				Options::new(buffer).map(Extension::Destination),
+
+			Protocol::Ah =>
+				Authentication::new(buffer).map(Extension::Authentication),
+
+			_ =>
+				return None
+		})
+	}
+
+	/// Protocol of this extension header.
+	pub fn protocol(&self) -> Protocol {
+		match *self {
+			Extension::HopByHop(_)       => Protocol::Hopopt,
+			Extension::Routing(_)        => Protocol::Ipv6Route,
+			Extension::Fragment(_)       => Protocol::Ipv6Frag,
+			Extension::Destination(_)    => Protocol::Ipv6Opts,
+			Extension::Authentication(_) => Protocol::Ah,
+		}
+	}
+
+	/// Protocol of the following header.
+	pub fn next_header(&self) -> Protocol {
+		match *self {
+			Extension::HopByHop(ref e)       => e.next_header(),
+			Extension::Routing(ref e)        => e.next_header(),
+			Extension::Fragment(ref e)       => e.next_header(),
+			Extension::Destination(ref e)    => e.next_header(),
+			Extension::Authentication(ref e) => e.next_header(),
+		}
+	}
+
+	/// Length of the extension header in octets.
+	pub fn length(&self) -> usize {
+		use crate::size::Size;
+
+		match *self {
+			Extension::HopByHop(ref e)       => e.size(),
+			Extension::Routing(ref e)        => e.size(),
+			Extension::Fragment(ref e)       => e.size(),
+			Extension::Destination(ref e)    => e.size(),
+			Extension::Authentication(ref e) => e.size(),
+		}
+	}
+}
+
+/// Iterator over the extension header chain of an IPv6 packet.
+pub struct ExtensionIter<'a> {
+	buffer: &'a [u8],
+	next:   Protocol,
+	offset: usize,
+}
+
+impl<'a> ExtensionIter<'a> {
+	pub(in crate::ip::v6) fn new(next: Protocol, buffer: &'a [u8]) -> Self {
+		ExtensionIter {
+			buffer,
+			next,
+			offset: 0,
+		}
+	}
+
+	/// Protocol of the header following the last parsed extension header.
+	pub fn next_header(&self) -> Protocol {
+		self.next
+	}
+
+	/// Offset of the header following the last parsed extension header,
+	/// relative to the end of the fixed IPv6 header.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+}
+
+impl<'a> Iterator for ExtensionIter<'a> {
+	type Item = Result<Extension<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let buffer = &self.buffer[self.offset ..];
+
+		match Extension::new(self.next, buffer)? {
+			Ok(extension) => {
+				self.next    = extension.next_header();
+				self.offset += extension.length();
+
+				Some(Ok(extension))
+			}
+
+			Err(error) => {
+				self.buffer = &[];
+				self.offset = 0;
+				self.next   = Protocol::Ipv6NoNxt;
+
+				Some(Err(error))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::packet::Packet;
+	use crate::ip;
+	use crate::ip::v6::extension::{self, Extension};
+
+	#[test]
+	fn chain() {
+		let raw = hex::decode("600000000030004020010db800000000000000000000000120010db80000000000000000000000022b000502000001002c0200010000000020010db8000000000000000000000003110000011234567804d2003500080000").unwrap();
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+
+		let extensions = ip.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(extensions.len(), 3);
+
+		match extensions[0] {
+			Extension::HopByHop(ref options) => {
+				assert_eq!(options.next_header(), ip::Protocol::Ipv6Route);
+				assert_eq!(options.payload().len(), 6);
+
+				let options = options.options().collect::<Result<Vec<_>, _>>().unwrap();
+				assert_eq!(options.len(), 2);
+				assert_eq!(options[0].number(), extension::option::Number::RouterAlert);
+				assert_eq!(options[0].action(), extension::option::Action::Skip);
+				assert_eq!(options[0].payload(), &[0, 0]);
+				assert!(options[1].is_padding());
+			}
+
+			_ => panic!("expected hop-by-hop options"),
+		}
+
+		match extensions[1] {
+			Extension::Routing(ref routing) => {
+				assert_eq!(routing.next_header(), ip::Protocol::Ipv6Frag);
+				assert_eq!(routing.kind(), 0);
+				assert_eq!(routing.segments_left(), 1);
+				assert_eq!(routing.payload().len(), 20);
+			}
+
+			_ => panic!("expected routing header"),
+		}
+
+		match extensions[2] {
+			Extension::Fragment(ref fragment) => {
+				assert_eq!(fragment.next_header(), ip::Protocol::Udp);
+				assert_eq!(fragment.offset(), 0);
+				assert!(fragment.is_more());
+				assert_eq!(fragment.identification(), 0x12345678);
+			}
+
+			_ => panic!("expected fragment header"),
+		}
+
+		assert_eq!(ip.protocol().unwrap(), ip::Protocol::Udp);
+		assert_eq!(ip.payload_offset().unwrap(), 80);
+	}
+
+	#[test]
+	fn none() {
+		let raw = hex::decode("600000000008114020010db800000000000000000000000120010db800000000000000000000000204d2003500080000").unwrap();
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+
+		assert_eq!(ip.extensions().count(), 0);
+		assert_eq!(ip.protocol().unwrap(), ip::Protocol::Udp);
+		assert_eq!(ip.payload_offset().unwrap(), 40);
+	}
+
+	#[test]
+	fn fragment() {
+		let mut raw = hex::decode("6000000000102c4020010db800000000000000000000000120010db80000000000000000000000021100000012345678003500350008dead").unwrap();
+
+		// First fragment, the UDP header follows the fragment header.
+		let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ip.protocol().unwrap(), ip::Protocol::Udp);
+		assert_eq!(ip.payload_offset().unwrap(), 48);
+
+		// Fragment at offset 100, only fragment data follows.
+		raw[42] = 0x03;
+		raw[43] = 0x20;
+
+		let ip = ip::v6::Packet::new(&raw[..]).unwrap();
+		assert_eq!(ip.protocol().unwrap(), ip::Protocol::Ipv6Frag);
+		assert_eq!(ip.payload_offset().unwrap(), 48);
+	}
+
+	#[test]
+	fn truncated() {
+		let raw = hex::decode("600000000008004020010db800000000000000000000000120010db80000000000000000000000022b01050200000100").unwrap();
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+
+		assert!(ip.extensions().next().unwrap().is_err());
+		assert!(ip.protocol().is_err());
+		assert!(ip.payload_offset().is_err());
+	}
+}
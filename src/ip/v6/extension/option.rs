@@ -0,0 +1,208 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+
+/// Hop-by-Hop and Destination option parser.
+pub struct Option<B> {
+	buffer: B,
+}
+
+sized!(Option,
+	header {
+		min:  1,
+		max:  2,
+		size: p => match p.number() {
+			Number::Pad1 => 1,
+			_            => 2,
+		},
+	}
+
+	payload {
+		min:  0,
+		max:  255,
+		size: p => match p.number() {
+			Number::Pad1 => 0,
+			_            => p.length() as usize,
+		},
+	});
+
+/// Hop-by-Hop and Destination option number.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Number {
+	/// Single octet of padding.
+	Pad1,
+
+	/// Multiple octets of padding.
+	PadN,
+
+	/// Router alert.
+	RouterAlert,
+
+	/// Tunnel encapsulation limit.
+	TunnelEncapsulationLimit,
+
+	/// Jumbo payload length.
+	JumboPayload,
+
+	/// Home address for Mobile IPv6.
+	HomeAddress,
+
+	/// Unknown option.
+	Unknown(u8),
+}
+
+/// Action to take when the option is not recognized.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Action {
+	/// Skip over the option.
+	Skip,
+
+	/// Discard the packet.
+	Discard,
+
+	/// Discard the packet and send an ICMP Parameter Problem.
+	DiscardAndReport,
+
+	/// Discard the packet and send an ICMP Parameter Problem if the
+	/// destination is not multicast.
+	DiscardAndReportUnicast,
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Option")
+			.field("number", &self.number())
+			.field("action", &self.action())
+			.field("mutable", &self.is_mutable())
+			.field("length", &self.length())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Parse an option, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Option<B>> {
+		use crate::size::Size;
+
+		let option = Option { buffer };
+
+		if option.buffer.as_ref().is_empty() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if option.number() != Number::Pad1 && option.buffer.as_ref().len() < 2 {
+			Err(Error::SmallBuffer)?
+		}
+
+		if option.buffer.as_ref().len() < option.size() {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(option)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Option<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Option<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Option<&[u8]>> {
+		Option::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Option<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		use crate::size::header::Size;
+
+		self.as_ref().split_at(self.size())
+	}
+}
+
+impl<B: AsRef<[u8]>> Option<B> {
+	/// Option number.
+	pub fn number(&self) -> Number {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Action to take when the option is not recognized.
+	pub fn action(&self) -> Action {
+		match self.buffer.as_ref()[0] >> 6 {
+			0b00 => Action::Skip,
+			0b01 => Action::Discard,
+			0b10 => Action::DiscardAndReport,
+			_    => Action::DiscardAndReportUnicast,
+		}
+	}
+
+	/// Whether the option data may change en route.
+	pub fn is_mutable(&self) -> bool {
+		self.buffer.as_ref()[0] & 0b0010_0000 != 0
+	}
+
+	/// Length of the option data.
+	pub fn length(&self) -> u8 {
+		match self.number() {
+			Number::Pad1 => 0,
+			_            => self.buffer.as_ref()[1],
+		}
+	}
+
+	/// Whether the option is padding.
+	pub fn is_padding(&self) -> bool {
+		matches!(self.number(), Number::Pad1 | Number::PadN)
+	}
+}
+
+impl From<u8> for Number {
+	fn from(value: u8) -> Self {
+		use self::Number::*;
+
+		match value {
+			0x00 => Pad1,
+			0x01 => PadN,
+			0x04 => TunnelEncapsulationLimit,
+			0x05 => RouterAlert,
+			0xc2 => JumboPayload,
+			0xc9 => HomeAddress,
+			n    => Unknown(n),
+		}
+	}
+}
+
+impl From<Number> for u8 {
+	fn from(value: Number) -> u8 {
+		use self::Number::*;
+
+		match value {
+			Pad1                     => 0x00,
+			PadN                     => 0x01,
+			TunnelEncapsulationLimit => 0x04,
+			RouterAlert              => 0x05,
+			JumboPayload             => 0xc2,
+			HomeAddress              => 0xc9,
+			Unknown(n)               => n,
+		}
+	}
+}
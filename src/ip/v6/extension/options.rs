@@ -0,0 +1,136 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+use crate::ip::Protocol;
+use crate::ip::v6::extension::option;
+
+/// Hop-by-Hop and Destination Options header parser.
+pub struct Options<B> {
+	buffer: B,
+}
+
+sized!(Options,
+	header {
+		min:  2,
+		max:  2,
+		size: 2,
+	}
+
+	payload {
+		min:  6,
+		max:  255 * 8 + 6,
+		size: p => p.length() as usize * 8 + 6,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Options<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Options")
+			.field("next_header", &self.next_header())
+			.field("length", &self.length())
+			.field("options", &self.options().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Options<B> {
+	/// Parse an options header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Options<B>> {
+		use crate::size::header::Min;
+
+		let packet = Options { buffer };
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if packet.buffer.as_ref().len() < (packet.length() as usize + 1) * 8 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Options<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Options<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Options<&[u8]>> {
+		Options::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Options<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.as_ref().split_at(2)
+	}
+}
+
+impl<B: AsRef<[u8]>> Options<B> {
+	/// Protocol of the following header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the header in units of 8 octets, not including the first 8
+	/// octets.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Options within the header, padding included.
+	pub fn options(&self) -> OptionIter<'_> {
+		OptionIter {
+			buffer: self.payload(),
+		}
+	}
+}
+
+/// Iterator over Hop-by-Hop and Destination options.
+pub struct OptionIter<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+	type Item = Result<option::Option<&'a [u8]>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		use crate::size::Size;
+
+		if self.buffer.is_empty() {
+			return None;
+		}
+
+		match option::Option::new(self.buffer) {
+			Ok(option) => {
+				self.buffer = &self.buffer[option.size() ..];
+				Some(Ok(option))
+			}
+
+			Err(error) => {
+				self.buffer = &[];
+				Some(Err(error))
+			}
+		}
+	}
+}
@@ -0,0 +1,111 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::fmt;
+
+use crate::error::*;
+use crate::packet::{Packet as P, AsPacket};
+use crate::ip::Protocol;
+
+/// Routing header parser.
+pub struct Routing<B> {
+	buffer: B,
+}
+
+sized!(Routing,
+	header {
+		min:  4,
+		max:  4,
+		size: 4,
+	}
+
+	payload {
+		min:  4,
+		max:  255 * 8 + 4,
+		size: p => p.length() as usize * 8 + 4,
+	});
+
+impl<B: AsRef<[u8]>> fmt::Debug for Routing<B> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ip::v6::extension::Routing")
+			.field("next_header", &self.next_header())
+			.field("length", &self.length())
+			.field("kind", &self.kind())
+			.field("segments_left", &self.segments_left())
+			.field("payload", &self.payload())
+			.finish()
+	}
+}
+
+impl<B: AsRef<[u8]>> Routing<B> {
+	/// Parse a routing header, checking the buffer contents are correct.
+	pub fn new(buffer: B) -> Result<Routing<B>> {
+		use crate::size::header::Min;
+
+		let packet = Routing { buffer };
+
+		if packet.buffer.as_ref().len() < Self::min() {
+			Err(Error::SmallBuffer)?
+		}
+
+		if packet.buffer.as_ref().len() < (packet.length() as usize + 1) * 8 {
+			Err(Error::SmallBuffer)?
+		}
+
+		Ok(packet)
+	}
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Routing<B> {
+	fn as_ref(&self) -> &[u8] {
+		use crate::size::Size;
+
+		&self.buffer.as_ref()[.. self.size()]
+	}
+}
+
+impl<'a, B: AsRef<[u8]>> AsPacket<'a, Routing<&'a [u8]>> for B {
+	fn as_packet(&self) -> Result<Routing<&[u8]>> {
+		Routing::new(self.as_ref())
+	}
+}
+
+impl<B: AsRef<[u8]>> P for Routing<B> {
+	fn split(&self) -> (&[u8], &[u8]) {
+		self.as_ref().split_at(4)
+	}
+}
+
+impl<B: AsRef<[u8]>> Routing<B> {
+	/// Protocol of the following header.
+	pub fn next_header(&self) -> Protocol {
+		self.buffer.as_ref()[0].into()
+	}
+
+	/// Length of the header in units of 8 octets, not including the first 8
+	/// octets.
+	pub fn length(&self) -> u8 {
+		self.buffer.as_ref()[1]
+	}
+
+	/// Routing type.
+	pub fn kind(&self) -> u8 {
+		self.buffer.as_ref()[2]
+	}
+
+	/// Number of route segments remaining.
+	pub fn segments_left(&self) -> u8 {
+		self.buffer.as_ref()[3]
+	}
+}
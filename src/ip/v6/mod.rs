@@ -12,6 +12,10 @@
 //
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
+/// IPv6 extension header parsers.
+pub mod extension;
+pub use self::extension::Extension;
+
 mod packet;
 pub use self::packet::Packet;
 
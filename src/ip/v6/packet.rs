@@ -16,6 +16,8 @@ use crate::error::*;
 use crate::packet::{AsPacket, AsPacketMut, Packet as P, PacketMut as PM};
 use std::fmt;
 use std::net::Ipv6Addr;
+use crate::ip::Protocol;
+use crate::ip::v6::extension::{Extension, ExtensionIter};
 
 
 /// IPv6 packet parser.
@@ -227,6 +229,47 @@ impl<B: AsRef<[u8]>> Packet<B> {
             self.buffer.as_ref()[39],
         ])
     }
+
+    /// Iterator over the extension header chain.
+    pub fn extensions(&self) -> ExtensionIter<'_> {
+        ExtensionIter::new(self.next_header().into(), &self.buffer.as_ref()[40..])
+    }
+
+    /// Upper-layer protocol, found by walking the extension header chain.
+    ///
+    /// # Note
+    ///
+    /// Headers that cannot be walked over, like ESP, are returned as the
+    /// upper-layer protocol. Fragments after the first carry no upper-layer
+    /// header, so `Ipv6Frag` is returned for them.
+    pub fn protocol(&self) -> Result<Protocol> {
+        self.upper_layer().map(|(protocol, _)| protocol)
+    }
+
+    /// Offset of the upper-layer header from the start of the packet.
+    pub fn payload_offset(&self) -> Result<usize> {
+        self.upper_layer().map(|(_, offset)| offset)
+    }
+
+    fn upper_layer(&self) -> Result<(Protocol, usize)> {
+        let mut extensions = self.extensions();
+        let mut first      = true;
+
+        for extension in &mut extensions {
+            if let Extension::Fragment(fragment) = extension? {
+                if fragment.offset() != 0 {
+                    first = false;
+                    break;
+                }
+            }
+        }
+
+        if !first {
+            return Ok((Protocol::Ipv6Frag, 40 + extensions.offset()));
+        }
+
+        Ok((extensions.next_header(), 40 + extensions.offset()))
+    }
 }
 impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
     pub fn set_traffic_class(&mut self, value: u8) -> Result<&mut Self> {
@@ -31,9 +31,15 @@ pub use crate::buffer::Buffer;
 pub mod builder;
 pub use crate::builder::Builder;
 
+/// Internet checksum helpers.
+pub mod checksum;
+
 /// Ethernet packet parser and builder.
 pub mod ether;
 
+/// ARP packet parser and builder.
+pub mod arp;
+
 /// IPv4 and IPv6 packet parser and builder.
 pub mod ip;
 
@@ -45,3 +51,25 @@ pub mod tcp;
 
 /// UDP packet parser and builder.
 pub mod udp;
+
+/// GRE packet parser and builder.
+pub mod gre;
+
+/// DNS message parser and builder.
+pub mod dns;
+
+/// DHCPv4 message parser and builder.
+pub mod dhcp;
+
+/// Capture file reader and writer.
+pub mod pcap;
+
+/// pcapng capture file reader and writer.
+pub mod pcapng;
+
+/// Dissection of a whole buffer in its protocol layers.
+pub mod dissect;
+pub use crate::dissect::dissect;
+
+/// Flow identification.
+pub mod flow;
@@ -0,0 +1,120 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Write;
+use byteorder::{ByteOrder, WriteBytesExt, BigEndian, LittleEndian};
+
+use crate::error::*;
+use crate::pcap::LinkType;
+
+/// Byte order of a capture file.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Endianness {
+	/// Most significant byte first.
+	Big,
+
+	/// Least significant byte first.
+	Little,
+}
+
+impl Endianness {
+	pub(crate) fn read_u16(self, buffer: &[u8]) -> u16 {
+		match self {
+			Endianness::Big    => BigEndian::read_u16(buffer),
+			Endianness::Little => LittleEndian::read_u16(buffer),
+		}
+	}
+
+	pub(crate) fn read_u32(self, buffer: &[u8]) -> u32 {
+		match self {
+			Endianness::Big    => BigEndian::read_u32(buffer),
+			Endianness::Little => LittleEndian::read_u32(buffer),
+		}
+	}
+
+	pub(crate) fn write_u16<W: Write>(self, inner: &mut W, value: u16) -> Result<()> {
+		match self {
+			Endianness::Big    => inner.write_u16::<BigEndian>(value)?,
+			Endianness::Little => inner.write_u16::<LittleEndian>(value)?,
+		}
+
+		Ok(())
+	}
+
+	pub(crate) fn write_u32<W: Write>(self, inner: &mut W, value: u32) -> Result<()> {
+		match self {
+			Endianness::Big    => inner.write_u32::<BigEndian>(value)?,
+			Endianness::Little => inner.write_u32::<LittleEndian>(value)?,
+		}
+
+		Ok(())
+	}
+}
+
+/// Resolution of the timestamps in a capture file.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Resolution {
+	/// Timestamps in microseconds.
+	Micro,
+
+	/// Timestamps in nanoseconds.
+	Nano,
+}
+
+/// Global header of a capture file.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Header {
+	/// Byte order of the file.
+	pub endianness: Endianness,
+
+	/// Resolution of the timestamps.
+	pub resolution: Resolution,
+
+	/// Major version of the format.
+	pub major: u16,
+
+	/// Minor version of the format.
+	pub minor: u16,
+
+	/// Maximum number of octets captured per packet.
+	pub snaplen: u32,
+
+	/// Link-layer header type of the packets.
+	pub link_type: LinkType,
+}
+
+impl Default for Header {
+	fn default() -> Self {
+		Header {
+			endianness: Endianness::Little,
+			resolution: Resolution::Micro,
+			major:      2,
+			minor:      4,
+			snaplen:    65535,
+			link_type:  LinkType::Ethernet,
+		}
+	}
+}
+
+impl Header {
+	pub(in crate::pcap) const MAGIC_MICRO: u32 = 0xa1b2_c3d4;
+	pub(in crate::pcap) const MAGIC_NANO: u32  = 0xa1b2_3c4d;
+
+	pub(in crate::pcap) fn magic(&self) -> u32 {
+		match self.resolution {
+			Resolution::Micro => Self::MAGIC_MICRO,
+			Resolution::Nano  => Self::MAGIC_NANO,
+		}
+	}
+}
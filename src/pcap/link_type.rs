@@ -0,0 +1,75 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// Link-layer header types of captured packets.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum LinkType {
+	/// BSD loopback encapsulation.
+	Null,
+
+	/// Ethernet.
+	Ethernet,
+
+	/// Raw IPv4 or IPv6.
+	Raw,
+
+	/// IEEE 802.11 wireless.
+	Ieee80211,
+
+	/// Linux cooked capture.
+	LinuxSll,
+
+	/// Raw IPv4.
+	Ipv4,
+
+	/// Raw IPv6.
+	Ipv6,
+
+	/// Unknown link type.
+	Unknown(u32),
+}
+
+impl From<u32> for LinkType {
+	fn from(value: u32) -> LinkType {
+		use self::LinkType::*;
+
+		match value {
+			0   => Null,
+			1   => Ethernet,
+			101 => Raw,
+			105 => Ieee80211,
+			113 => LinuxSll,
+			228 => Ipv4,
+			229 => Ipv6,
+			n   => Unknown(n),
+		}
+	}
+}
+
+impl From<LinkType> for u32 {
+	fn from(value: LinkType) -> u32 {
+		use self::LinkType::*;
+
+		match value {
+			Null       => 0,
+			Ethernet   => 1,
+			Raw        => 101,
+			Ieee80211  => 105,
+			LinuxSll   => 113,
+			Ipv4       => 228,
+			Ipv6       => 229,
+			Unknown(n) => n,
+		}
+	}
+}
@@ -0,0 +1,29 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+mod link_type;
+pub use self::link_type::LinkType;
+
+mod header;
+pub use self::header::{Header, Endianness, Resolution};
+
+mod record;
+pub use self::record::Record;
+
+mod reader;
+pub use self::reader::Reader;
+pub(crate) use self::reader::fill;
+
+mod writer;
+pub use self::writer::Writer;
@@ -0,0 +1,188 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::{self, Read};
+use std::time::Duration;
+use byteorder::{ByteOrder, BigEndian, LittleEndian};
+
+use crate::error::*;
+use crate::pcap::{Header, Endianness, Resolution, Record};
+
+/// Largest record accepted regardless of the snapshot length, to avoid huge
+/// allocations on corrupted files.
+const MAX_RECORD: u32 = 256 * 1024;
+
+/// Streaming capture file reader.
+#[derive(Debug)]
+pub struct Reader<R> {
+	inner:  R,
+	header: Header,
+}
+
+impl<R: Read> Reader<R> {
+	/// Create a reader, parsing the global header.
+	pub fn new(mut inner: R) -> Result<Self> {
+		let mut buffer = [0u8; 24];
+		inner.read_exact(&mut buffer)?;
+
+		let (endianness, resolution) = match BigEndian::read_u32(&buffer[0 ..]) {
+			Header::MAGIC_MICRO => (Endianness::Big, Resolution::Micro),
+			Header::MAGIC_NANO  => (Endianness::Big, Resolution::Nano),
+
+			_ => match LittleEndian::read_u32(&buffer[0 ..]) {
+				Header::MAGIC_MICRO => (Endianness::Little, Resolution::Micro),
+				Header::MAGIC_NANO  => (Endianness::Little, Resolution::Nano),
+
+				_ =>
+					Err(Error::InvalidPacket)?
+			}
+		};
+
+		let header = Header {
+			endianness,
+			resolution,
+			major:     endianness.read_u16(&buffer[4 ..]),
+			minor:     endianness.read_u16(&buffer[6 ..]),
+			snaplen:   endianness.read_u32(&buffer[16 ..]),
+			link_type: endianness.read_u32(&buffer[20 ..]).into(),
+		};
+
+		Ok(Reader { inner, header })
+	}
+
+	/// Global header of the file.
+	pub fn header(&self) -> &Header {
+		&self.header
+	}
+
+	/// Get back the inner reader.
+	pub fn into_inner(self) -> R {
+		self.inner
+	}
+
+	/// Read the next record, returns `None` at the end of the file.
+	pub fn read(&mut self) -> Result<Option<Record>> {
+		let mut buffer = [0u8; 16];
+
+		if !fill(&mut self.inner, &mut buffer)? {
+			return Ok(None);
+		}
+
+		let endianness = self.header.endianness;
+		let seconds    = endianness.read_u32(&buffer[0 ..]);
+		let fraction   = endianness.read_u32(&buffer[4 ..]);
+		let captured   = endianness.read_u32(&buffer[8 ..]);
+		let length     = endianness.read_u32(&buffer[12 ..]);
+
+		if captured > self.header.snaplen.max(MAX_RECORD) {
+			Err(Error::InvalidPacket)?
+		}
+
+		let nanos = match self.header.resolution {
+			Resolution::Micro => u64::from(fraction) * 1_000,
+			Resolution::Nano  => u64::from(fraction),
+		};
+
+		let mut data = vec![0u8; captured as usize];
+		self.inner.read_exact(&mut data)?;
+
+		Ok(Some(Record {
+			timestamp: Duration::from_secs(u64::from(seconds)) + Duration::from_nanos(nanos),
+			length,
+			data,
+		}))
+	}
+}
+
+impl<R: Read> Iterator for Reader<R> {
+	type Item = Result<Record>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.read().transpose()
+	}
+}
+
+/// Fill the buffer, returns `false` if the reader was already at its end.
+pub(crate) fn fill<R: Read>(inner: &mut R, buffer: &mut [u8]) -> io::Result<bool> {
+	let mut read = 0;
+
+	while read < buffer.len() {
+		match inner.read(&mut buffer[read ..]) {
+			Ok(0) if read == 0 =>
+				return Ok(false),
+
+			Ok(0) =>
+				return Err(io::ErrorKind::UnexpectedEof.into()),
+
+			Ok(n) =>
+				read += n,
+
+			Err(ref e) if e.kind() == io::ErrorKind::Interrupted =>
+				(),
+
+			Err(e) =>
+				return Err(e),
+		}
+	}
+
+	Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+	use crate::pcap;
+
+	#[test]
+	fn big_endian_nano() {
+		let raw = hex::decode(concat!(
+			"a1b23c4d00020004000000000000000000000040000000e4",
+			"5f5e10000000002a000000040000003c",
+			"45000000",
+		)).unwrap();
+
+		let mut reader = pcap::Reader::new(&raw[..]).unwrap();
+		assert_eq!(reader.header().endianness, pcap::Endianness::Big);
+		assert_eq!(reader.header().resolution, pcap::Resolution::Nano);
+		assert_eq!(reader.header().snaplen, 64);
+		assert_eq!(reader.header().link_type, pcap::LinkType::Ipv4);
+
+		let record = reader.next().unwrap().unwrap();
+		assert_eq!(record.timestamp, Duration::new(0x5f5e1000, 42));
+		assert_eq!(record.length, 60);
+		assert_eq!(record.data, [0x45, 0, 0, 0]);
+		assert!(record.is_truncated());
+
+		assert!(reader.next().is_none());
+	}
+
+	#[test]
+	fn invalid_magic() {
+		let raw = [0u8; 24];
+		assert!(pcap::Reader::new(&raw[..]).is_err());
+	}
+
+	#[test]
+	fn truncated_record() {
+		let raw = hex::decode(concat!(
+			"d4c3b2a1020004000000000000000000ffff000001000000",
+			"01000000020000000a0000000a000000",
+			"0102",
+		)).unwrap();
+
+		let mut reader = pcap::Reader::new(&raw[..]).unwrap();
+		assert_eq!(reader.header().endianness, pcap::Endianness::Little);
+		assert!(reader.next().unwrap().is_err());
+	}
+}
@@ -0,0 +1,44 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::time::Duration;
+
+/// A captured packet.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Record {
+	/// Time of capture since the UNIX epoch.
+	pub timestamp: Duration,
+
+	/// Length of the packet on the wire.
+	pub length: u32,
+
+	/// Captured data, possibly truncated to the snapshot length.
+	pub data: Vec<u8>,
+}
+
+impl Record {
+	/// Create a record for a packet captured in full.
+	pub fn new(timestamp: Duration, data: Vec<u8>) -> Self {
+		Record {
+			timestamp,
+			length: data.len() as u32,
+			data,
+		}
+	}
+
+	/// Check if the captured data is shorter than the packet on the wire.
+	pub fn is_truncated(&self) -> bool {
+		(self.data.len() as u64) < u64::from(self.length)
+	}
+}
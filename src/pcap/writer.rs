@@ -0,0 +1,156 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Write;
+
+use crate::error::*;
+use crate::pcap::{Header, Resolution, Record};
+
+/// Capture file writer.
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+	inner:  W,
+	header: Header,
+}
+
+impl<W: Write> Writer<W> {
+	/// Create a writer, writing out the global header.
+	pub fn new(mut inner: W, header: Header) -> Result<Self> {
+		let endianness = header.endianness;
+
+		endianness.write_u32(&mut inner, header.magic())?;
+		endianness.write_u16(&mut inner, header.major)?;
+		endianness.write_u16(&mut inner, header.minor)?;
+		endianness.write_u32(&mut inner, 0)?;
+		endianness.write_u32(&mut inner, 0)?;
+		endianness.write_u32(&mut inner, header.snaplen)?;
+		endianness.write_u32(&mut inner, header.link_type.into())?;
+
+		Ok(Writer { inner, header })
+	}
+
+	/// Global header of the file.
+	pub fn header(&self) -> &Header {
+		&self.header
+	}
+
+	/// Get back the inner writer.
+	pub fn into_inner(self) -> W {
+		self.inner
+	}
+
+	/// Flush the inner writer.
+	pub fn flush(&mut self) -> Result<()> {
+		self.inner.flush()?;
+		Ok(())
+	}
+
+	/// Write a record, truncating the data to the snapshot length.
+	pub fn write(&mut self, record: &Record) -> Result<()> {
+		let seconds = record.timestamp.as_secs();
+		if seconds > u64::from(u32::MAX) {
+			Err(Error::InvalidValue)?
+		}
+
+		let fraction = match self.header.resolution {
+			Resolution::Micro => record.timestamp.subsec_micros(),
+			Resolution::Nano  => record.timestamp.subsec_nanos(),
+		};
+
+		let captured = record.data.len().min(self.header.snaplen as usize);
+		let length   = record.length.max(record.data.len() as u32);
+
+		let endianness = self.header.endianness;
+		endianness.write_u32(&mut self.inner, seconds as u32)?;
+		endianness.write_u32(&mut self.inner, fraction)?;
+		endianness.write_u32(&mut self.inner, captured as u32)?;
+		endianness.write_u32(&mut self.inner, length)?;
+		self.inner.write_all(&record.data[.. captured])?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::pcap;
+
+	fn frame() -> Vec<u8> {
+		ether::Builder::default()
+			.destination("00:23:69:63:59:be".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.ip().unwrap().v4().unwrap()
+				.ttl(64).unwrap()
+				.source("66.102.1.108".parse().unwrap()).unwrap()
+				.destination("192.168.0.79".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1337).unwrap()
+					.destination(9001).unwrap()
+					.payload(b"test").unwrap()
+					.build().unwrap()
+	}
+
+	#[test]
+	fn roundtrip() {
+		for &endianness in &[pcap::Endianness::Big, pcap::Endianness::Little] {
+			for &resolution in &[pcap::Resolution::Micro, pcap::Resolution::Nano] {
+				let header = pcap::Header {
+					endianness,
+					resolution,
+					.. Default::default()
+				};
+
+				let timestamp = Duration::new(1_600_000_000, 123_456_000);
+				let mut writer = pcap::Writer::new(Vec::new(), header).unwrap();
+				writer.write(&pcap::Record::new(timestamp, frame())).unwrap();
+
+				let raw        = writer.into_inner();
+				let mut reader = pcap::Reader::new(&raw[..]).unwrap();
+				assert_eq!(*reader.header(), header);
+
+				let record = reader.next().unwrap().unwrap();
+				assert_eq!(record.timestamp, timestamp);
+				assert_eq!(record.length as usize, frame().len());
+				assert_eq!(record.data, frame());
+
+				let ether = ether::Packet::new(&record.data[..]).unwrap();
+				assert_eq!(ether.protocol(), ether::Protocol::Ipv4);
+				assert_eq!(ether.payload().len(), 32);
+
+				assert!(reader.next().is_none());
+			}
+		}
+	}
+
+	#[test]
+	fn snaplen() {
+		let header = pcap::Header {
+			snaplen: 14,
+			.. Default::default()
+		};
+
+		let mut writer = pcap::Writer::new(Vec::new(), header).unwrap();
+		writer.write(&pcap::Record::new(Duration::from_secs(1), frame())).unwrap();
+
+		let raw    = writer.into_inner();
+		let record = pcap::Reader::new(&raw[..]).unwrap().next().unwrap().unwrap();
+		assert_eq!(record.data.len(), 14);
+		assert_eq!(record.length as usize, frame().len());
+		assert!(record.is_truncated());
+	}
+}
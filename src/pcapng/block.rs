@@ -0,0 +1,288 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::time::Duration;
+
+use crate::error::*;
+use crate::pcap::{Endianness, LinkType};
+use crate::pcapng::Options;
+
+/// Section Header Block.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SectionHeader {
+	/// Byte order of the section.
+	pub endianness: Endianness,
+
+	/// Major version of the format.
+	pub major: u16,
+
+	/// Minor version of the format.
+	pub minor: u16,
+
+	/// Length of the section in octets, -1 if unspecified.
+	pub length: i64,
+
+	/// Options of the section.
+	pub options: Options,
+}
+
+/// Interface Description Block.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct InterfaceDescription {
+	/// Link-layer header type of the packets.
+	pub link_type: LinkType,
+
+	/// Maximum number of octets captured per packet, 0 if unlimited.
+	pub snaplen: u32,
+
+	/// Options of the interface.
+	pub options: Options,
+}
+
+/// Enhanced Packet Block.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct EnhancedPacket {
+	/// Interface the packet was captured on.
+	pub interface: u32,
+
+	/// Timestamp in units of the interface resolution.
+	pub timestamp: u64,
+
+	/// Length of the packet on the wire.
+	pub length: u32,
+
+	/// Captured data.
+	pub data: Vec<u8>,
+
+	/// Options of the packet.
+	pub options: Options,
+}
+
+/// Simple Packet Block.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SimplePacket {
+	/// Length of the packet on the wire.
+	pub length: u32,
+
+	/// Captured data.
+	pub data: Vec<u8>,
+}
+
+/// A pcapng block.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Block {
+	/// Section Header Block.
+	SectionHeader(SectionHeader),
+
+	/// Interface Description Block.
+	InterfaceDescription(InterfaceDescription),
+
+	/// Enhanced Packet Block.
+	EnhancedPacket(EnhancedPacket),
+
+	/// Simple Packet Block.
+	SimplePacket(SimplePacket),
+
+	/// Block of an unsupported type.
+	Unknown {
+		/// Block type.
+		kind: u32,
+
+		/// Raw block body.
+		body: Vec<u8>,
+	},
+}
+
+impl Block {
+	pub(in crate::pcapng) const SECTION_HEADER: u32        = 0x0a0d_0d0a;
+	pub(in crate::pcapng) const INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+	pub(in crate::pcapng) const SIMPLE_PACKET: u32         = 0x0000_0003;
+	pub(in crate::pcapng) const ENHANCED_PACKET: u32       = 0x0000_0006;
+	pub(in crate::pcapng) const BYTE_ORDER_MAGIC: u32      = 0x1a2b_3c4d;
+}
+
+impl Default for SectionHeader {
+	fn default() -> Self {
+		SectionHeader {
+			endianness: Endianness::Little,
+			major:      1,
+			minor:      0,
+			length:     -1,
+			options:    Options::default(),
+		}
+	}
+}
+
+impl SectionHeader {
+	pub(in crate::pcapng) fn parse(endianness: Endianness, body: &[u8]) -> Result<Self> {
+		if body.len() < 12 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let high = u64::from(endianness.read_u32(&body[8 ..]));
+		let low  = u64::from(endianness.read_u32(&body[4 ..]));
+		let (high, low) = match endianness {
+			Endianness::Big    => (low, high),
+			Endianness::Little => (high, low),
+		};
+
+		Ok(SectionHeader {
+			endianness,
+			major:   endianness.read_u16(&body[0 ..]),
+			minor:   endianness.read_u16(&body[2 ..]),
+			length:  ((high << 32) | low) as i64,
+			options: Options::parse(endianness, &body[12 ..])?,
+		})
+	}
+}
+
+impl InterfaceDescription {
+	/// Interface name option.
+	pub const NAME: u16 = 2;
+
+	/// Interface description option.
+	pub const DESCRIPTION: u16 = 3;
+
+	/// Timestamp resolution option.
+	pub const TSRESOL: u16 = 9;
+
+	/// Create an interface with microsecond timestamps.
+	pub fn new(link_type: LinkType, snaplen: u32) -> Self {
+		InterfaceDescription {
+			link_type,
+			snaplen,
+			options: Options::default(),
+		}
+	}
+
+	/// Name of the interface.
+	pub fn name(&self) -> Option<&str> {
+		self.options.get(Self::NAME).and_then(|value| std::str::from_utf8(value).ok())
+	}
+
+	/// Description of the interface.
+	pub fn description(&self) -> Option<&str> {
+		self.options.get(Self::DESCRIPTION).and_then(|value| std::str::from_utf8(value).ok())
+	}
+
+	/// Timestamp resolution, the most significant bit picks a power of 2
+	/// instead of a power of 10 for the remaining exponent.
+	pub fn resolution(&self) -> u8 {
+		match self.options.get(Self::TSRESOL) {
+			Some(value) if !value.is_empty() =>
+				value[0],
+
+			_ =>
+				6
+		}
+	}
+
+	/// Convert a timestamp in units of the interface resolution.
+	pub fn timestamp(&self, value: u64) -> Result<Duration> {
+		let units = self.units()?;
+		let value = u128::from(value);
+
+		Ok(Duration::new(
+			(value / units) as u64,
+			((value % units) * 1_000_000_000 / units) as u32))
+	}
+
+	/// Convert a timestamp to units of the interface resolution.
+	pub fn ticks(&self, value: Duration) -> Result<u64> {
+		let units = self.units()?;
+		let ticks = u128::from(value.as_secs()).checked_mul(units)
+			.zip(u128::from(value.subsec_nanos()).checked_mul(units))
+			.and_then(|(seconds, nanos)| seconds.checked_add(nanos / 1_000_000_000))
+			.ok_or(Error::InvalidValue)?;
+
+		if ticks > u128::from(u64::MAX) {
+			Err(Error::InvalidValue)?
+		}
+
+		Ok(ticks as u64)
+	}
+
+	fn units(&self) -> Result<u128> {
+		let resolution = self.resolution();
+		let units      = if resolution & 0x80 != 0 {
+			1u128.checked_shl(u32::from(resolution & 0x7f))
+		}
+		else {
+			10u128.checked_pow(u32::from(resolution))
+		};
+
+		units.ok_or(Error::InvalidPacket)
+	}
+
+	pub(in crate::pcapng) fn parse(endianness: Endianness, body: &[u8]) -> Result<Self> {
+		if body.len() < 8 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let interface = InterfaceDescription {
+			link_type: u32::from(endianness.read_u16(&body[0 ..])).into(),
+			snaplen:   endianness.read_u32(&body[4 ..]),
+			options:   Options::parse(endianness, &body[8 ..])?,
+		};
+
+		interface.units()?;
+		Ok(interface)
+	}
+}
+
+impl EnhancedPacket {
+	pub(in crate::pcapng) fn parse(endianness: Endianness, body: &[u8]) -> Result<Self> {
+		if body.len() < 20 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let captured = endianness.read_u32(&body[12 ..]) as usize;
+		let padded   = (captured + 3) & !3;
+
+		if body.len() < 20 + padded {
+			Err(Error::InvalidPacket)?
+		}
+
+		let high = u64::from(endianness.read_u32(&body[4 ..]));
+		let low  = u64::from(endianness.read_u32(&body[8 ..]));
+
+		Ok(EnhancedPacket {
+			interface: endianness.read_u32(&body[0 ..]),
+			timestamp: (high << 32) | low,
+			length:    endianness.read_u32(&body[16 ..]),
+			data:      body[20 .. 20 + captured].to_vec(),
+			options:   Options::parse(endianness, &body[20 + padded ..])?,
+		})
+	}
+}
+
+impl SimplePacket {
+	pub(in crate::pcapng) fn parse(endianness: Endianness, body: &[u8], snaplen: u32) -> Result<Self> {
+		if body.len() < 4 {
+			Err(Error::InvalidPacket)?
+		}
+
+		let length   = endianness.read_u32(&body[0 ..]);
+		let captured = if snaplen == 0 { length } else { length.min(snaplen) } as usize;
+
+		if body.len() < 4 + captured {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(SimplePacket {
+			length,
+			data: body[4 .. 4 + captured].to_vec(),
+		})
+	}
+}
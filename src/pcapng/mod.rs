@@ -0,0 +1,30 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+mod options;
+pub use self::options::Options;
+
+mod block;
+pub use self::block::{Block, SectionHeader, InterfaceDescription, EnhancedPacket, SimplePacket};
+
+mod record;
+pub use self::record::Record;
+
+mod reader;
+pub use self::reader::Reader;
+
+mod writer;
+pub use self::writer::Writer;
+
+pub use crate::pcap::{Endianness, LinkType};
@@ -0,0 +1,113 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Write;
+
+use crate::error::*;
+use crate::pcap::Endianness;
+
+/// Options attached to a block.
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct Options(Vec<(u16, Vec<u8>)>);
+
+impl Options {
+	/// End of options marker.
+	pub const END: u16 = 0;
+
+	/// UTF-8 comment.
+	pub const COMMENT: u16 = 1;
+
+	/// Create an empty set of options.
+	pub fn new() -> Self {
+		Options::default()
+	}
+
+	/// Add an option.
+	pub fn add(&mut self, code: u16, value: &[u8]) -> Result<&mut Self> {
+		if code == Self::END || value.len() > u16::MAX as usize {
+			Err(Error::InvalidValue)?
+		}
+
+		self.0.push((code, value.to_vec()));
+		Ok(self)
+	}
+
+	/// Add a comment.
+	pub fn comment(&mut self, value: &str) -> Result<&mut Self> {
+		self.add(Self::COMMENT, value.as_bytes())
+	}
+
+	/// First value of the given option.
+	pub fn get(&self, code: u16) -> Option<&[u8]> {
+		self.iter().find(|&(c, _)| c == code).map(|(_, value)| value)
+	}
+
+	/// Iterator over all the options.
+	pub fn iter(&self) -> impl Iterator<Item = (u16, &[u8])> {
+		self.0.iter().map(|(code, value)| (*code, &value[..]))
+	}
+
+	/// Iterator over the comments, skipping invalid UTF-8.
+	pub fn comments(&self) -> impl Iterator<Item = &str> {
+		self.iter()
+			.filter(|&(code, _)| code == Self::COMMENT)
+			.filter_map(|(_, value)| std::str::from_utf8(value).ok())
+	}
+
+	/// Check if there are no options.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub(in crate::pcapng) fn parse(endianness: Endianness, mut buffer: &[u8]) -> Result<Self> {
+		let mut options = Options::default();
+
+		while buffer.len() >= 4 {
+			let code   = endianness.read_u16(&buffer[0 ..]);
+			let length = endianness.read_u16(&buffer[2 ..]) as usize;
+
+			if code == Self::END {
+				break;
+			}
+
+			let padded = (length + 3) & !3;
+			if buffer.len() < 4 + padded {
+				Err(Error::InvalidPacket)?
+			}
+
+			options.0.push((code, buffer[4 .. 4 + length].to_vec()));
+			buffer = &buffer[4 + padded ..];
+		}
+
+		Ok(options)
+	}
+
+	pub(in crate::pcapng) fn write<W: Write>(&self, endianness: Endianness, inner: &mut W) -> Result<()> {
+		if self.0.is_empty() {
+			return Ok(());
+		}
+
+		for (code, value) in &self.0 {
+			endianness.write_u16(inner, *code)?;
+			endianness.write_u16(inner, value.len() as u16)?;
+			inner.write_all(value)?;
+			inner.write_all(&[0u8; 3][.. ((value.len() + 3) & !3) - value.len()])?;
+		}
+
+		endianness.write_u16(inner, Self::END)?;
+		endianness.write_u16(inner, 0)?;
+
+		Ok(())
+	}
+}
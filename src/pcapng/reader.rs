@@ -0,0 +1,259 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Read;
+
+use crate::error::*;
+use crate::pcap::{self, Endianness};
+use crate::pcapng::{Block, SectionHeader, InterfaceDescription, EnhancedPacket, SimplePacket, Record};
+
+/// Largest block accepted, to avoid huge allocations on corrupted files.
+const MAX_BLOCK: u32 = 16 * 1024 * 1024;
+
+/// Streaming pcapng reader.
+#[derive(Debug)]
+pub struct Reader<R> {
+	inner:      R,
+	section:    SectionHeader,
+	interfaces: Vec<InterfaceDescription>,
+}
+
+impl<R: Read> Reader<R> {
+	/// Create a reader, parsing the first Section Header Block.
+	pub fn new(mut inner: R) -> Result<Self> {
+		let mut kind = [0u8; 4];
+		inner.read_exact(&mut kind)?;
+
+		if Endianness::Big.read_u32(&kind) != Block::SECTION_HEADER {
+			Err(Error::InvalidPacket)?
+		}
+
+		let section = Self::read_section(&mut inner)?;
+
+		Ok(Reader {
+			inner,
+			section,
+			interfaces: Vec::new(),
+		})
+	}
+
+	/// Header of the current section.
+	pub fn section(&self) -> &SectionHeader {
+		&self.section
+	}
+
+	/// Interfaces described so far in the current section.
+	pub fn interfaces(&self) -> &[InterfaceDescription] {
+		&self.interfaces
+	}
+
+	/// Get back the inner reader.
+	pub fn into_inner(self) -> R {
+		self.inner
+	}
+
+	/// Read the next block, returns `None` at the end of the file.
+	///
+	/// Section headers and interface descriptions are tracked, so later
+	/// packets can be matched to their interface.
+	pub fn read(&mut self) -> Result<Option<Block>> {
+		let mut kind = [0u8; 4];
+
+		if !pcap::fill(&mut self.inner, &mut kind)? {
+			return Ok(None);
+		}
+
+		let endianness = self.section.endianness;
+		let kind       = endianness.read_u32(&kind);
+
+		if kind == Block::SECTION_HEADER {
+			self.section    = Self::read_section(&mut self.inner)?;
+			self.interfaces = Vec::new();
+
+			return Ok(Some(Block::SectionHeader(self.section.clone())));
+		}
+
+		let mut length = [0u8; 4];
+		self.inner.read_exact(&mut length)?;
+		let body = Self::body(&mut self.inner, endianness, endianness.read_u32(&length), 8)?;
+
+		Ok(Some(match kind {
+			Block::INTERFACE_DESCRIPTION => {
+				let interface = InterfaceDescription::parse(endianness, &body)?;
+				self.interfaces.push(interface.clone());
+
+				Block::InterfaceDescription(interface)
+			}
+
+			Block::ENHANCED_PACKET =>
+				Block::EnhancedPacket(EnhancedPacket::parse(endianness, &body)?),
+
+			Block::SIMPLE_PACKET => {
+				let snaplen = self.interfaces.first()
+					.ok_or(Error::InvalidPacket)?
+					.snaplen;
+
+				Block::SimplePacket(SimplePacket::parse(endianness, &body, snaplen)?)
+			}
+
+			kind =>
+				Block::Unknown { kind, body }
+		}))
+	}
+
+	/// Read the next packet, skipping over any other block.
+	pub fn packet(&mut self) -> Result<Option<Record>> {
+		loop {
+			match self.read()? {
+				Some(Block::EnhancedPacket(packet)) => {
+					let interface = self.interfaces.get(packet.interface as usize)
+						.ok_or(Error::InvalidPacket)?;
+
+					return Ok(Some(Record::new(packet.interface, interface,
+						packet.timestamp, packet.length, packet.data, packet.options)?));
+				}
+
+				Some(Block::SimplePacket(packet)) => {
+					let interface = self.interfaces.first()
+						.ok_or(Error::InvalidPacket)?;
+
+					return Ok(Some(Record {
+						interface: 0,
+						link_type: interface.link_type,
+						timestamp: Default::default(),
+						length:    packet.length,
+						data:      packet.data,
+						options:   Default::default(),
+					}));
+				}
+
+				Some(_) =>
+					continue,
+
+				None =>
+					return Ok(None),
+			}
+		}
+	}
+
+	fn read_section(inner: &mut R) -> Result<SectionHeader> {
+		let mut buffer = [0u8; 8];
+		inner.read_exact(&mut buffer)?;
+
+		let endianness = match Endianness::Big.read_u32(&buffer[4 ..]) {
+			Block::BYTE_ORDER_MAGIC =>
+				Endianness::Big,
+
+			_ if Endianness::Little.read_u32(&buffer[4 ..]) == Block::BYTE_ORDER_MAGIC =>
+				Endianness::Little,
+
+			_ =>
+				Err(Error::InvalidPacket)?
+		};
+
+		let length = endianness.read_u32(&buffer[0 ..]);
+		let body   = Self::body(inner, endianness, length, 12)?;
+
+		SectionHeader::parse(endianness, &body)
+	}
+
+	/// Read the rest of a block given its total length and the octets already
+	/// consumed, checking the trailing length matches.
+	fn body(inner: &mut R, endianness: Endianness, length: u32, consumed: usize) -> Result<Vec<u8>> {
+		if (length as usize) < consumed + 4 || length & 0b11 != 0 || length > MAX_BLOCK {
+			Err(Error::InvalidPacket)?
+		}
+
+		let mut body = vec![0u8; length as usize - consumed - 4];
+		inner.read_exact(&mut body)?;
+
+		let mut trailer = [0u8; 4];
+		inner.read_exact(&mut trailer)?;
+
+		if endianness.read_u32(&trailer) != length {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(body)
+	}
+}
+
+impl<R: Read> Iterator for Reader<R> {
+	type Item = Result<Record>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.packet().transpose()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+	use crate::pcap;
+	use crate::pcapng;
+
+	#[test]
+	fn blocks() {
+		let raw = hex::decode(concat!(
+			// Section Header Block, little endian.
+			"0a0d0d0a1c0000004d3c2b1a01000000ffffffffffffffff1c000000",
+			// Interface Description Block, Ethernet, nanosecond resolution.
+			"0100000020000000010000000000000009000100090000000000000020000000",
+			// Custom block.
+			"ad0b00000c0000000c000000",
+			// Enhanced Packet Block.
+			"060000002400000000000000000000000e0000000400000004000000deadbeef24000000",
+		)).unwrap();
+
+		let mut reader = pcapng::Reader::new(&raw[..]).unwrap();
+		assert_eq!(reader.section().endianness, pcap::Endianness::Little);
+		assert_eq!(reader.section().length, -1);
+
+		match reader.read().unwrap().unwrap() {
+			pcapng::Block::InterfaceDescription(interface) => {
+				assert_eq!(interface.link_type, pcap::LinkType::Ethernet);
+				assert_eq!(interface.resolution(), 9);
+			}
+
+			block => panic!("unexpected block {:?}", block),
+		}
+
+		let record = reader.next().unwrap().unwrap();
+		assert_eq!(record.timestamp, Duration::new(0, 14));
+		assert_eq!(record.length, 4);
+		assert_eq!(record.data, [0xde, 0xad, 0xbe, 0xef]);
+
+		assert!(reader.next().is_none());
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(pcapng::Reader::new(&[0u8; 28][..]).is_err());
+
+		let raw = hex::decode("0a0d0d0a1c0000004d3c2b1a01000000ffffffffffffffff20000000").unwrap();
+		assert!(pcapng::Reader::new(&raw[..]).is_err());
+	}
+
+	#[test]
+	fn resolution() {
+		let raw = hex::decode(concat!(
+			"0a0d0d0a1c0000004d3c2b1a01000000ffffffffffffffff1c000000",
+			// Interface Description Block, 10^-39 resolution.
+			"0100000020000000010000000000000009000100270000000000000020000000",
+		)).unwrap();
+
+		let mut reader = pcapng::Reader::new(&raw[..]).unwrap();
+		assert!(reader.read().is_err());
+	}
+}
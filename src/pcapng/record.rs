@@ -0,0 +1,69 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::time::Duration;
+
+use crate::error::*;
+use crate::ether;
+use crate::pcap::LinkType;
+use crate::pcapng::{Options, InterfaceDescription};
+
+/// A captured packet with its interface metadata.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Record {
+	/// Index of the interface within the section.
+	pub interface: u32,
+
+	/// Link-layer header type of the interface.
+	pub link_type: LinkType,
+
+	/// Time of capture since the UNIX epoch.
+	pub timestamp: Duration,
+
+	/// Length of the packet on the wire.
+	pub length: u32,
+
+	/// Captured data.
+	pub data: Vec<u8>,
+
+	/// Options of the packet.
+	pub options: Options,
+}
+
+impl Record {
+	pub(in crate::pcapng) fn new(interface: u32, description: &InterfaceDescription, timestamp: u64, length: u32, data: Vec<u8>, options: Options) -> Result<Self> {
+		Ok(Record {
+			interface,
+			link_type: description.link_type,
+			timestamp: description.timestamp(timestamp)?,
+			length,
+			data,
+			options,
+		})
+	}
+
+	/// Check if the captured data is shorter than the packet on the wire.
+	pub fn is_truncated(&self) -> bool {
+		(self.data.len() as u64) < u64::from(self.length)
+	}
+
+	/// Parse the captured data as an Ethernet frame.
+	pub fn ether(&self) -> Result<ether::Packet<&[u8]>> {
+		if self.link_type != LinkType::Ethernet {
+			Err(Error::InvalidPacket)?
+		}
+
+		ether::Packet::new(&self.data[..])
+	}
+}
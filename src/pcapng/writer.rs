@@ -0,0 +1,286 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::io::Write;
+use std::time::Duration;
+
+use crate::error::*;
+use crate::pcap::Endianness;
+use crate::pcapng::{Block, SectionHeader, InterfaceDescription, EnhancedPacket, Options};
+
+/// pcapng writer.
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+	inner:      W,
+	section:    SectionHeader,
+	interfaces: Vec<InterfaceDescription>,
+}
+
+impl<W: Write> Writer<W> {
+	/// Create a writer, writing out the Section Header Block.
+	pub fn new(inner: W, section: SectionHeader) -> Result<Self> {
+		let mut writer = Writer {
+			inner,
+			section:    section.clone(),
+			interfaces: Vec::new(),
+		};
+
+		writer.write(&Block::SectionHeader(section))?;
+		Ok(writer)
+	}
+
+	/// Header of the current section.
+	pub fn section(&self) -> &SectionHeader {
+		&self.section
+	}
+
+	/// Interfaces described so far in the current section.
+	pub fn interfaces(&self) -> &[InterfaceDescription] {
+		&self.interfaces
+	}
+
+	/// Get back the inner writer.
+	pub fn into_inner(self) -> W {
+		self.inner
+	}
+
+	/// Flush the inner writer.
+	pub fn flush(&mut self) -> Result<()> {
+		self.inner.flush()?;
+		Ok(())
+	}
+
+	/// Describe a new interface, returning its index.
+	pub fn interface(&mut self, interface: InterfaceDescription) -> Result<u32> {
+		self.write(&Block::InterfaceDescription(interface))?;
+		Ok(self.interfaces.len() as u32 - 1)
+	}
+
+	/// Write a packet captured on the given interface, truncating the data to
+	/// the interface snapshot length.
+	pub fn packet(&mut self, interface: u32, timestamp: Duration, data: &[u8], options: Options) -> Result<()> {
+		let description = self.interfaces.get(interface as usize)
+			.ok_or(Error::InvalidValue)?;
+
+		let captured = match description.snaplen {
+			0       => data.len(),
+			snaplen => data.len().min(snaplen as usize),
+		};
+
+		let packet = EnhancedPacket {
+			interface,
+			timestamp: description.ticks(timestamp)?,
+			length:    data.len() as u32,
+			data:      data[.. captured].to_vec(),
+			options,
+		};
+
+		self.write(&Block::EnhancedPacket(packet))
+	}
+
+	/// Write a raw block.
+	///
+	/// Section headers and interface descriptions are tracked, so later
+	/// packets can be matched to their interface.
+	pub fn write(&mut self, block: &Block) -> Result<()> {
+		let endianness = match *block {
+			Block::SectionHeader(ref section) => section.endianness,
+			_                                 => self.section.endianness,
+		};
+
+		let mut body = Vec::new();
+
+		let kind = match *block {
+			Block::SectionHeader(ref section) => {
+				let length = section.length as u64;
+				let (first, second) = match endianness {
+					Endianness::Big    => (length >> 32, length),
+					Endianness::Little => (length, length >> 32),
+				};
+
+				endianness.write_u32(&mut body, Block::BYTE_ORDER_MAGIC)?;
+				endianness.write_u16(&mut body, section.major)?;
+				endianness.write_u16(&mut body, section.minor)?;
+				endianness.write_u32(&mut body, first as u32)?;
+				endianness.write_u32(&mut body, second as u32)?;
+				section.options.write(endianness, &mut body)?;
+
+				self.section    = section.clone();
+				self.interfaces = Vec::new();
+
+				Block::SECTION_HEADER
+			}
+
+			Block::InterfaceDescription(ref interface) => {
+				let link_type: u32 = interface.link_type.into();
+				if link_type > u32::from(u16::MAX) {
+					Err(Error::InvalidValue)?
+				}
+
+				endianness.write_u16(&mut body, link_type as u16)?;
+				endianness.write_u16(&mut body, 0)?;
+				endianness.write_u32(&mut body, interface.snaplen)?;
+				interface.options.write(endianness, &mut body)?;
+
+				self.interfaces.push(interface.clone());
+
+				Block::INTERFACE_DESCRIPTION
+			}
+
+			Block::EnhancedPacket(ref packet) => {
+				endianness.write_u32(&mut body, packet.interface)?;
+				endianness.write_u32(&mut body, (packet.timestamp >> 32) as u32)?;
+				endianness.write_u32(&mut body, packet.timestamp as u32)?;
+				endianness.write_u32(&mut body, packet.data.len() as u32)?;
+				endianness.write_u32(&mut body, packet.length)?;
+				body.extend_from_slice(&packet.data);
+				pad(&mut body);
+				packet.options.write(endianness, &mut body)?;
+
+				Block::ENHANCED_PACKET
+			}
+
+			Block::SimplePacket(ref packet) => {
+				endianness.write_u32(&mut body, packet.length)?;
+				body.extend_from_slice(&packet.data);
+				pad(&mut body);
+
+				Block::SIMPLE_PACKET
+			}
+
+			Block::Unknown { kind, body: ref raw } => {
+				body.extend_from_slice(raw);
+				pad(&mut body);
+
+				kind
+			}
+		};
+
+		let length = body.len() as u32 + 12;
+		endianness.write_u32(&mut self.inner, kind)?;
+		endianness.write_u32(&mut self.inner, length)?;
+		self.inner.write_all(&body)?;
+		endianness.write_u32(&mut self.inner, length)?;
+
+		Ok(())
+	}
+}
+
+fn pad(body: &mut Vec<u8>) {
+	let padded = (body.len() + 3) & !3;
+	body.resize(padded, 0);
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+	use crate::builder::Builder;
+	use crate::packet::Packet;
+	use crate::ether;
+	use crate::ip;
+	use crate::pcap;
+	use crate::pcapng;
+
+	fn frame() -> Vec<u8> {
+		ether::Builder::default()
+			.destination("00:23:69:63:59:be".parse().unwrap()).unwrap()
+			.source("e4:b3:18:26:63:a3".parse().unwrap()).unwrap()
+			.ip().unwrap().v4().unwrap()
+				.ttl(64).unwrap()
+				.source("66.102.1.108".parse().unwrap()).unwrap()
+				.destination("192.168.0.79".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1337).unwrap()
+					.destination(9001).unwrap()
+					.payload(b"test!").unwrap()
+					.build().unwrap()
+	}
+
+	#[test]
+	fn roundtrip() {
+		for &endianness in &[pcap::Endianness::Big, pcap::Endianness::Little] {
+			let mut options = pcapng::Options::new();
+			options.comment("capture").unwrap();
+
+			let section = pcapng::SectionHeader {
+				endianness,
+				options,
+				.. Default::default()
+			};
+
+			let mut writer = pcapng::Writer::new(Vec::new(), section).unwrap();
+
+			let mut eth0 = pcapng::InterfaceDescription::new(pcap::LinkType::Ethernet, 0);
+			eth0.options.add(pcapng::InterfaceDescription::NAME, b"eth0").unwrap();
+			let eth0 = writer.interface(eth0).unwrap();
+
+			let mut raw = pcapng::InterfaceDescription::new(pcap::LinkType::Raw, 20);
+			raw.options.add(pcapng::InterfaceDescription::TSRESOL, &[9]).unwrap();
+			let raw = writer.interface(raw).unwrap();
+
+			let mut options = pcapng::Options::new();
+			options.comment("first").unwrap();
+
+			let frame = frame();
+			writer.packet(eth0, Duration::new(1_600_000_000, 123_456_000), &frame, options).unwrap();
+			writer.packet(raw, Duration::new(1_600_000_001, 123_456_789), &frame[14 ..], Default::default()).unwrap();
+
+			let data       = writer.into_inner();
+			let mut reader = pcapng::Reader::new(&data[..]).unwrap();
+			assert_eq!(reader.section().endianness, endianness);
+			assert_eq!(reader.section().options.comments().collect::<Vec<_>>(), ["capture"]);
+
+			let first = reader.next().unwrap().unwrap();
+			assert_eq!(first.interface, 0);
+			assert_eq!(first.link_type, pcap::LinkType::Ethernet);
+			assert_eq!(first.timestamp, Duration::new(1_600_000_000, 123_456_000));
+			assert_eq!(first.data, frame);
+			assert_eq!(first.options.comments().collect::<Vec<_>>(), ["first"]);
+			assert_eq!(reader.interfaces()[0].name(), Some("eth0"));
+
+			let ether = first.ether().unwrap();
+			assert_eq!(ether.protocol(), ether::Protocol::Ipv4);
+			assert!(ip::v4::Packet::new(ether.payload()).unwrap().is_valid());
+
+			let second = reader.next().unwrap().unwrap();
+			assert_eq!(second.interface, 1);
+			assert_eq!(second.link_type, pcap::LinkType::Raw);
+			assert_eq!(second.timestamp, Duration::new(1_600_000_001, 123_456_789));
+			assert_eq!(second.data.len(), 20);
+			assert_eq!(second.length as usize, frame.len() - 14);
+			assert!(second.is_truncated());
+			assert!(second.ether().is_err());
+
+			assert!(reader.next().is_none());
+		}
+	}
+
+	#[test]
+	fn resolution_overflow() {
+		let mut writer = pcapng::Writer::new(Vec::new(), Default::default()).unwrap();
+
+		let mut interface = pcapng::InterfaceDescription::new(pcap::LinkType::Raw, 0);
+		interface.options.add(pcapng::InterfaceDescription::TSRESOL, &[38]).unwrap();
+		let interface = writer.interface(interface).unwrap();
+
+		assert!(writer.packet(interface, Duration::new(0, 1), &[], Default::default()).is_err());
+		assert!(writer.packet(interface, Duration::new(1_600_000_000, 0), &[], Default::default()).is_err());
+	}
+
+	#[test]
+	fn unknown_interface() {
+		let mut writer = pcapng::Writer::new(Vec::new(), Default::default()).unwrap();
+		assert!(writer.packet(0, Duration::from_secs(1), &frame(), Default::default()).is_err());
+	}
+}
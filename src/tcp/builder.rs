@@ -22,6 +22,7 @@ use crate::packet::{AsPacket, AsPacketMut};
 use crate::ip;
 use crate::tcp::Packet;
 use crate::tcp::Flags;
+use crate::tcp::option::Number;
 use crate::tcp::checksum;
 
 /// TCP packet builder.
@@ -33,6 +34,7 @@ pub struct Builder<B: Buffer = buffer::Dynamic> {
 	ip:      (usize, usize),
 	options: bool,
 	payload: bool,
+	options_length: usize,
 	payload_length: usize,
 }
 
@@ -56,6 +58,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
 			ip:      ip,
 			options: false,
 			payload: false,
+			options_length: 0,
 			payload_length: 0,
 		})
 	}
@@ -65,6 +68,7 @@ impl<B: Buffer> Build<B> for Builder<B> {
 	}
 
 	fn build(mut self) -> Result<B::Inner> {
+		self.pad()?;
 		self.prepare();
 
 		let mut buffer = self.buffer.into_inner();
@@ -134,27 +138,113 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
+	/// Raw options for the packet.
 	pub fn options<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
 		if self.options {
 			Err(Error::AlreadyDefined)?
 		}
 
 		self.options = true;
-		
+		self.option(&value.into_iter().cloned().collect::<Vec<u8>>())
+	}
+
+	/// No-Operation option, useful to align the following option.
+	pub fn nop(self) -> Result<Self> {
+		self.option(&[Number::NoOperation.into()])
+	}
+
+	/// Maximum Segment Size option.
+	pub fn mss(self, value: u16) -> Result<Self> {
+		let value = value.to_be_bytes();
+		self.option(&[Number::MaximumSegmentSize.into(), 4, value[0], value[1]])
+	}
+
+	/// Window Scale option, the shift count can be at most 14.
+	pub fn window_scale(self, value: u8) -> Result<Self> {
+		if value > 14 {
+			Err(Error::InvalidValue)?
+		}
+
+		self.option(&[Number::WindowScale.into(), 3, value])
+	}
+
+	/// SACK Permitted option.
+	pub fn sack_permitted(self) -> Result<Self> {
+		self.option(&[Number::SelectiveAcknowledgmentPermitted.into(), 2])
+	}
+
+	/// SACK option with the given left and right edges, at most 4 blocks fit.
+	pub fn sack(self, blocks: &[(u32, u32)]) -> Result<Self> {
+		if blocks.is_empty() || blocks.len() > 4 {
+			Err(Error::InvalidValue)?
+		}
+
+		let mut option = vec![Number::SelectiveAcknowledgment.into(), (2 + blocks.len() * 8) as u8];
+		for &(left, right) in blocks {
+			option.extend_from_slice(&left.to_be_bytes());
+			option.extend_from_slice(&right.to_be_bytes());
+		}
+
+		self.option(&option)
+	}
+
+	/// Timestamp option with the TSval and TSecr.
+	pub fn timestamp(self, value: u32, echo: u32) -> Result<Self> {
+		let mut option = vec![Number::Timestamp.into(), 10];
+		option.extend_from_slice(&value.to_be_bytes());
+		option.extend_from_slice(&echo.to_be_bytes());
+
+		self.option(&option)
+	}
+
+	/// Option of any kind with the given payload.
+	pub fn unknown(self, kind: u8, payload: &[u8]) -> Result<Self> {
+		if payload.len() > 38 {
+			Err(Error::InvalidValue)?
+		}
+
+		let mut option = vec![kind, (payload.len() + 2) as u8];
+		option.extend_from_slice(payload);
+
+		self.option(&option)
+	}
+
+	fn option(mut self, value: &[u8]) -> Result<Self> {
+		if self.payload {
+			Err(Error::AlreadyDefined)?
+		}
+
+		if self.options_length + value.len() > 40 {
+			Err(Error::InvalidValue)?
+		}
+
 		for byte in value {
 			self.buffer.more(1)?;
-			// self.payload_length += 1;			
 			*self.buffer.data_mut().last_mut().unwrap() = *byte;
 		}
 
+		self.options_length += value.len();
 		Ok(self)
 	}
+
+	/// Pad the options to a 4 octets boundary with End of Option List.
+	fn pad(&mut self) -> Result<()> {
+		while self.options_length & 0b11 != 0 {
+			self.buffer.more(1)?;
+			*self.buffer.data_mut().last_mut().unwrap() = Number::End.into();
+			self.options_length += 1;
+		}
+
+		Ok(())
+	}
+
 	/// Payload for the packet.
 	pub fn payload<'a, T: IntoIterator<Item = &'a u8>>(mut self, value: T) -> Result<Self> {
 		if self.payload {
 			Err(Error::AlreadyDefined)?
 		}
 
+		self.pad()?;
 		self.payload = true;
 
 		for byte in value {
@@ -231,4 +321,68 @@ mod test {
 		assert_eq!(tcp.flags(), tcp::flag::SYN);
 		assert!(tcp.is_valid(&ip::Packet::from(&ip)));
 	}
+
+	#[test]
+	fn options() {
+		let packet = ip::v4::Builder::default()
+			.source("66.102.1.108".parse().unwrap()).unwrap()
+			.destination("192.168.0.79".parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(1337).unwrap()
+				.destination(9001).unwrap()
+				.flags(tcp::flag::SYN).unwrap()
+				.mss(1460).unwrap()
+				.sack_permitted().unwrap()
+				.timestamp(0x592b2997, 0).unwrap()
+				.nop().unwrap()
+				.window_scale(7).unwrap()
+				.payload(b"lol").unwrap()
+				.build().unwrap();
+
+		let ip  = ip::v4::Packet::new(packet).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(tcp.offset(), 10);
+		assert_eq!(tcp.payload(), b"lol");
+		assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+
+		let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 5);
+		assert_eq!(options[0].maximum_segment_size().unwrap(), 1460);
+		assert_eq!(options[2].timestamp().unwrap(), (0x592b2997, 0));
+		assert_eq!(options[4].window_scale().unwrap(), 7);
+	}
+
+	#[test]
+	fn padding() {
+		let packet = ip::v4::Builder::default()
+			.tcp().unwrap()
+				.mss(536).unwrap()
+				.window_scale(2).unwrap()
+				.sack(&[(1000, 2000)]).unwrap()
+				.build().unwrap();
+
+		let ip  = ip::v4::Packet::new(packet).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(tcp.offset(), 10);
+		assert_eq!(&tcp.raw_options()[17 ..], &[0, 0, 0]);
+
+		let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(options.len(), 3);
+		assert_eq!(options[2].selective_acknowledgment().unwrap().collect::<Vec<_>>(), vec![(1000, 2000)]);
+	}
+
+	#[test]
+	fn limits() {
+		assert!(ip::v4::Builder::default().tcp().unwrap()
+			.sack(&[(0, 1), (1, 2), (2, 3), (3, 4)]).unwrap()
+			.nop().unwrap()
+			.timestamp(0, 0).is_err());
+
+		assert!(ip::v4::Builder::default().tcp().unwrap()
+			.window_scale(15).is_err());
+
+		assert!(ip::v4::Builder::default().tcp().unwrap()
+			.payload(b"lol").unwrap()
+			.nop().is_err());
+	}
 }
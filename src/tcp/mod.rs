@@ -26,6 +26,10 @@ pub use self::packet::Packet;
 mod builder;
 pub use self::builder::Builder;
 
+/// TCP stream reassembly.
+pub mod stream;
+pub use self::stream::Reassembler;
+
 use crate::ip;
 use crate::ip::Protocol;
 
@@ -50,8 +54,13 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 				.write_u16::<BigEndian>(buffer.len() as u16).unwrap();
 		}
 
-		ip::Packet::V6(ref _packet) => {
-			unimplemented!();
+		ip::Packet::V6(ref packet) => {
+			prefix[0 .. 16].copy_from_slice(&packet.source().octets());
+			prefix[16 .. 32].copy_from_slice(&packet.destination().octets());
+
+			Cursor::new(&mut prefix[32 ..])
+				.write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+			prefix[39] = Protocol::Tcp.into();
 		}
 	};
 
@@ -86,5 +95,16 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 		}
 	}
 
+	if let Ok(value) = buffer.read_u8() {
+		// if we have a trailing byte, make a padded 16-bit value.
+		let value = (value as u16) << 8;
+
+		result += u32::from(value);
+
+		if result > 0xffff {
+			result -= 0xffff;
+		}
+	}
+
 	!result as u16
 }
@@ -13,6 +13,7 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::fmt;
+use byteorder::{ReadBytesExt, BigEndian};
 
 use crate::error::*;
 use crate::size;
@@ -70,6 +71,28 @@ pub enum Number {
 	Unknown(u8),
 }
 
+/// Iterator over the blocks of a SACK option.
+#[derive(Debug)]
+pub struct Blocks<'a> {
+	buffer: &'a [u8],
+}
+
+impl<'a> Iterator for Blocks<'a> {
+	type Item = (u32, u32);
+
+	fn next(&mut self) -> std::option::Option<Self::Item> {
+		if self.buffer.len() < 8 {
+			return None;
+		}
+
+		let left  = (&self.buffer[0 ..]).read_u32::<BigEndian>().unwrap();
+		let right = (&self.buffer[4 ..]).read_u32::<BigEndian>().unwrap();
+		self.buffer = &self.buffer[8 ..];
+
+		Some((left, right))
+	}
+}
+
 impl<B: AsRef<[u8]>> fmt::Debug for Option<B> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		f.debug_struct("tcp::Option")
@@ -91,7 +114,40 @@ impl<B: AsRef<[u8]>> Option<B> {
 			Err(Error::SmallBuffer)?
 		}
 
-		if option.buffer.as_ref().len() < option.length() as usize {
+		if matches!(option.number(), Number::End | Number::NoOperation) {
+			return Ok(option);
+		}
+
+		if option.buffer.as_ref().len() < 2 {
+			Err(Error::SmallBuffer)?
+		}
+
+		let length = option.length();
+		let valid  = match option.number() {
+			Number::MaximumSegmentSize =>
+				length == 4,
+
+			Number::WindowScale =>
+				length == 3,
+
+			Number::SelectiveAcknowledgmentPermitted =>
+				length == 2,
+
+			Number::SelectiveAcknowledgment =>
+				(10 ..= 34).contains(&length) && (length - 2) & 0b111 == 0,
+
+			Number::Timestamp =>
+				length == 10,
+
+			_ =>
+				length >= 2,
+		};
+
+		if !valid {
+			Err(Error::InvalidPacket)?
+		}
+
+		if option.buffer.as_ref().len() < length as usize {
 			Err(Error::SmallBuffer)?
 		}
 
@@ -169,6 +225,46 @@ impl<B: AsRef<[u8]>> Option<B> {
 				self.buffer.as_ref()[1]
 		}
 	}
+
+	/// Value of a Maximum Segment Size option.
+	pub fn maximum_segment_size(&self) -> Result<u16> {
+		if self.number() != Number::MaximumSegmentSize {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok((&self.payload()[..]).read_u16::<BigEndian>()?)
+	}
+
+	/// Shift count of a Window Scale option.
+	pub fn window_scale(&self) -> Result<u8> {
+		if self.number() != Number::WindowScale {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(self.payload()[0])
+	}
+
+	/// Left and right edges of the blocks of a SACK option.
+	pub fn selective_acknowledgment(&self) -> Result<Blocks<'_>> {
+		if self.number() != Number::SelectiveAcknowledgment {
+			Err(Error::InvalidPacket)?
+		}
+
+		Ok(Blocks {
+			buffer: self.payload(),
+		})
+	}
+
+	/// TSval and TSecr of a Timestamp option.
+	pub fn timestamp(&self) -> Result<(u32, u32)> {
+		if self.number() != Number::Timestamp {
+			Err(Error::InvalidPacket)?
+		}
+
+		let payload = self.payload();
+		Ok(((&payload[0 ..]).read_u32::<BigEndian>()?,
+		    (&payload[4 ..]).read_u32::<BigEndian>()?))
+	}
 }
 
 impl From<u8> for Number {
@@ -204,3 +300,52 @@ impl Into<u8> for Number {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use crate::tcp::option::{Option, Number};
+
+	#[test]
+	fn maximum_segment_size() {
+		let option = Option::new(&[0x02, 0x04, 0x05, 0xb4][..]).unwrap();
+
+		assert_eq!(option.number(), Number::MaximumSegmentSize);
+		assert_eq!(option.maximum_segment_size().unwrap(), 1460);
+		assert!(option.window_scale().is_err());
+	}
+
+	#[test]
+	fn window_scale() {
+		let option = Option::new(&[0x03, 0x03, 0x07][..]).unwrap();
+		assert_eq!(option.window_scale().unwrap(), 7);
+	}
+
+	#[test]
+	fn selective_acknowledgment() {
+		let option = Option::new(&[
+			0x05, 0x12,
+			0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x07, 0xd0,
+			0x00, 0x00, 0x0b, 0xb8, 0x00, 0x00, 0x0f, 0xa0,
+		][..]).unwrap();
+
+		assert_eq!(option.selective_acknowledgment().unwrap().collect::<Vec<_>>(),
+			vec![(1000, 2000), (3000, 4000)]);
+	}
+
+	#[test]
+	fn timestamp() {
+		let option = Option::new(&[0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x01][..]).unwrap();
+		assert_eq!(option.timestamp().unwrap(), (0x592b2997, 1));
+	}
+
+	#[test]
+	fn malformed() {
+		assert!(Option::new(&[0x02, 0x03, 0x05][..]).is_err());
+		assert!(Option::new(&[0x03, 0x04, 0x07, 0x00][..]).is_err());
+		assert!(Option::new(&[0x05, 0x0b, 0, 0, 0, 0, 0, 0, 0, 0, 0][..]).is_err());
+		assert!(Option::new(&[0x08, 0x08, 0, 0, 0, 0, 0, 0][..]).is_err());
+		assert!(Option::new(&[0x1e, 0x01][..]).is_err());
+		assert!(Option::new(&[0x1e, 0x00][..]).is_err());
+		assert!(Option::new(&[0x08, 0x0a, 0, 0][..]).is_err());
+	}
+}
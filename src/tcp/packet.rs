@@ -75,9 +75,13 @@ impl<B: AsRef<[u8]>> Packet<B> {
 			Err(Error::SmallBuffer)?
 		}
 
-		// if packet.buffer.as_ref().len() < (packet.offset() as usize * 4 + 20){
-		// 	Err(Error::SmallBuffer)?
-		// }
+		if packet.offset() < 5 {
+			Err(Error::InvalidPacket)?
+		}
+
+		if packet.buffer.as_ref().len() < packet.offset() as usize * 4 {
+			Err(Error::SmallBuffer)?
+		}
 
 		Ok(packet)
 	}
@@ -241,7 +245,7 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 		let old = self.header()[12] & 0b1111_0000;
 
 		Cursor::new(&mut self.header_mut()[12 ..])
-			.write_u16::<BigEndian>((u16::from(old)) << 12 | value.bits())?;
+			.write_u16::<BigEndian>(u16::from(old) << 8 | value.bits())?;
 
 		Ok(self)
 	}
@@ -265,8 +269,9 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 	/// Create a checksumed setter.
 	pub fn checked<'a, 'b, BI: AsRef<[u8]> + 'b>(&'a mut self, ip: &'b ip::Packet<BI>) -> Checked<'a, 'b, B, BI> {
 		Checked {
-			packet: self,
-			ip:     ip,
+			packet:      self,
+			ip,
+			incremental: false,
 		}
 	}
 
@@ -283,65 +288,92 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 		let checksum = checksum(ip, self.buffer.as_ref());
 		self.set_checksum(checksum)
 	}
+
+	/// Adjust the checksum value for covered data changed from `old` to `new`,
+	/// both starting at the same even offset.
+	///
+	/// # Note
+	///
+	/// This also applies to the pseudo-header, for instance to account for a
+	/// rewritten IP address.
+	pub fn adjust_checksum(&mut self, old: &[u8], new: &[u8]) -> Result<&mut Self> {
+		let checksum = crate::checksum::update(self.checksum(), old, new);
+		self.set_checksum(checksum)
+	}
 }
 
 /// Checked wrapper for UDP packets.
 ///
 /// # Note
 ///
-/// The checksum recalculation happens on `Drop`, so don't leak it.
+/// The checksum recalculation happens on `Drop`, so don't leak it, unless
+/// the incremental mode is enabled.
 pub struct Checked<'a, 'b, BP, BI>
 	where BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
 	      BI: AsRef<[u8]> + 'b
 {
-	packet: &'a mut Packet<BP>,
-	ip:     &'b ip::Packet<BI>,
+	packet:      &'a mut Packet<BP>,
+	ip:          &'b ip::Packet<BI>,
+	incremental: bool,
 }
 
 impl<'a, 'b, BP, BI> Checked<'a, 'b, BP, BI>
 	where BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
 	      BI: AsRef<[u8]> + 'b
 {
+	/// Update the checksum from the old and new value of every changed field,
+	/// as described in RFC 1624, instead of recalculating it on `Drop`.
+	pub fn incremental(&mut self) -> &mut Self {
+		self.incremental = true;
+		self
+	}
+
+	fn change<F>(&mut self, offset: usize, length: usize, f: F) -> Result<&mut Self>
+		where F: FnOnce(&mut Packet<BP>) -> Result<&mut Packet<BP>>
+	{
+		if self.incremental {
+			crate::checksum::change(self.packet, offset, length, f, Packet::adjust_checksum)?;
+		}
+		else {
+			f(self.packet)?;
+		}
+
+		Ok(self)
+	}
+
 	/// Source port.
 	pub fn set_source(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_source(value)?;
-		Ok(self)
+		self.change(0, 2, |p| p.set_source(value))
 	}
 
 	/// Destination port.
 	pub fn set_destination(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_destination(value)?;
-		Ok(self)
+		self.change(2, 2, |p| p.set_destination(value))
 	}
 
 	/// Packet sequence.
 	pub fn set_sequence(&mut self, value: u32) -> Result<&mut Self> {
-		self.packet.set_sequence(value)?;
-		Ok(self)
+		self.change(4, 4, |p| p.set_sequence(value))
 	}
 
 	/// Optional acknowledgment.
 	pub fn set_acknowledgment(&mut self, value: u32) -> Result<&mut Self> {
-		self.packet.set_acknowledgment(value)?;
-		Ok(self)
+		self.change(8, 4, |p| p.set_acknowledgment(value))
 	}
 
 	/// Packet flags.
 	pub fn set_flags(&mut self, value: Flags) -> Result<&mut Self> {
-		self.packet.set_flags(value)?;
-		Ok(self)
+		self.change(12, 2, |p| p.set_flags(value))
 	}
 
 	/// Packet window.
 	pub fn set_window(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_window(value)?;
-		Ok(self)
+		self.change(14, 2, |p| p.set_window(value))
 	}
 
 	/// Urgent pointer.
 	pub fn set_pointer(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_pointer(value)?;
-		Ok(self)
+		self.change(18, 2, |p| p.set_pointer(value))
 	}
 }
 
@@ -350,7 +382,9 @@ impl<'a, 'b, BP, BI> Drop for Checked<'a, 'b, BP, BI>
 	      BI: AsRef<[u8]> + 'b
 {
 	fn drop(&mut self) {
-		self.packet.update_checksum(self.ip).unwrap();
+		if !self.incremental {
+			self.packet.update_checksum(self.ip).unwrap();
+		}
 	}
 }
 
@@ -379,8 +413,10 @@ impl<'a> Iterator for OptionIter<'a> {
 				Some(Ok(option))
 			}
 
-			Err(error) =>
+			Err(error) => {
+				self.buffer = &[];
 				Some(Err(error))
+			}
 		}
 	}
 }
@@ -405,6 +441,60 @@ mod test {
 		assert_eq!(tcp.destination(), 80);
 	}
 
+	#[test]
+	fn options() {
+		let raw = [0x45u8, 0x00, 0x00, 0x3c, 0xc8, 0xa5, 0x40, 0x00, 0x40, 0x06, 0x9f, 0xd5, 0xc0, 0xa8, 0x01, 0x89, 0x08, 0x08, 0x08, 0x08, 0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a, 0x00, 0x00, 0x00, 0x00, 0xa0, 0x02, 0x72, 0x10, 0x3f, 0x5f, 0x00, 0x00, 0x02, 0x04, 0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00, 0x01, 0x03, 0x03, 0x07];
+
+		let ip      = ip::v4::Packet::new(&raw[..]).unwrap();
+		let tcp     = tcp::Packet::new(ip.payload()).unwrap();
+		let options = tcp.options().collect::<Result<Vec<_>, _>>().unwrap();
+
+		assert_eq!(options.len(), 5);
+		assert_eq!(options[0].maximum_segment_size().unwrap(), 1460);
+		assert_eq!(options[1].number(), tcp::option::Number::SelectiveAcknowledgmentPermitted);
+		assert_eq!(options[2].timestamp().unwrap(), (0x592b2997, 0));
+		assert_eq!(options[3].number(), tcp::option::Number::NoOperation);
+		assert_eq!(options[4].window_scale().unwrap(), 7);
+	}
+
+	#[test]
+	fn malformed_options() {
+		let raw = [0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a, 0x00, 0x00, 0x00, 0x00, 0x60, 0x02, 0x72, 0x10, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x05, 0xb4];
+
+		let tcp     = tcp::Packet::new(&raw[..]).unwrap();
+		let options = tcp.options().collect::<Vec<_>>();
+
+		assert_eq!(options.len(), 1);
+		assert!(options[0].is_err());
+	}
+
+	#[test]
+	fn values_ipv6() {
+		let raw = hex::decode("600000000028064020010db800000000000000000000000120010db80000000000000000000000029b8a0050de67c74a00000000a0027210b63f0000020405a00402080a592b29970000000001030307").unwrap();
+
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+		let tcp = tcp::Packet::new(ip.payload()).unwrap();
+
+		assert!(tcp.is_valid(&ip::Packet::from(&ip)));
+
+		assert_eq!(tcp.flags(), tcp::flag::SYN);
+		assert_eq!(tcp.destination(), 80);
+	}
+
+	#[test]
+	fn mutable_ipv6() {
+		let mut raw = hex::decode("600000000028064020010db800000000000000000000000120010db80000000000000000000000029b8a0050de67c74a00000000a0027210b63f0000020405a00402080a592b29970000000001030307").unwrap();
+
+		let mut ip    = ip::v6::Packet::new(&mut raw[..]).unwrap();
+		let (ip, tcp) = ip.split_mut();
+		let     ip    = ip::Packet::from(ip::v6::Packet::unchecked(ip));
+		let mut tcp   = tcp::Packet::new(tcp).unwrap();
+
+		tcp.checked(&ip).set_destination(9001).unwrap();
+		assert_eq!(tcp.destination(), 9001);
+		assert!(tcp.is_valid(&ip));
+	}
+
 	#[test]
 	fn mutable() {
 		let mut raw = [0x45u8, 0x00, 0x00, 0x3c, 0xc8, 0xa5, 0x40, 0x00, 0x40, 0x06, 0x9f, 0xd5, 0xc0, 0xa8, 0x01, 0x89, 0x08, 0x08, 0x08, 0x08, 0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a, 0x00, 0x00, 0x00, 0x00, 0xa0, 0x02, 0x72, 0x10, 0x3f, 0x5f, 0x00, 0x00, 0x02, 0x04, 0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00, 0x01, 0x03, 0x03, 0x07];
@@ -441,4 +531,50 @@ mod test {
 		assert_eq!(tcp.destination(), 9001);
 		assert!(tcp.is_valid(&ip));
 	}
+
+	#[test]
+	fn set_flags() {
+		let mut raw = [0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a, 0x00, 0x00, 0x00, 0x00, 0x60, 0x02, 0x72, 0x10, 0x00, 0x00, 0x00, 0x00, 0x02, 0x04, 0x05, 0xb4];
+
+		let mut tcp = tcp::Packet::new(&mut raw[..]).unwrap();
+		tcp.set_flags(tcp::flag::SYN | tcp::flag::ACK).unwrap();
+
+		assert_eq!(tcp.offset(), 6);
+		assert_eq!(tcp.flags(), tcp::flag::SYN | tcp::flag::ACK);
+		assert_eq!(tcp.options().next().unwrap().unwrap().maximum_segment_size().unwrap(), 1460);
+	}
+
+	#[test]
+	fn mutable_incremental() {
+		let mut raw = [0x45u8, 0x00, 0x00, 0x3c, 0xc8, 0xa5, 0x40, 0x00, 0x40, 0x06, 0x9f, 0xd5, 0xc0, 0xa8, 0x01, 0x89, 0x08, 0x08, 0x08, 0x08, 0x9b, 0x8a, 0x00, 0x50, 0xde, 0x67, 0xc7, 0x4a, 0x00, 0x00, 0x00, 0x00, 0xa0, 0x02, 0x72, 0x10, 0x3f, 0x5f, 0x00, 0x00, 0x02, 0x04, 0x05, 0xb4, 0x04, 0x02, 0x08, 0x0a, 0x59, 0x2b, 0x29, 0x97, 0x00, 0x00, 0x00, 0x00, 0x01, 0x03, 0x03, 0x07];
+
+		let mut ip    = ip::v4::Packet::new(&mut raw[..]).unwrap();
+		let (ip, tcp) = ip.split_mut();
+		let mut tcp   = tcp::Packet::new(tcp).unwrap();
+
+		{
+			let ip = ip::Packet::from(ip::v4::Packet::unchecked(&ip[..]));
+
+			tcp.checked(&ip).incremental()
+				.set_source(1337).unwrap()
+				.set_destination(9001).unwrap()
+				.set_sequence(0x12345678).unwrap()
+				.set_flags(tcp::flag::SYN | tcp::flag::ACK).unwrap()
+				.set_window(1024).unwrap();
+
+			assert_eq!(tcp.destination(), 9001);
+			assert!(tcp.is_valid(&ip));
+		}
+
+		// Rewrite the source address, the pseudo-header changes as well.
+		let mut packet = ip::v4::Packet::unchecked(&mut ip[..]);
+		let old        = packet.source();
+		let new        = "10.0.0.1".parse::<std::net::Ipv4Addr>().unwrap();
+
+		packet.checked().incremental().set_source(new).unwrap();
+		assert!(packet.is_valid());
+
+		tcp.adjust_checksum(&old.octets(), &new.octets()).unwrap();
+		assert!(tcp.is_valid(&ip::Packet::from(ip::v4::Packet::unchecked(&ip[..]))));
+	}
 }
@@ -0,0 +1,516 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::error::*;
+use crate::packet::Packet as P;
+use crate::ip::{self, Protocol};
+use crate::tcp::{self, Flags};
+use crate::flow::Key;
+
+/// Event produced while reassembling a stream.
+///
+/// The key is always oriented in the direction the data flows.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Event {
+	/// A SYN was seen.
+	Open(Key),
+
+	/// Contiguous data.
+	Data(Key, Vec<u8>),
+
+	/// Octets that were never received, preceding the next data.
+	Gap(Key, u32),
+
+	/// A FIN was seen and all the data before it was delivered.
+	Close(Key),
+
+	/// A RST was seen, the connection is dropped.
+	Reset(Key),
+}
+
+/// State of one direction of a connection.
+#[derive(Default, Debug)]
+struct Half {
+	next:    std::option::Option<u32>,
+	fin:     std::option::Option<u32>,
+	closed:  bool,
+	pending: Vec<(u32, Vec<u8>)>,
+	size:    usize,
+}
+
+/// Signed distance between two sequence numbers.
+fn distance(from: u32, to: u32) -> i64 {
+	to.wrapping_sub(from) as i32 as i64
+}
+
+impl Half {
+	fn deliver(&mut self, key: Key, data: &[u8], events: &mut Vec<Event>) {
+		if data.is_empty() {
+			return;
+		}
+
+		self.next = self.next.map(|n| n.wrapping_add(data.len() as u32));
+		events.push(Event::Data(key, data.to_vec()));
+	}
+
+	fn segment(&mut self, key: Key, sequence: u32, data: &[u8], events: &mut Vec<Event>) {
+		self.next.get_or_insert(sequence);
+
+		if !data.is_empty() {
+			self.size += data.len();
+			self.pending.push((sequence, data.to_vec()));
+		}
+
+		self.drain(key, events);
+	}
+
+	/// Deliver the pending segments that became contiguous.
+	///
+	/// Segments are kept in the order they were received, so when they
+	/// overlap the data that was received first wins.
+	fn drain(&mut self, key: Key, events: &mut Vec<Event>) {
+		while let Some(next) = self.next {
+			let index = match self.pending.iter().position(|&(s, _)| distance(next, s) <= 0) {
+				Some(index) => index,
+				None        => break,
+			};
+
+			let (sequence, data) = self.pending.remove(index);
+			self.size -= data.len();
+
+			let skip = distance(sequence, next) as usize;
+			if skip >= data.len() {
+				continue;
+			}
+
+			let data = &data[skip ..];
+			let end  = self.pending[.. index].iter()
+				.map(|&(s, _)| distance(next, s))
+				.filter(|&d| d > 0 && (d as usize) < data.len())
+				.min()
+				.map_or(data.len(), |d| d as usize);
+
+			let (data, rest) = data.split_at(end);
+			if !rest.is_empty() {
+				self.size += rest.len();
+				self.pending.insert(index, (next.wrapping_add(end as u32), rest.to_vec()));
+			}
+
+			self.deliver(key, data, events);
+		}
+
+		if let (Some(next), Some(fin)) = (self.next, self.fin) {
+			if !self.closed && next == fin {
+				self.closed = true;
+				self.next   = Some(next.wrapping_add(1));
+				events.push(Event::Close(key));
+			}
+		}
+	}
+
+	/// Skip to the earliest pending segment, reporting the missing octets.
+	fn skip(&mut self, key: Key, events: &mut Vec<Event>) -> bool {
+		let next = match self.next {
+			Some(next) => next,
+			None => return false,
+		};
+
+		let target = self.pending.iter()
+			.map(|&(s, _)| s)
+			.chain(self.fin)
+			.min_by_key(|&s| distance(next, s));
+
+		match target {
+			Some(target) if distance(next, target) > 0 => {
+				events.push(Event::Gap(key, target.wrapping_sub(next)));
+				self.next = Some(target);
+				self.drain(key, events);
+
+				true
+			}
+
+			_ =>
+				false,
+		}
+	}
+}
+
+/// State of a connection.
+#[derive(Debug)]
+struct Connection {
+	halves:  [Half; 2],
+	updated: Duration,
+}
+
+impl Connection {
+	fn size(&self) -> usize {
+		self.halves[0].size + self.halves[1].size
+	}
+}
+
+/// Index of the half the given oriented key belongs to.
+fn side(key: &Key) -> usize {
+	if key.is_canonical() { 0 } else { 1 }
+}
+
+/// TCP stream reassembler.
+///
+/// Segments are tracked per connection and direction, ordered by sequence
+/// number and delivered as contiguous data; overlapping data is trimmed,
+/// keeping what was received first.
+#[derive(Debug)]
+pub struct Reassembler {
+	timeout: Duration,
+	limit:   usize,
+
+	connections: HashMap<Key, Connection>,
+}
+
+impl Default for Reassembler {
+	fn default() -> Self {
+		Reassembler {
+			timeout: Duration::from_secs(300),
+			limit:   1024 * 1024,
+
+			connections: HashMap::new(),
+		}
+	}
+}
+
+impl Reassembler {
+	/// Create a new reassembler with the default settings.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Time after which an idle connection is discarded.
+	pub fn timeout(mut self, value: Duration) -> Self {
+		self.timeout = value;
+		self
+	}
+
+	/// Maximum amount of out of order data buffered per connection, once
+	/// exceeded the missing data is skipped and reported as a gap.
+	pub fn limit(mut self, value: usize) -> Self {
+		self.limit = value;
+		self
+	}
+
+	/// Number of tracked connections.
+	pub fn len(&self) -> usize {
+		self.connections.len()
+	}
+
+	/// Whether there are no tracked connections.
+	pub fn is_empty(&self) -> bool {
+		self.connections.is_empty()
+	}
+
+	/// Discard the connections that timed out, returning how many were dropped.
+	pub fn expire(&mut self, now: Duration) -> usize {
+		let timeout = self.timeout;
+		let before  = self.connections.len();
+
+		self.connections.retain(|_, c| now.saturating_sub(c.updated) < timeout);
+		before - self.connections.len()
+	}
+
+	/// Add a segment, returning the resulting events.
+	pub fn push<B: AsRef<[u8]>>(&mut self, packet: &ip::Packet<B>, now: Duration) -> Result<Vec<Event>> {
+		self.expire(now);
+
+		let key = Key::new(packet)?;
+		if key.protocol != Protocol::Tcp {
+			Err(Error::InvalidPacket)?
+		}
+
+		let segment = match *packet {
+			// Only the first fragment carries the TCP header.
+			ip::Packet::V4(ref packet) if packet.offset() != 0 =>
+				Err(Error::InvalidPacket)?,
+
+			ip::Packet::V4(ref packet) =>
+				tcp::Packet::new(packet.payload())?,
+
+			ip::Packet::V6(ref packet) if packet.protocol()? != Protocol::Tcp =>
+				Err(Error::InvalidPacket)?,
+
+			ip::Packet::V6(ref packet) => {
+				let buffer = packet.payload();
+				tcp::Packet::new(&buffer[(packet.payload_offset()? - 40).min(buffer.len()) ..])?
+			}
+		};
+
+		let mut events = Vec::new();
+		let canonical  = key.canonical();
+		let flags      = segment.flags();
+
+		if flags.contains(Flags::RST) {
+			self.connections.remove(&canonical);
+			events.push(Event::Reset(key));
+
+			return Ok(events);
+		}
+
+		let connection = self.connections.entry(canonical).or_insert_with(|| Connection {
+			halves:  Default::default(),
+			updated: now,
+		});
+
+		connection.updated = now;
+
+		let half = &mut connection.halves[side(&key)];
+		let mut sequence = segment.sequence();
+
+		if flags.contains(Flags::SYN) {
+			if half.next.is_none() {
+				events.push(Event::Open(key));
+			}
+
+			sequence = sequence.wrapping_add(1);
+			half.next.get_or_insert(sequence);
+		}
+
+		half.segment(key, sequence, segment.payload(), &mut events);
+
+		if flags.contains(Flags::FIN) && half.fin.is_none() {
+			half.fin = Some(sequence.wrapping_add(segment.payload().len() as u32));
+			half.drain(key, &mut events);
+		}
+
+		while connection.size() > self.limit {
+			let index = if connection.halves[0].size >= connection.halves[1].size { 0 } else { 1 };
+			let key   = if index == side(&key) { key } else { key.reverse() };
+
+			if !connection.halves[index].skip(key, &mut events) {
+				break;
+			}
+		}
+
+		if connection.halves.iter().all(|h| h.closed) {
+			self.connections.remove(&canonical);
+		}
+
+		Ok(events)
+	}
+
+	/// Deliver all the buffered data, reporting any gaps, and drop every
+	/// connection.
+	pub fn flush(&mut self) -> Vec<Event> {
+		let mut events = Vec::new();
+
+		for (canonical, mut connection) in self.connections.drain() {
+			for (index, half) in connection.halves.iter_mut().enumerate() {
+				let key = if index == 0 { canonical } else { canonical.reverse() };
+				while half.skip(key, &mut events) { }
+			}
+		}
+
+		events
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+	use crate::builder::Builder;
+	use crate::ip;
+	use crate::tcp::{self, Flags};
+	use crate::tcp::stream::{Reassembler, Event};
+	use crate::flow::Key;
+
+	fn segment(client: bool, sequence: u32, flags: Flags, payload: &[u8]) -> Vec<u8> {
+		let (source, destination, sport, dport) = if client {
+			("192.168.0.79", "66.102.1.108", 40000, 80)
+		}
+		else {
+			("66.102.1.108", "192.168.0.79", 80, 40000)
+		};
+
+		ip::v4::Builder::default()
+			.source(source.parse().unwrap()).unwrap()
+			.destination(destination.parse().unwrap()).unwrap()
+			.tcp().unwrap()
+				.source(sport).unwrap()
+				.destination(dport).unwrap()
+				.sequence(sequence).unwrap()
+				.flags(flags).unwrap()
+				.payload(payload).unwrap()
+				.build().unwrap()
+	}
+
+	fn push(reassembler: &mut Reassembler, raw: &[u8]) -> Vec<Event> {
+		reassembler.push(&ip::Packet::new(raw).unwrap(), Duration::from_secs(0)).unwrap()
+	}
+
+	fn data(events: &[Event]) -> Vec<u8> {
+		events.iter().flat_map(|e| match *e {
+			Event::Data(_, ref data) => data.clone(),
+			_ => Vec::new(),
+		}).collect()
+	}
+
+	fn key(client: bool) -> Key {
+		let raw = segment(client, 0, tcp::flag::ACK, &[]);
+		Key::new(&ip::Packet::new(&raw[..]).unwrap()).unwrap()
+	}
+
+	#[test]
+	fn in_order() {
+		let mut reassembler = Reassembler::new();
+		let mut events = Vec::new();
+
+		events.extend(push(&mut reassembler, &segment(true, 100, tcp::flag::SYN, &[])));
+		events.extend(push(&mut reassembler, &segment(false, 500, tcp::flag::SYN | tcp::flag::ACK, &[])));
+		events.extend(push(&mut reassembler, &segment(true, 101, tcp::flag::ACK, b"GET /")));
+		events.extend(push(&mut reassembler, &segment(false, 501, tcp::flag::ACK, b"200 OK")));
+		events.extend(push(&mut reassembler, &segment(true, 106, tcp::flag::FIN | tcp::flag::ACK, &[])));
+		assert_eq!(reassembler.len(), 1);
+		events.extend(push(&mut reassembler, &segment(false, 507, tcp::flag::FIN | tcp::flag::ACK, &[])));
+
+		assert_eq!(events, vec![
+			Event::Open(key(true)),
+			Event::Open(key(false)),
+			Event::Data(key(true), b"GET /".to_vec()),
+			Event::Data(key(false), b"200 OK".to_vec()),
+			Event::Close(key(true)),
+			Event::Close(key(false)),
+		]);
+
+		assert!(reassembler.is_empty());
+	}
+
+	#[test]
+	fn out_of_order() {
+		let mut reassembler = Reassembler::new();
+
+		push(&mut reassembler, &segment(true, 0, tcp::flag::SYN, &[]));
+		assert!(push(&mut reassembler, &segment(true, 8, tcp::flag::ACK, b"world")).is_empty());
+		assert!(push(&mut reassembler, &segment(true, 5, tcp::flag::ACK, b"o, ")).is_empty());
+
+		let events = push(&mut reassembler, &segment(true, 1, tcp::flag::ACK, b"hell"));
+		assert_eq!(data(&events), b"hello, world");
+	}
+
+	#[test]
+	fn overlap() {
+		let mut reassembler = Reassembler::new();
+		let mut events = Vec::new();
+
+		events.extend(push(&mut reassembler, &segment(true, 0, tcp::flag::SYN, &[])));
+		events.extend(push(&mut reassembler, &segment(true, 1, tcp::flag::ACK, b"abcd")));
+		events.extend(push(&mut reassembler, &segment(true, 1, tcp::flag::ACK, b"abcd")));
+		events.extend(push(&mut reassembler, &segment(true, 7, tcp::flag::ACK, b"ghij")));
+		events.extend(push(&mut reassembler, &segment(true, 3, tcp::flag::ACK, b"XXefXX")));
+
+		assert_eq!(data(&events), b"abcdefghij");
+	}
+
+	#[test]
+	fn wraparound() {
+		let mut reassembler = Reassembler::new();
+		let mut events = Vec::new();
+
+		events.extend(push(&mut reassembler, &segment(true, u32::MAX - 2, tcp::flag::SYN, &[])));
+		events.extend(push(&mut reassembler, &segment(true, 1, tcp::flag::ACK, b"def")));
+		events.extend(push(&mut reassembler, &segment(true, u32::MAX - 1, tcp::flag::ACK, b"abcd")));
+
+		assert_eq!(data(&events), b"abcdef");
+	}
+
+	#[test]
+	fn gap() {
+		let mut reassembler = Reassembler::new();
+
+		push(&mut reassembler, &segment(true, 0, tcp::flag::SYN, &[]));
+		push(&mut reassembler, &segment(true, 1, tcp::flag::ACK, b"abc"));
+		assert!(push(&mut reassembler, &segment(true, 10, tcp::flag::ACK, b"xyz")).is_empty());
+
+		assert_eq!(reassembler.flush(), vec![
+			Event::Gap(key(true), 6),
+			Event::Data(key(true), b"xyz".to_vec()),
+		]);
+
+		assert!(reassembler.is_empty());
+	}
+
+	#[test]
+	fn limit() {
+		let mut reassembler = Reassembler::new().limit(4);
+
+		push(&mut reassembler, &segment(true, 0, tcp::flag::SYN, &[]));
+		assert!(push(&mut reassembler, &segment(true, 5, tcp::flag::ACK, b"ef")).is_empty());
+
+		let events = push(&mut reassembler, &segment(true, 7, tcp::flag::ACK, b"ghi"));
+		assert_eq!(events, vec![
+			Event::Gap(key(true), 4),
+			Event::Data(key(true), b"ef".to_vec()),
+			Event::Data(key(true), b"ghi".to_vec()),
+		]);
+	}
+
+	#[test]
+	fn reset() {
+		let mut reassembler = Reassembler::new();
+
+		push(&mut reassembler, &segment(true, 0, tcp::flag::SYN, &[]));
+		assert_eq!(reassembler.len(), 1);
+
+		let events = push(&mut reassembler, &segment(false, 0, tcp::flag::RST | tcp::flag::ACK, &[]));
+		assert_eq!(events, vec![Event::Reset(key(false))]);
+		assert!(reassembler.is_empty());
+	}
+
+	#[test]
+	fn midstream() {
+		let mut reassembler = Reassembler::new();
+
+		let events = push(&mut reassembler, &segment(false, 1000, tcp::flag::ACK, b"data"));
+		assert_eq!(events, vec![Event::Data(key(false), b"data".to_vec())]);
+	}
+
+	#[test]
+	fn expire() {
+		let mut reassembler = Reassembler::new().timeout(Duration::from_secs(10));
+
+		push(&mut reassembler, &segment(true, 0, tcp::flag::SYN, &[]));
+		assert_eq!(reassembler.expire(Duration::from_secs(5)), 0);
+		assert_eq!(reassembler.expire(Duration::from_secs(10)), 1);
+	}
+
+	#[test]
+	fn malformed() {
+		let mut reassembler = Reassembler::new();
+
+		// Data offset past the end of the segment.
+		let mut raw = segment(true, 0, tcp::flag::ACK, b"data");
+		raw[32] = 0xf0;
+		assert!(reassembler.push(&ip::Packet::new(&raw[..]).unwrap(), Duration::from_secs(0)).is_err());
+
+		// IPv4 fragment after the first.
+		let mut raw = segment(true, 0, tcp::flag::ACK, b"data");
+		raw[7] = 0x10;
+		assert!(reassembler.push(&ip::Packet::new(&raw[..]).unwrap(), Duration::from_secs(0)).is_err());
+
+		// IPv6 fragment after the first.
+		let raw = hex::decode("6000000000182c4020010db800000000000000000000000120010db8000000000000000000000002060003201234567800010002000000000000000050180000").unwrap();
+		assert!(reassembler.push(&ip::Packet::new(&raw[..]).unwrap(), Duration::from_secs(0)).is_err());
+
+		assert!(reassembler.is_empty());
+	}
+}
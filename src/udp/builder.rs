@@ -20,6 +20,8 @@ use crate::buffer::{self, Buffer};
 use crate::builder::{Builder as Build, Finalization};
 use crate::packet::{AsPacket, AsPacketMut};
 use crate::ip;
+use crate::dns;
+use crate::dhcp;
 use crate::udp::Packet;
 use crate::udp::checksum;
 
@@ -109,6 +111,16 @@ impl<B: Buffer> Builder<B> {
 		Ok(self)
 	}
 
+	/// DNS message as payload for the packet.
+	pub fn dns(self, value: dns::Builder) -> Result<Self> {
+		self.payload(&value.build()?)
+	}
+
+	/// DHCP message as payload for the packet.
+	pub fn dhcp(self, value: dhcp::Builder) -> Result<Self> {
+		self.payload(&value.build()?)
+	}
+
 	fn prepare(&mut self) {
 		let ip     = self.ip;
 		let length = self.buffer.length();
@@ -131,8 +143,12 @@ impl<B: Buffer> Builder<B> {
 				Err(Error::InvalidPacket)?
 			};
 
+			// A zero checksum means no checksum, so send its complement instead.
 			Cursor::new(&mut udp[6 ..])
-				.write_u16::<BigEndian>(checksum?)?;
+				.write_u16::<BigEndian>(match checksum? {
+					0        => 0xffff,
+					checksum => checksum,
+				})?;
 
 			Ok(())
 		});
@@ -146,6 +162,7 @@ mod test {
 	use crate::packet::Packet;
 	use crate::ip;
 	use crate::udp;
+	use crate::dns;
 
 	#[test]
 	fn simple() {
@@ -174,4 +191,53 @@ mod test {
 		assert_eq!(udp.destination(), 9001);
 		assert!(udp.is_valid(&ip::Packet::from(&ip)));
 	}
+
+	#[test]
+	fn dns() {
+		let packet = ip::v4::Builder::default()
+			.source("192.168.1.137".parse().unwrap()).unwrap()
+			.destination("192.168.1.254".parse().unwrap()).unwrap()
+			.udp().unwrap()
+				.source(47663).unwrap()
+				.destination(53).unwrap()
+				.dns(dns::Builder::default()
+					.id(0xbc81).unwrap()
+					.flags(dns::flag::RECURSION_DESIRED).unwrap()
+					.question("api.steampowered.com", dns::Type::Aaaa, dns::Class::In).unwrap()).unwrap()
+				.build().unwrap();
+
+		let ip  = ip::v4::Packet::new(packet).unwrap();
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+
+		let dns = dns::Packet::new(udp.payload()).unwrap();
+		assert_eq!(dns.id(), 0xbc81);
+		assert_eq!(dns.questions().next().unwrap().unwrap().name(), "api.steampowered.com");
+	}
+
+	#[test]
+	fn zero_v6() {
+		fn build(payload: &[u8]) -> Vec<u8> {
+			ip::v6::Builder::default()
+				.source("2001:db8::1".parse().unwrap()).unwrap()
+				.destination("2001:db8::2".parse().unwrap()).unwrap()
+				.udp().unwrap()
+					.source(1337).unwrap()
+					.destination(9001).unwrap()
+					.payload(payload).unwrap()
+					.build().unwrap()
+		}
+
+		// Putting the checksum in the payload makes the sum 0xffff.
+		let packet   = build(&[0, 0]);
+		let ip       = ip::v6::Packet::new(&packet[..]).unwrap();
+		let checksum = udp::Packet::new(ip.payload()).unwrap().checksum();
+
+		let packet = build(&checksum.to_be_bytes());
+		let ip     = ip::v6::Packet::new(&packet[..]).unwrap();
+		let udp    = udp::Packet::new(ip.payload()).unwrap();
+		assert_eq!(udp::checksum(&ip::Packet::from(&ip), ip.payload()), 0);
+		assert_eq!(udp.checksum(), 0xffff);
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
 }
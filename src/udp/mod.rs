@@ -42,8 +42,13 @@ pub fn checksum<B: AsRef<[u8]>>(ip: &ip::Packet<B>, buffer: &[u8]) -> u16 {
 				.write_u16::<BigEndian>(buffer.len() as u16).unwrap();
 		}
 
-		ip::Packet::V6(ref _packet) => {
-			unimplemented!("There is no checksum in ipv6 packet")
+		ip::Packet::V6(ref packet) => {
+			prefix[0 .. 16].copy_from_slice(&packet.source().octets());
+			prefix[16 .. 32].copy_from_slice(&packet.destination().octets());
+
+			Cursor::new(&mut prefix[32 ..])
+				.write_u32::<BigEndian>(buffer.len() as u32).unwrap();
+			prefix[39] = Protocol::Udp.into();
 		}
 	};
 
@@ -116,4 +121,15 @@ mod tests {
 
 		assert_eq!(checksum(&ip::Packet::V4(ip), ip.payload()), udp.checksum());
 	}
+
+	#[test]
+	fn test_checksum_v6() {
+		let raw = hex::decode("60000000005c11ff2607f2c0f00fb00100000000faceb00c200105900000000000000000451f1f6210f610f6005c464e15000001fb7aff572ebc6869000199101f5000022607f2c0f00fb00100000000faceb00c001c000199101f5000000005011c10000000000199101f500164ff00000500022607f2c0f00fb00100000000faceb00c").unwrap();
+
+		let ip  = ip::v6::Packet::new(&raw[..]).unwrap();
+		let udp = udp::Packet::new(ip.payload()).unwrap();
+
+		assert_eq!(checksum(&ip::Packet::from(&ip), ip.payload()), udp.checksum());
+		assert!(udp.is_valid(&ip::Packet::from(&ip)));
+	}
 }
@@ -186,7 +186,13 @@ impl<B: AsRef<[u8]>> Packet<B> {
 
 	/// Verify the packet is valid by calculating the checksum.
 	pub fn is_valid<I: AsRef<[u8]>>(&self, ip: &ip::Packet<I>) -> bool {
-		checksum(ip, self.buffer.as_ref()) == self.checksum()
+		match checksum(ip, self.buffer.as_ref()) {
+			0 =>
+				self.checksum() == 0xffff,
+
+			checksum =>
+				checksum == self.checksum(),
+		}
 	}
 }
 
@@ -210,8 +216,9 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 	/// Create a checksumed setter.
 	pub fn checked<'a, 'b, BI: AsRef<[u8]> + 'b>(&'a mut self, ip: &'b ip::Packet<BI>) -> Checked<'a, 'b, B, BI> {
 		Checked {
-			packet: self,
-			ip:     ip,
+			packet:      self,
+			ip,
+			incremental: false,
 		}
 	}
 
@@ -225,8 +232,34 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 
 	/// Recalculate and set the checksum value.
 	pub fn update_checksum<BI: AsRef<[u8]>>(&mut self, ip: &ip::Packet<BI>) -> Result<&mut Self> {
-		let checksum = checksum(ip, self.buffer.as_ref());
-		self.set_checksum(checksum)
+		match checksum(ip, self.buffer.as_ref()) {
+			0 =>
+				self.set_checksum(0xffff),
+
+			checksum =>
+				self.set_checksum(checksum),
+		}
+	}
+
+	/// Adjust the checksum value for covered data changed from `old` to `new`,
+	/// both starting at the same even offset.
+	///
+	/// # Note
+	///
+	/// This also applies to the pseudo-header, for instance to account for a
+	/// rewritten IP address. A disabled checksum is left untouched.
+	pub fn adjust_checksum(&mut self, old: &[u8], new: &[u8]) -> Result<&mut Self> {
+		if self.checksum() == 0 {
+			return Ok(self);
+		}
+
+		match crate::checksum::update(self.checksum(), old, new) {
+			0 =>
+				self.set_checksum(0xffff),
+
+			checksum =>
+				self.set_checksum(checksum),
+		}
 	}
 }
 
@@ -234,29 +267,49 @@ impl<B: AsRef<[u8]> + AsMut<[u8]>> Packet<B> {
 ///
 /// # Note
 ///
-/// The checksum recalculation happens on `Drop`, so don't leak it.
+/// The checksum recalculation happens on `Drop`, so don't leak it, unless
+/// the incremental mode is enabled.
 pub struct Checked<'a, 'b, BP, BI>
 	where BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
 	      BI: AsRef<[u8]> + 'b
 {
-	packet: &'a mut Packet<BP>,
-	ip:     &'b ip::Packet<BI>,
+	packet:      &'a mut Packet<BP>,
+	ip:          &'b ip::Packet<BI>,
+	incremental: bool,
 }
 
 impl<'a, 'b, BP, BI> Checked<'a, 'b, BP, BI>
 	where BP: AsRef<[u8]> + AsMut<[u8]> + 'a,
 	      BI: AsRef<[u8]> + 'b
 {
+	/// Update the checksum from the old and new value of every changed field,
+	/// as described in RFC 1624, instead of recalculating it on `Drop`.
+	pub fn incremental(&mut self) -> &mut Self {
+		self.incremental = true;
+		self
+	}
+
+	fn change<F>(&mut self, offset: usize, length: usize, f: F) -> Result<&mut Self>
+		where F: FnOnce(&mut Packet<BP>) -> Result<&mut Packet<BP>>
+	{
+		if self.incremental {
+			crate::checksum::change(self.packet, offset, length, f, Packet::adjust_checksum)?;
+		}
+		else {
+			f(self.packet)?;
+		}
+
+		Ok(self)
+	}
+
 	/// Source port.
 	pub fn set_source(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_source(value)?;
-		Ok(self)
+		self.change(0, 2, |p| p.set_source(value))
 	}
 
 	/// Destination port.
 	pub fn set_destination(&mut self, value: u16) -> Result<&mut Self> {
-		self.packet.set_destination(value)?;
-		Ok(self)
+		self.change(2, 2, |p| p.set_destination(value))
 	}
 }
 
@@ -265,7 +318,9 @@ impl<'a, 'b, BP, BI> Drop for Checked<'a, 'b, BP, BI>
 	      BI: AsRef<[u8]> + 'b
 {
 	fn drop(&mut self) {
-		self.packet.update_checksum(self.ip).unwrap();
+		if !self.incremental {
+			self.packet.update_checksum(self.ip).unwrap();
+		}
 	}
 }
 
@@ -324,4 +379,26 @@ mod test {
 		assert_eq!(udp.destination(), 9001);
 		assert!(udp.is_valid(&ip));
 	}
+
+	#[test]
+	fn mutable_incremental() {
+		let mut raw = [0x45u8, 0x00, 0x00, 0x42, 0x47, 0x07, 0x40, 0x00, 0x40, 0x11, 0x6e, 0xcc, 0xc0, 0xa8, 0x01, 0x89, 0xc0, 0xa8, 0x01, 0xfe, 0xba, 0x2f, 0x00, 0x35, 0x00, 0x2e, 0x1d, 0xf8, 0xbc, 0x81, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x61, 0x70, 0x69, 0x0c, 0x73, 0x74, 0x65, 0x61, 0x6d, 0x70, 0x6f, 0x77, 0x65, 0x72, 0x65, 0x64, 0x03, 0x63, 0x6f, 0x6d, 0x00, 0x00, 0x1c, 0x00, 0x01];
+
+		let mut ip    = ip::v4::Packet::new(&mut raw[..]).unwrap();
+		let (ip, udp) = ip.split_mut();
+		let     ip    = ip::Packet::from(ip::v4::Packet::unchecked(ip));
+		let mut udp   = udp::Packet::new(udp).unwrap();
+
+		udp.checked(&ip).incremental()
+			.set_source(1337).unwrap()
+			.set_destination(9001).unwrap();
+
+		assert_eq!(udp.destination(), 9001);
+		assert!(udp.is_valid(&ip));
+
+		// A disabled checksum stays disabled.
+		udp.set_checksum(0).unwrap();
+		udp.checked(&ip).incremental().set_destination(53).unwrap();
+		assert_eq!(udp.checksum(), 0);
+	}
 }